@@ -0,0 +1,50 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use packtool::{
+    arbitrary::{Arbitrary, Unstructured},
+    Packed, Packet, View,
+};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary, value = b"packtool")]
+struct Tag;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary)]
+#[repr(u8)]
+enum Version {
+    V1 = 1,
+    V2 = 2,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary)]
+struct BlockNumber(u32, u32);
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary)]
+struct Header {
+    tag: Tag,
+    version: Version,
+    block_number: BlockNumber,
+    hash: [u8; 32],
+    value: (i64, u16),
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = Unstructured::new(data);
+
+    if let Ok(header) = Header::arbitrary(&mut u) {
+        let mut slice = [0; Header::SIZE];
+        header.unchecked_write_to_slice(&mut slice);
+
+        Header::check(&slice).expect("a written value is always valid");
+        let view = View::<Header>::try_from_slice(&slice).expect("a written value is always valid");
+        assert_eq!(view.unpack(), header);
+    }
+
+    if let Ok(packet) = Packet::<Header>::arbitrary_valid(&mut u) {
+        Header::check(packet.as_ref()).expect("arbitrary packets are always valid");
+    }
+});
@@ -0,0 +1,65 @@
+/*!
+`packtool` re-exported behind a facade module, the way an internal
+crate re-exports its dependencies. The `packtool` crate is renamed in
+the dependencies of this crate so the generated code can only find it
+with `#[packed(crate = "...")]`.
+*/
+
+pub mod codecs {
+    pub use ::facade as packtool;
+}
+
+use codecs::packtool::{Packed, PackedVersions, View};
+
+#[derive(Packed, Debug, PartialEq, Eq, Clone, Copy)]
+#[packed(crate = "crate::codecs::packtool", arbitrary)]
+#[repr(u8)]
+pub enum Version {
+    V1 = 1,
+    V2 = 2,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(crate = "crate::codecs::packtool", value = b"hd", arbitrary)]
+pub struct Magic;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(crate = "crate::codecs::packtool", builder, arbitrary)]
+pub struct HeaderV1 {
+    pub magic: Magic,
+    pub version: Version,
+    pub length: u16,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(crate = "crate::codecs::packtool", size = 12, padding, arbitrary)]
+#[packed(upgrade(HeaderV1))]
+pub struct HeaderV2 {
+    pub magic: Magic,
+    #[packed(version(Version::V2))]
+    pub version: Version,
+    pub length: u32,
+    #[packed(offset = 8, default)]
+    pub flags: u32,
+}
+
+#[derive(Packed)]
+#[packed(crate = "crate::codecs::packtool", ordered, arbitrary)]
+pub struct Key(pub u32, pub [u8; 2]);
+
+#[derive(Packed)]
+#[packed(crate = "crate::codecs::packtool", overlay, arbitrary)]
+pub struct Raw {
+    pub header: HeaderV1,
+    pub bytes: [u8; 5],
+}
+
+#[derive(PackedVersions)]
+#[packed(crate = "crate::codecs::packtool")]
+#[packed(tag(Version), tag_offset = 2, upgrade)]
+pub enum AnyHeader<'a> {
+    #[packed(version(Version::V1))]
+    V1(View<'a, HeaderV1>),
+    #[packed(version(Version::V2))]
+    V2(View<'a, HeaderV2>),
+}
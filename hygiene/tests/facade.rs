@@ -0,0 +1,22 @@
+use packtool_hygiene::{
+    codecs::packtool::Packed, AnyHeader, HeaderV1, HeaderV2, Key, Raw, Version,
+};
+
+#[test]
+fn generated_through_the_facade() {
+    assert_eq!(HeaderV1::SIZE, 5);
+    assert_eq!(HeaderV2::SIZE, 12);
+    assert_eq!(Key::SIZE, 6);
+    assert_eq!(Raw::SIZE, 5);
+
+    let header = HeaderV1::builder()
+        .version(Version::V1)
+        .length(42u16)
+        .build();
+    let raw = Raw::bytes(header.view().try_cast::<Raw>().unwrap());
+    assert_eq!(raw.unpack(), *b"hd\x01\x2a\x00");
+
+    let any = AnyHeader::try_from_slice(header.view().as_slice()).unwrap();
+    assert_eq!(any.tag(), Version::V1);
+    assert_eq!(any.upgrade().length, 42);
+}
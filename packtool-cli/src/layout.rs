@@ -0,0 +1,157 @@
+use packtool::{formats::tar::Header, Inspector, Node, Packed};
+use std::{collections::BTreeMap, fmt, marker::PhantomData, rc::Rc};
+
+/// a layout the inspector can decode a record with
+///
+/// implemented by [`Typed`] for the Rust types deriving [`Packed`] and
+/// by the runtime [`Schema`](crate::Schema).
+pub trait Layout {
+    /// the name of the layout, as given to `--layout`
+    fn name(&self) -> &str;
+
+    /// the size, in bytes, of a record
+    fn size(&self) -> usize;
+
+    /// decode the `slice` of [`Layout::size`] bytes in the `inspector`
+    fn inspect(&self, slice: &[u8], inspector: &mut Inspector);
+}
+
+/// a [`Layout`] decoding the records as the packed type `T`
+pub struct Typed<T> {
+    name: String,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// the layouts known to the inspector, by name
+#[derive(Default, Clone)]
+pub struct Registry {
+    layouts: BTreeMap<String, Rc<dyn Layout>>,
+}
+
+/// the [`Header`] of the tar archives, the checksum is verified once
+/// all the fields are valid
+struct TarHeader;
+
+impl<T: Packed> Typed<T> {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Packed> Layout for Typed<T> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn size(&self) -> usize {
+        T::SIZE
+    }
+
+    fn inspect(&self, slice: &[u8], inspector: &mut Inspector) {
+        T::inspect(slice, inspector)
+    }
+}
+
+impl Layout for TarHeader {
+    fn name(&self) -> &str {
+        "tar-header"
+    }
+
+    fn size(&self) -> usize {
+        Header::SIZE
+    }
+
+    fn inspect(&self, slice: &[u8], inspector: &mut Inspector) {
+        Header::inspect(slice, inspector);
+
+        if Header::check(slice).is_ok() {
+            if let Err(error) = Header::verify(slice) {
+                inspector.error(error);
+            }
+        }
+    }
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the layouts of the `packtool` binary: the integers and the
+    /// [`Header`] of the tar archives
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        registry
+            .register::<u8>("u8")
+            .register::<u16>("u16")
+            .register::<u32>("u32")
+            .register::<u64>("u64")
+            .register::<u128>("u128")
+            .register::<i8>("i8")
+            .register::<i16>("i16")
+            .register::<i32>("i32")
+            .register::<i64>("i64")
+            .register::<i128>("i128")
+            .add(TarHeader);
+        registry
+    }
+
+    /// register the packed type `T` under the given `name`
+    pub fn register<T: Packed + 'static>(&mut self, name: impl Into<String>) -> &mut Self {
+        self.add(Typed::<T>::new(name))
+    }
+
+    /// register the `layout` under its [`Layout::name`], replacing the
+    /// layout previously registered with the same name
+    pub fn add(&mut self, layout: impl Layout + 'static) -> &mut Self {
+        self.layouts
+            .insert(layout.name().to_owned(), Rc::new(layout));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<Rc<dyn Layout>> {
+        self.layouts.get(name).cloned()
+    }
+
+    /// all the registered layouts, sorted by name
+    pub fn layouts(&self) -> impl Iterator<Item = &dyn Layout> {
+        self.layouts.values().map(|layout| layout.as_ref())
+    }
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.layouts.keys()).finish()
+    }
+}
+
+/// decode the `slice` as a record of the `layout`
+///
+/// this is [`View::inspect`] for the layouts only known at runtime: the
+/// size of the slice is checked and the record decoded even if it is
+/// not valid.
+///
+/// [`View::inspect`]: packtool::View::inspect
+pub fn inspect(layout: &dyn Layout, slice: &[u8]) -> Node {
+    let mut inspector = Inspector::new(layout.size());
+
+    if slice.len() != layout.size() {
+        inspector.error(packtool::Error::Message(
+            format!(
+                "Invalid size for {}: expected {} bytes but received {} bytes",
+                layout.name(),
+                layout.size(),
+                slice.len()
+            )
+            .into(),
+        ));
+    }
+    if let Some(slice) = slice.get(..layout.size()) {
+        layout.inspect(slice, &mut inspector);
+    }
+
+    inspector.finish()
+}
@@ -0,0 +1,246 @@
+/*!
+`packtool`, the command line inspector of binary files
+
+the file is decoded against a layout and printed as an annotated tree:
+the offset of every field, its size, its bytes and its decoded value.
+The fields failing their validation are highlighted with the error and
+its causes.
+
+```text
+$ packtool --layout tar-header archive.tar
+0x00000000  tar-header        512
+0x00000000    name            100  61 72 63 68 69 76 65 2f …  "archive/"
+0x00000064    mode              8  30 30 30 30 37 35 35 00    493 (0o755)
+...
+```
+
+* `--offset` starts decoding at the given byte of the file;
+* `--repeat` decodes consecutive records, `0` decodes up to the end of
+  the file;
+* `--json` prints the records as JSON;
+* `--list` prints the known layouts.
+
+# Layouts
+
+the layouts are either the Rust types registered in the [`Registry`],
+or a [`Schema`] loaded at runtime with `--schema`. The `packtool`
+binary only knows the integers and the tar header. A small host crate
+can register its own packed types and reuse this command line:
+
+```no_run
+use packtool::Packed;
+use packtool_cli::Registry;
+
+#[derive(Packed)]
+pub struct Record {
+    kind: u8,
+    length: u32,
+}
+
+fn main() -> std::process::ExitCode {
+    let mut registry = Registry::builtin();
+    registry.register::<Record>("record");
+
+    packtool_cli::main(registry)
+}
+```
+*/
+
+mod layout;
+mod render;
+mod schema;
+
+pub use self::{
+    layout::{inspect, Layout, Registry, Typed},
+    render::{json, text, Record},
+    schema::Schema,
+};
+use clap::{Parser, ValueEnum};
+use std::{
+    fs,
+    io::{self, IsTerminal as _},
+    path::PathBuf,
+    process::ExitCode,
+    rc::Rc,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Cannot read {path}: {error}", path = path.display())]
+    Read { path: PathBuf, error: io::Error },
+
+    #[error("Cannot write the output: {0}")]
+    Write(#[from] io::Error),
+
+    #[error("Invalid schema (line {line}): {reason}")]
+    Schema { line: usize, reason: String },
+
+    #[error("Unknown layout `{0}`, see `packtool --list`")]
+    UnknownLayout(String),
+
+    #[error("Missing layout, use `--layout` or `--schema`")]
+    MissingLayout,
+
+    #[error("Offset {offset} is past the end of the file ({length} bytes)")]
+    OutOfBounds { offset: usize, length: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Color {
+    /// only if the output is a terminal
+    Auto,
+    Always,
+    Never,
+}
+
+/// decode a binary file against a packed layout
+#[derive(Debug, Parser)]
+#[command(name = "packtool", version)]
+pub struct Args {
+    /// the file to inspect
+    #[arg(required_unless_present = "list")]
+    pub file: Option<PathBuf>,
+
+    /// the name of a registered layout, see `--list`
+    #[arg(short, long, conflicts_with = "schema")]
+    pub layout: Option<String>,
+
+    /// the file describing the layout, one `name: type` field per line
+    #[arg(short, long)]
+    pub schema: Option<PathBuf>,
+
+    /// the byte of the file to start decoding at (`0x` for hexadecimal)
+    #[arg(long, default_value = "0", value_parser = parse_offset)]
+    pub offset: usize,
+
+    /// the number of consecutive records to decode, `0` to decode up to
+    /// the end of the file
+    #[arg(long, default_value_t = 1)]
+    pub repeat: usize,
+
+    /// print the records as JSON
+    #[arg(long)]
+    pub json: bool,
+
+    /// print the known layouts and their size
+    #[arg(long)]
+    pub list: bool,
+
+    /// highlight the invalid fields in red
+    #[arg(long, value_enum, default_value_t = Color::Auto)]
+    pub color: Color,
+}
+
+fn parse_offset(offset: &str) -> Result<usize, String> {
+    let result = match offset.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => offset.parse(),
+    };
+    result.map_err(|error| error.to_string())
+}
+
+/// run the inspector with the given `args`, writing the output in `out`
+///
+/// returns `false` if any of the decoded records is not valid.
+pub fn run(registry: &Registry, args: &Args, out: &mut impl io::Write) -> Result<bool, Error> {
+    if args.list {
+        let width = registry
+            .layouts()
+            .map(|layout| layout.name().len())
+            .max()
+            .unwrap_or_default();
+        for layout in registry.layouts() {
+            writeln!(
+                out,
+                "{:<width$}  {} bytes",
+                layout.name(),
+                layout.size(),
+                width = width
+            )?;
+        }
+        return Ok(true);
+    }
+
+    let layout: Rc<dyn Layout> = match (&args.schema, &args.layout) {
+        (Some(schema), _) => Rc::new(Schema::load(schema, registry)?),
+        (None, Some(name)) => registry
+            .get(name)
+            .ok_or_else(|| Error::UnknownLayout(name.clone()))?,
+        (None, None) => return Err(Error::MissingLayout),
+    };
+
+    let path = args.file.clone().unwrap_or_default();
+    let bytes = fs::read(&path).map_err(|error| Error::Read { path, error })?;
+    if args.offset > bytes.len() {
+        return Err(Error::OutOfBounds {
+            offset: args.offset,
+            length: bytes.len(),
+        });
+    }
+
+    let mut valid = true;
+    let mut records = Vec::new();
+    let mut position = args.offset;
+    let mut count = 0;
+    let mut fields = None;
+    while (args.repeat == 0 && position < bytes.len()) || count < args.repeat {
+        let end = bytes.len().min(position + layout.size());
+        let record = Record {
+            layout: layout.name(),
+            position,
+            bytes: &bytes[position..end],
+            node: inspect(layout.as_ref(), &bytes[position..end]),
+        };
+        valid &= record.node.is_valid();
+
+        if args.json {
+            records.push(json(&record));
+        } else {
+            // one line per record unless the layout has fields
+            let fields = *fields.get_or_insert(!record.node.children().is_empty());
+            if count > 0 && fields {
+                writeln!(out)?;
+            }
+            text(out, &record, args.color == Color::Always)?;
+        }
+
+        position = end;
+        count += 1;
+        // nothing left to decode after a truncated record
+        if record.bytes.len() < layout.size() || layout.size() == 0 {
+            break;
+        }
+    }
+
+    if args.json {
+        serde_json::to_writer_pretty(&mut *out, &records).map_err(io::Error::from)?;
+        writeln!(out)?;
+    }
+
+    Ok(valid)
+}
+
+/// the `packtool` command line with the layouts of the `registry`
+///
+/// the exit code is `1` if a record is not valid and `2` on errors.
+pub fn main(registry: Registry) -> ExitCode {
+    let mut args = Args::parse();
+    if args.color == Color::Auto {
+        args.color = if io::stdout().is_terminal() {
+            Color::Always
+        } else {
+            Color::Never
+        };
+    }
+
+    match run(&registry, &args, &mut io::stdout().lock()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        // the output was closed early, by `head` for example
+        Err(Error::Write(error)) if error.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,6 @@
+use packtool_cli::Registry;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    packtool_cli::main(Registry::builtin())
+}
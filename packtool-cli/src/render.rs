@@ -0,0 +1,141 @@
+use packtool::{Node, Segment};
+use serde_json::{json, Value};
+use std::{error, io};
+
+/// the number of bytes printed before eliding the rest
+const HEX_BYTES: usize = 8;
+
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+/// a record decoded from the inspected file
+pub struct Record<'a> {
+    pub layout: &'a str,
+    /// position of the record in the file
+    pub position: usize,
+    pub bytes: &'a [u8],
+    pub node: Node,
+}
+
+/// the error followed by its causes
+fn chain(error: &dyn error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+fn label(record: &Record<'_>, node: &Node) -> String {
+    match node.segment() {
+        None => record.layout.to_owned(),
+        Some(Segment::Field(field)) => field.to_owned(),
+        Some(Segment::Index(index)) => index.to_string(),
+    }
+}
+
+fn rows<'a>(node: &'a Node, depth: usize, rows: &mut Vec<(usize, &'a Node)>) {
+    rows.push((depth, node));
+    for child in node.children() {
+        self::rows(child, depth + 1, rows);
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut hex = bytes
+        .iter()
+        .take(HEX_BYTES)
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if bytes.len() > HEX_BYTES {
+        hex.push_str(" …");
+    }
+    hex
+}
+
+/// print the record as an annotated tree: the offset in the file, the
+/// name and size of the field, its first bytes and its decoded value.
+/// The invalid fields are marked with `✗`, in red if `color` is set.
+pub fn text(out: &mut impl io::Write, record: &Record<'_>, color: bool) -> io::Result<()> {
+    let mut all = Vec::new();
+    rows(&record.node, 0, &mut all);
+
+    let width = all
+        .iter()
+        .map(|(depth, node)| depth * 2 + label(record, node).chars().count())
+        .max()
+        .unwrap_or_default();
+    let hex_width = HEX_BYTES * 3 + 1;
+
+    for (depth, node) in all {
+        let name = format!("{:indent$}{}", "", label(record, node), indent = depth * 2);
+        let bytes = if node.children().is_empty() {
+            hex(node.bytes(record.bytes))
+        } else {
+            String::new()
+        };
+        let mut line = format!(
+            "0x{:08x}  {:<width$}  {:>5}  {:<hex_width$}",
+            record.position + node.offset(),
+            name,
+            node.size(),
+            bytes,
+            width = width,
+            hex_width = hex_width,
+        );
+
+        if let Some(value) = node.value() {
+            line.push_str("  ");
+            line.push_str(value);
+        }
+        if let Some(error) = node.error() {
+            let error = format!("✗ {}", chain(error));
+            line.push_str("  ");
+            if color {
+                line.push_str(RED);
+                line.push_str(&error);
+                line.push_str(RESET);
+            } else {
+                line.push_str(&error);
+            }
+        }
+
+        writeln!(out, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+fn node(record: &Record<'_>, node: &Node) -> Value {
+    let name = match node.segment() {
+        None => json!(record.layout),
+        Some(Segment::Field(field)) => json!(field),
+        Some(Segment::Index(index)) => json!(index),
+    };
+
+    json!({
+        "name": name,
+        "offset": record.position + node.offset(),
+        "size": node.size(),
+        "bytes": hex::encode(node.bytes(record.bytes)),
+        "value": node.value(),
+        "error": node.error().map(|error| chain(error)),
+        "children": node
+            .children()
+            .iter()
+            .map(|child| self::node(record, child))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// the record as a JSON object, the tree of fields has the same
+/// information as the [`text`] output with all the bytes of the fields
+pub fn json(record: &Record<'_>) -> Value {
+    let mut value = node(record, &record.node);
+    value["valid"] = json!(record.node.is_valid());
+    value
+}
@@ -0,0 +1,247 @@
+use crate::{Error, Layout, Registry};
+use packtool::{Inspector, Segment};
+use std::{fmt, fs, path::Path, rc::Rc};
+
+/// a [`Layout`] described at runtime, one field per line
+///
+/// ```text
+/// # a comment
+/// magic: [4]          # 4 raw bytes
+/// version: u16        # little endian integer
+/// length: u32be       # big endian integer
+/// name: str[16]       # NUL padded UTF-8 string
+/// header: tar-header  # any layout of the registry
+/// ```
+///
+/// the integers are `u8` to `u128` and `i8` to `i128`, little endian
+/// unless suffixed with `be`.
+pub struct Schema {
+    name: String,
+    fields: Vec<Field>,
+    size: usize,
+}
+
+struct Field {
+    name: &'static str,
+    offset: usize,
+    ty: Type,
+}
+
+#[derive(Clone)]
+enum Type {
+    Integer {
+        size: usize,
+        signed: bool,
+        big_endian: bool,
+    },
+    Bytes(usize),
+    Str(usize),
+    Layout(Rc<dyn Layout>),
+}
+
+impl Schema {
+    /// parse the schema `source`, the other layouts it refers to are
+    /// looked up in the `registry`
+    pub fn parse(
+        name: impl Into<String>,
+        source: &str,
+        registry: &Registry,
+    ) -> Result<Self, Error> {
+        let mut fields: Vec<Field> = Vec::new();
+        let mut size = 0;
+
+        for (index, line) in source.lines().enumerate() {
+            let error = |reason: String| Error::Schema {
+                line: index + 1,
+                reason,
+            };
+
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (name, ty) = line
+                .split_once(':')
+                .ok_or_else(|| error(format!("expecting `name: type` but found `{}`", line)))?;
+            let (name, ty) = (name.trim(), ty.trim());
+
+            if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return Err(error(format!("invalid field name `{}`", name)));
+            }
+            if fields.iter().any(|field| field.name == name) {
+                return Err(error(format!("duplicate field `{}`", name)));
+            }
+            let ty = Type::parse(ty, registry).map_err(error)?;
+            let offset = size;
+            size += ty.size();
+
+            fields.push(Field {
+                // the segments of the inspected nodes are static, a
+                // schema is loaded once for the whole inspection
+                name: Box::leak(name.to_owned().into_boxed_str()),
+                offset,
+                ty,
+            });
+        }
+
+        if fields.is_empty() {
+            return Err(Error::Schema {
+                line: 0,
+                reason: "the schema has no field".to_owned(),
+            });
+        }
+
+        Ok(Self {
+            name: name.into(),
+            fields,
+            size,
+        })
+    }
+
+    /// load the schema at `path`, the name of the layout is the name of
+    /// the file without its extension
+    pub fn load(path: impl AsRef<Path>, registry: &Registry) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(|error| Error::Read {
+            path: path.to_owned(),
+            error,
+        })?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Self::parse(name, &source, registry)
+    }
+}
+
+impl Layout for Schema {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn inspect(&self, slice: &[u8], inspector: &mut Inspector) {
+        for field in self.fields.iter() {
+            let range = field.offset..field.offset + field.ty.size();
+            inspector.nested(
+                Segment::Field(field.name),
+                field.offset,
+                field.ty.size(),
+                |inspector| field.ty.inspect(&slice[range], inspector),
+            );
+        }
+    }
+}
+
+impl fmt::Debug for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Schema")
+            .field("name", &self.name)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl Type {
+    fn parse(ty: &str, registry: &Registry) -> Result<Self, String> {
+        let length = |length: &str| match length.parse::<usize>() {
+            Ok(0) | Err(_) => Err(format!("invalid length `{}` in `{}`", length, ty)),
+            Ok(length) => Ok(length),
+        };
+
+        if let Some(bytes) = ty.strip_prefix('[').and_then(|ty| ty.strip_suffix(']')) {
+            return length(bytes).map(Self::Bytes);
+        }
+        if let Some(string) = ty.strip_prefix("str[").and_then(|ty| ty.strip_suffix(']')) {
+            return length(string).map(Self::Str);
+        }
+
+        let (integer, big_endian) = match ty.strip_suffix("be") {
+            Some(integer) => (integer, true),
+            None => (ty, false),
+        };
+        let signed = integer.starts_with('i');
+        match integer
+            .get(1..)
+            .filter(|_| signed || integer.starts_with('u'))
+        {
+            Some("8") | Some("16") | Some("32") | Some("64") | Some("128") => {
+                let bits = integer[1..]
+                    .parse::<usize>()
+                    .expect("a valid number of bits");
+                return Ok(Self::Integer {
+                    size: bits / 8,
+                    signed,
+                    big_endian,
+                });
+            }
+            _ => (),
+        }
+
+        registry
+            .get(ty)
+            .map(Self::Layout)
+            .ok_or_else(|| format!("unknown type `{}`", ty))
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::Integer { size, .. } => *size,
+            Self::Bytes(size) | Self::Str(size) => *size,
+            Self::Layout(layout) => layout.size(),
+        }
+    }
+
+    fn inspect(&self, slice: &[u8], inspector: &mut Inspector) {
+        match self {
+            Self::Integer {
+                size,
+                signed,
+                big_endian,
+            } => {
+                let fold = |value: u128, byte: &u8| value << 8 | *byte as u128;
+                let value = if *big_endian {
+                    slice.iter().fold(0, fold)
+                } else {
+                    slice.iter().rev().fold(0, fold)
+                };
+
+                let bits = size * 8;
+                if *signed && bits < 128 && value >> (bits - 1) == 1 {
+                    inspector.value(value as i128 - (1 << bits))
+                } else if *signed {
+                    inspector.value(value as i128)
+                } else {
+                    inspector.value(value)
+                }
+            }
+            // the raw bytes are printed as they are
+            Self::Bytes(_) => (),
+            Self::Str(_) => {
+                let length = slice
+                    .iter()
+                    .position(|byte| *byte == 0)
+                    .unwrap_or(slice.len());
+
+                if slice[length..].iter().any(|byte| *byte != 0) {
+                    inspector.error(packtool::Error::invalid_string::<str>(
+                        "expecting NUL padding after the string",
+                    ));
+                    return;
+                }
+                match std::str::from_utf8(&slice[..length]) {
+                    Ok(string) => inspector.value(format_args!("{:?}", string)),
+                    Err(error) => {
+                        inspector.error(packtool::Error::invalid_string::<str>(error.to_string()))
+                    }
+                }
+            }
+            Self::Layout(layout) => layout.inspect(slice, inspector),
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use clap::Parser as _;
+use packtool::Packed;
+use packtool_cli::{run, Args, Error, Registry, Schema};
+use std::{io::Write as _, path::PathBuf};
+
+const USTAR: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../tests/fixtures/tar/ustar.tar"
+);
+
+#[derive(Packed)]
+#[repr(u8)]
+enum Kind {
+    File = 1,
+    Directory = 2,
+}
+
+#[derive(Packed)]
+struct Entry {
+    kind: Kind,
+    length: u16,
+}
+
+fn file(bytes: &[u8]) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(bytes).unwrap();
+    file
+}
+
+fn inspect(registry: &Registry, args: &[&str]) -> Result<(bool, String), Error> {
+    let args =
+        Args::try_parse_from(std::iter::once("packtool").chain(args.iter().copied())).unwrap();
+    let mut out = Vec::new();
+    let valid = run(registry, &args, &mut out)?;
+    Ok((valid, String::from_utf8(out).unwrap()))
+}
+
+#[test]
+fn list() {
+    let (valid, out) = inspect(&Registry::builtin(), &["--list"]).unwrap();
+    assert!(valid);
+    assert!(out.lines().any(|line| line == "tar-header  512 bytes"));
+    assert!(out.lines().any(|line| line == "u32         4 bytes"));
+}
+
+#[test]
+fn tar_header() {
+    let (valid, out) = inspect(&Registry::builtin(), &["--layout", "tar-header", USTAR]).unwrap();
+    assert!(valid);
+
+    let lines = out.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 16);
+    assert_eq!(lines[0], "0x00000000  tar-header        512");
+    assert_eq!(
+        lines[1],
+        "0x00000000    name            100  61 72 63 68 69 76 65 2f …  \"archive/\""
+    );
+    assert_eq!(
+        lines[2],
+        "0x00000064    mode              8  30 30 30 30 37 35 35 00    493 (0o755)"
+    );
+    assert!(lines[8].ends_with("Directory"));
+    assert!(lines[10].ends_with("Posix"));
+}
+
+#[test]
+fn invalid_fields() {
+    let mut archive = std::fs::read(USTAR).unwrap();
+    // the size of the first entry
+    archive[124] = b'9';
+    let archive = file(&archive);
+
+    let (valid, out) = inspect(
+        &Registry::builtin(),
+        &["-l", "tar-header", archive.path().to_str().unwrap()],
+    )
+    .unwrap();
+    assert!(!valid);
+    assert!(!out.contains('\x1b'));
+
+    let invalid = out
+        .lines()
+        .filter(|line| line.contains('✗'))
+        .collect::<Vec<_>>();
+    assert_eq!(invalid.len(), 1);
+    assert!(invalid[0].starts_with("0x0000007c    size"));
+    assert!(invalid[0].ends_with("invalid octal digit 0x39 at byte 0"));
+
+    let (_, out) = inspect(
+        &Registry::builtin(),
+        &[
+            "-l",
+            "tar-header",
+            "--color",
+            "always",
+            archive.path().to_str().unwrap(),
+        ],
+    )
+    .unwrap();
+    assert!(out.contains("\x1b[31m✗"));
+}
+
+#[test]
+fn checksum() {
+    let mut archive = std::fs::read(USTAR).unwrap();
+    // the mode of the first entry: 0755 -> 0757
+    archive[106] = b'7';
+    let archive = file(&archive);
+
+    let (valid, out) = inspect(
+        &Registry::builtin(),
+        &["-l", "tar-header", archive.path().to_str().unwrap()],
+    )
+    .unwrap();
+    assert!(!valid);
+
+    let root = out.lines().next().unwrap();
+    assert!(root.starts_with("0x00000000  tar-header"));
+    assert!(root.ends_with("the checksum is 10665 but the header sums to 10667"));
+}
+
+#[test]
+fn offset_and_repeat() {
+    let (valid, out) = inspect(
+        &Registry::builtin(),
+        &["-l", "u16", "--offset", "0x200", "--repeat", "2", USTAR],
+    )
+    .unwrap();
+    assert!(valid);
+    assert_eq!(
+        out,
+        "0x00000200  u16      2  61 72                      29281\n\
+         0x00000202  u16      2  63 68                      26723\n"
+    );
+}
+
+#[test]
+fn repeat_until_the_end() {
+    let records = file(&[1, 0x2a, 0x00, 2, 0x10, 0x00, 3, 0x00, 0x00, 1]);
+    let mut registry = Registry::new();
+    registry.register::<Entry>("entry");
+
+    let (valid, out) = inspect(
+        &registry,
+        &[
+            "-l",
+            "entry",
+            "--repeat",
+            "0",
+            records.path().to_str().unwrap(),
+        ],
+    )
+    .unwrap();
+    assert!(!valid);
+
+    let records = out.split("\n\n").collect::<Vec<_>>();
+    assert_eq!(records.len(), 4);
+    assert!(records[0]
+        .lines()
+        .nth(1)
+        .unwrap()
+        .ends_with("01                         File"));
+    assert!(records[1]
+        .lines()
+        .nth(2)
+        .unwrap()
+        .ends_with("10 00                      16"));
+    assert!(records[2].contains("0x00000006    kind"));
+    assert!(records[2].contains("✗ Invalid discriminant"));
+    assert!(records[3].starts_with("0x00000009  entry"));
+    assert!(records[3].contains("✗ Invalid size for entry: expected 3 bytes but received 1 bytes"));
+}
+
+#[test]
+fn json() {
+    let (valid, out) =
+        inspect(&Registry::builtin(), &["-l", "tar-header", "--json", USTAR]).unwrap();
+    assert!(valid);
+
+    let records: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(records.as_array().unwrap().len(), 1);
+
+    let header = &records[0];
+    assert_eq!(header["name"], "tar-header");
+    assert_eq!(header["valid"], true);
+    assert_eq!(header["size"], 512);
+
+    let mode = &header["children"][1];
+    assert_eq!(mode["name"], "mode");
+    assert_eq!(mode["offset"], 100);
+    assert_eq!(mode["bytes"], "3030303037353500");
+    assert_eq!(mode["value"], "493 (0o755)");
+    assert_eq!(mode["error"], serde_json::Value::Null);
+}
+
+#[test]
+fn schema() {
+    let schema = tempfile::Builder::new()
+        .suffix(".schema")
+        .tempfile()
+        .unwrap();
+    std::fs::write(
+        schema.path(),
+        "# the header of the records\n\
+         magic: [2]\n\
+         length: u32be   # big endian\n\
+         delta: i16\n\
+         name: str[6]\n\
+         entry: entry\n",
+    )
+    .unwrap();
+    let records = file(b"PK\x00\x00\x01\x00\xfe\xffhello\x00\x01\x2a\x00");
+
+    let mut registry = Registry::builtin();
+    registry.register::<Entry>("entry");
+
+    let (valid, out) = inspect(
+        &registry,
+        &[
+            "--schema",
+            schema.path().to_str().unwrap(),
+            records.path().to_str().unwrap(),
+        ],
+    )
+    .unwrap();
+    assert!(valid);
+
+    let lines = out.lines().collect::<Vec<_>>();
+    assert!(lines[0].ends_with("17"));
+    assert_eq!(lines[1], "0x00000000    magic         2  50 4b");
+    assert!(lines[2].ends_with("00 00 01 00                256"));
+    assert!(lines[3].ends_with("-2"));
+    assert!(lines[4].ends_with("\"hello\""));
+    assert!(lines[6].starts_with("0x0000000e      kind"));
+    assert!(lines[6].ends_with("File"));
+    assert!(lines[7].ends_with("42"));
+}
+
+#[test]
+fn invalid_schemas() {
+    let registry = Registry::builtin();
+    let line = |source: &str| match Schema::parse("test", source, &registry) {
+        Err(Error::Schema { line, .. }) => line,
+        Err(error) => panic!("unexpected error: {}", error),
+        Ok(_) => panic!("the schema is valid"),
+    };
+
+    assert_eq!(line(""), 0);
+    assert_eq!(line("a: u8\nb u8"), 2);
+    assert_eq!(line("a: u8\n\n# comment\na: u16"), 4);
+    assert_eq!(line("a: u24"), 1);
+    assert_eq!(line("a: [0]"), 1);
+    assert_eq!(line("a: str[x]"), 1);
+    assert_eq!(line("a-b: u8"), 1);
+    assert_eq!(line("a: unknown"), 1);
+}
+
+#[test]
+fn errors() {
+    let registry = Registry::builtin();
+
+    assert!(matches!(
+        inspect(&registry, &["-l", "unknown", USTAR]),
+        Err(Error::UnknownLayout(name)) if name == "unknown"
+    ));
+    assert!(matches!(
+        inspect(&registry, &[USTAR]),
+        Err(Error::MissingLayout)
+    ));
+    assert!(matches!(
+        inspect(&registry, &["-l", "u8", "--offset", "100000", USTAR]),
+        Err(Error::OutOfBounds {
+            offset: 100_000,
+            ..
+        })
+    ));
+
+    let missing = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("missing.bin");
+    assert!(matches!(
+        inspect(&registry, &["-l", "u8", missing.to_str().unwrap()]),
+        Err(Error::Read { .. })
+    ));
+}
@@ -0,0 +1,150 @@
+/*!
+generate the `arbitrary::Arbitrary` and `ArbitraryPacked` implementations
+of the packed types marked with `#[packed(arbitrary)]`
+
+the bytes are generated directly and are always valid: enumerations
+only write the declared variants and the unit types are always set to
+their expected `value`. The overlays are generated from the bytes of
+one member at a time, until the whole overlay passes its check (the
+other members of a `check = "all"` overlay may reject these bytes).
+`Arbitrary` reads the value back from these bytes.
+*/
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    ast::{Container, Data, PackedField},
+    expand::{field_start, unit_constructor},
+};
+
+pub fn expand(container: &Container) -> TokenStream {
+    if container.attributes.arbitrary.is_none() {
+        return quote! {};
+    }
+
+    let ident = container.ident();
+    let ordered = container.attributes.ordered.is_some();
+    let body = match &container.data {
+        _ if container.attributes.overlay.is_some() => expand_overlay(container),
+        Data::Unit(unit) => {
+            let constructor = unit_constructor(ident, &unit.from);
+            quote! {
+                __packtool::Packed::unchecked_write_to_slice(&#constructor, slice);
+            }
+        }
+        Data::Tuple(tuple) => expand_fields(&tuple.fields, ordered),
+        Data::Struct(structure) => expand_fields(&structure.fields, ordered),
+        Data::Enum(enumeration) => {
+            let last = enumeration.variants.len() - 1;
+            let variants = enumeration
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(index, variant)| {
+                    let variant = &variant.ident;
+                    quote! { #index => #ident :: #variant }
+                });
+
+            quote! {
+                let variant = match u.int_in_range(0..=#last)? {
+                    #( #variants, )*
+                    _ => ::core::unreachable!(),
+                };
+                __packtool::Packed::unchecked_write_to_slice(&variant, slice);
+            }
+        }
+    };
+
+    quote! {
+        impl __packtool::ArbitraryPacked for #ident {
+            #[allow(unused_variables)]
+            fn arbitrary_write(
+                u: &mut __packtool::arbitrary::Unstructured<'_>,
+                slice: &mut [u8],
+            ) -> __packtool::arbitrary::Result<()> {
+                #body
+                ::core::result::Result::Ok(())
+            }
+        }
+
+        impl<'arbitrary> __packtool::arbitrary::Arbitrary<'arbitrary> for #ident {
+            fn arbitrary(
+                u: &mut __packtool::arbitrary::Unstructured<'arbitrary>,
+            ) -> __packtool::arbitrary::Result<Self> {
+                let mut bytes = <<Self as __packtool::Packed>::Bytes as __packtool::ByteArray>::zeroed(
+                    <Self as __packtool::Packed>::SIZE,
+                );
+                <Self as __packtool::ArbitraryPacked>::arbitrary_write(
+                    u,
+                    ::core::convert::AsMut::<[u8]>::as_mut(&mut bytes),
+                )?;
+                ::core::result::Result::Ok(
+                    <Self as __packtool::Packed>::unchecked_read_from_slice(
+                        ::core::convert::AsRef::<[u8]>::as_ref(&bytes),
+                    ),
+                )
+            }
+        }
+    }
+}
+
+fn expand_overlay(container: &Container) -> TokenStream {
+    let fields = match crate::overlay::fields(container) {
+        Some(fields) => fields,
+        None => return quote! { ::core::unreachable!(); },
+    };
+    let (last, fields) = match fields.split_last() {
+        Some((last, fields)) => (&last.ty, fields.iter().map(|field| &field.ty)),
+        None => return quote! {},
+    };
+
+    quote! {
+        #(
+            <#fields as __packtool::ArbitraryPacked>::arbitrary_write(
+                u,
+                &mut slice[..<#fields as __packtool::Packed>::SIZE],
+            )?;
+            if <Self as __packtool::Packed>::check(slice).is_ok() {
+                return ::core::result::Result::Ok(());
+            }
+            for byte in slice.iter_mut() {
+                *byte = 0;
+            }
+        )*
+        <#last as __packtool::ArbitraryPacked>::arbitrary_write(
+            u,
+            &mut slice[..<#last as __packtool::Packed>::SIZE],
+        )?;
+        if <Self as __packtool::Packed>::check(slice).is_err() {
+            return ::core::result::Result::Err(
+                __packtool::arbitrary::Error::IncorrectFormat,
+            );
+        }
+    }
+}
+
+fn expand_fields<'a, I>(fields: I, ordered: bool) -> TokenStream
+where
+    I: IntoIterator<Item = &'a PackedField>,
+{
+    let write = if ordered {
+        quote! { arbitrary_write_ordered }
+    } else {
+        quote! { arbitrary_write }
+    };
+
+    let mut writes = Vec::new();
+    let mut start = quote! { 0 };
+    for field in fields {
+        let ty = &field.ty;
+        start = field_start(field, start);
+        let end = quote! { #start + <#ty as __packtool::Packed>::SIZE };
+        writes.push(quote! {
+            <#ty as __packtool::ArbitraryPacked>::#write(u, &mut slice[(#start)..(#end)])?;
+        });
+        start = end;
+    }
+
+    quote! { #(#writes)* }
+}
@@ -15,7 +15,6 @@ pub enum Data {
     Tuple(PackedTuple),
     Struct(PackedStruct),
     Enum(PackedEnum),
-    //Union(PackedUnion),
 }
 
 impl Data {
@@ -47,7 +46,13 @@ impl Parse for Container {
 
 impl Parse for Data {
     fn parse(input: ParseStream) -> Result<Self> {
-        if input.peek(Token!(struct)) {
+        if (input.peek(Token!(struct)) || input.peek(Token!(enum))) && input.peek3(Token!(<)) {
+            Err(syn::Error::new(
+                input.span(),
+                "generic types cannot be packed, the layout needs to be known: \
+                implement `Packed` manually for each of the concrete types",
+            ))
+        } else if input.peek(Token!(struct)) {
             if input.peek3(Token!(;)) {
                 input.parse().map(Data::Unit)
             } else if input.peek3(syn::token::Paren) {
@@ -76,7 +81,10 @@ impl Parse for Data {
                 Ok(Data::Enum(enumeration))
             }
         } else if input.peek(Token!(union)) {
-            todo!("learn to parse union")
+            Err(syn::Error::new(
+                input.span(),
+                "unions cannot be packed, use a structure with #[packed(overlay)] instead: every field views the same bytes",
+            ))
         } else {
             Err(syn::Error::new(input.span(), "not handled by `packtool`"))
         }
@@ -1,7 +1,8 @@
 use syn::{
     parse::{Parse, ParseStream},
+    punctuated::Punctuated,
     spanned::Spanned,
-    Result,
+    Result, Token,
 };
 
 #[derive(Default)]
@@ -9,14 +10,57 @@ pub struct PackedAttributes {
     pub value: Option<ValueType>,
     pub repr: Option<syn::Path>,
     pub accessor: AccessorType,
+    pub accessor_span: Option<proc_macro2::Span>,
+    pub arbitrary: Option<syn::Path>,
+    pub quickcheck: Option<syn::Path>,
+    pub proptest: Option<syn::Path>,
+    pub builder: Option<syn::Path>,
+    pub ordered: Option<syn::Path>,
+    pub default: Option<DefaultValue>,
+    pub upgrade: Option<syn::Path>,
+    pub upgrade_from: Option<syn::Path>,
+    pub tag: Option<syn::Path>,
+    pub tag_type: Option<syn::Type>,
+    pub skip: Vec<syn::Path>,
+    pub tag_offset: Option<syn::LitInt>,
+    pub version: Option<syn::Path>,
+    pub size: Option<syn::LitInt>,
+    pub offset: Option<syn::LitInt>,
+    pub padding: Option<syn::Path>,
+    pub overlay: Option<syn::Path>,
+    pub check: Option<OverlayCheck>,
+    pub krate: Option<syn::Path>,
+    /// the invalid attributes, reported with the other diagnostics
+    /// of the container
+    pub errors: Vec<syn::Error>,
 }
 
+#[derive(Default)]
 pub enum AccessorType {
+    #[default]
     Default,
     Ignore,
     Custom(syn::Ident),
 }
 
+/// the value of a field that did not exist in the previous version
+/// of a structure (see `#[packed(upgrade(...))]`)
+pub enum DefaultValue {
+    /// `#[packed(default)]`: use `Default::default()`
+    Default(syn::Path),
+    /// `#[packed(default = "expr")]`: use the given expression
+    Expr(syn::LitStr, proc_macro2::TokenStream),
+}
+
+/// how the members of an overlay decide of the validity of the bytes
+/// (see `#[packed(overlay)]`)
+pub enum OverlayCheck {
+    /// `#[packed(check = "all")]`: every member must be valid (default)
+    All(syn::LitStr),
+    /// `#[packed(check = "any")]`: at least one member must be valid
+    Any(syn::LitStr),
+}
+
 pub enum ValueType {
     Lit(syn::Lit),
     Const(syn::Path),
@@ -26,16 +70,78 @@ enum PackedAttribute {
     Value(ValueType),
     Repr(syn::Path),
     Accessor(proc_macro2::Span, AccessorType),
+    Arbitrary(syn::Path),
+    QuickCheck(syn::Path),
+    PropTest(syn::Path),
+    Builder(syn::Path),
+    Ordered(syn::Path),
+    Default(DefaultValue),
+    Upgrade(syn::Path),
+    UpgradeFrom(syn::Path),
+    Tag(syn::Path),
+    TagOffset(syn::LitInt),
+    Version(syn::Path),
+    Size(syn::LitInt),
+    Offset(syn::LitInt),
+    Padding(syn::Path),
+    Overlay(syn::Path),
+    Check(OverlayCheck),
+    Crate(syn::Path),
+    TagType(syn::Type),
+    Skip(Vec<syn::Path>),
+}
+
+/// an entry of the attribute list: the usual `syn::NestedMeta` or the
+/// wire type of the discriminant of an enum (`tag = u16`) that is not
+/// a literal and is not a valid `syn::Meta`.
+enum Entry {
+    TagType(syn::Path, syn::Type),
+    Nested(syn::NestedMeta),
 }
 
-const ATTRIBUTE_LIST: &[&str] = &[PackedAttribute::VALUE, PackedAttribute::ACCESSOR];
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let fork = input.fork();
+        let is_tag_type = fork
+            .parse::<syn::Path>()
+            .map(|path| path.is_ident(PackedAttribute::TAG))
+            .unwrap_or(false)
+            && fork.peek(Token![=])
+            && !fork.peek2(syn::Lit);
 
-impl Default for AccessorType {
-    fn default() -> Self {
-        Self::Default
+        if is_tag_type {
+            let path = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let ty = input.parse()?;
+            Ok(Self::TagType(path, ty))
+        } else {
+            input.parse().map(Self::Nested)
+        }
     }
 }
 
+const ATTRIBUTE_LIST: &[&str] = &[
+    PackedAttribute::VALUE,
+    PackedAttribute::ACCESSOR,
+    PackedAttribute::ARBITRARY,
+    PackedAttribute::QUICKCHECK,
+    PackedAttribute::PROPTEST,
+    PackedAttribute::BUILDER,
+    PackedAttribute::ORDERED,
+    PackedAttribute::DEFAULT,
+    PackedAttribute::UPGRADE,
+    PackedAttribute::TAG,
+    PackedAttribute::TAG_OFFSET,
+    PackedAttribute::VERSION,
+    PackedAttribute::SIZE,
+    PackedAttribute::OFFSET,
+    PackedAttribute::PADDING,
+    PackedAttribute::OVERLAY,
+    PackedAttribute::CHECK,
+    PackedAttribute::CRATE,
+    PackedAttribute::SKIP,
+];
+
 impl ValueType {
     pub fn span(&self) -> proc_macro2::Span {
         match self {
@@ -46,98 +152,245 @@ impl ValueType {
 }
 
 impl PackedAttributes {
-    fn from_iter<T>(attributes: T) -> Result<Self>
-    where
-        T: IntoIterator<Item = PackedAttribute>,
-    {
+    /// the path to the `packtool` crate in the generated code: set with
+    /// `#[packed(crate = "...")]` when packtool is re-exported by another
+    /// crate.
+    pub fn crate_path(&self) -> proc_macro2::TokenStream {
+        if let Some(path) = self.krate.as_ref() {
+            quote::quote! { #path }
+        } else {
+            quote::quote! { ::packtool }
+        }
+    }
+}
+
+impl OverlayCheck {
+    pub fn span(&self) -> proc_macro2::Span {
+        match self {
+            Self::All(lit) | Self::Any(lit) => lit.span(),
+        }
+    }
+}
+
+impl DefaultValue {
+    pub fn span(&self) -> proc_macro2::Span {
+        match self {
+            Self::Default(path) => path.span(),
+            Self::Expr(lit, _) => lit.span(),
+        }
+    }
+}
+
+/// set the attribute `slot` to `value`, fails if it was already set
+fn set_once<T>(slot: &mut Option<T>, value: T, span: proc_macro2::Span, name: &str) -> Result<()> {
+    if slot.is_some() {
+        Err(syn::Error::new(span, format!("{} was already set", name)))
+    } else {
+        *slot = Some(value);
+        Ok(())
+    }
+}
+
+impl PackedAttributes {
+    /// parse the `#[packed(...)]` and `#[repr(...)]` attributes from
+    /// already parsed attributes.
+    ///
+    /// the invalid attributes are kept in `errors` so they are all
+    /// reported at once, along with the other diagnostics.
+    pub fn from_attributes(attributes: &[syn::Attribute]) -> Self {
         let mut result = PackedAttributes::default();
 
+        let attributes = attributes
+            .iter()
+            .filter(|attr| attr.path.is_ident("packed") || attr.path.is_ident("repr"));
         for attribute in attributes {
-            match attribute {
-                PackedAttribute::Value(value) => {
-                    if result.value.is_some() {
-                        return Err(syn::Error::new(value.span(), "Value was already set"));
-                    } else {
-                        result.value = Some(value);
-                    }
-                }
-                PackedAttribute::Repr(path) => {
-                    // leave the understanding of repr to the repr macro
-                    // we only use it to detect if it was set in the
-                    // case of enum with only unit variants
-                    result.repr = Some(path);
-                }
-                PackedAttribute::Accessor(span, accessor) => {
-                    if !matches!(result.accessor, AccessorType::Default) {
-                        return Err(syn::Error::new(span, "The accessor has already been set"));
-                    } else {
-                        result.accessor = accessor;
-                    }
+            for attribute in PackedAttribute::from_attribute(attribute) {
+                if let Err(error) = attribute.and_then(|attribute| result.set(attribute)) {
+                    result.errors.push(error);
                 }
             }
         }
 
-        Ok(result)
+        result
+    }
+
+    fn set(&mut self, attribute: PackedAttribute) -> Result<()> {
+        match attribute {
+            PackedAttribute::Value(value) => {
+                let span = value.span();
+                set_once(&mut self.value, value, span, "value")
+            }
+            PackedAttribute::Repr(path) => {
+                // leave the understanding of repr to the repr macro
+                // we only use it to detect if it was set in the
+                // case of enum with only unit variants
+                self.repr = Some(path);
+                Ok(())
+            }
+            PackedAttribute::Accessor(span, accessor) => {
+                set_once(&mut self.accessor_span, span, span, "accessor")?;
+                self.accessor = accessor;
+                Ok(())
+            }
+            PackedAttribute::Arbitrary(path) => {
+                let span = path.span();
+                set_once(&mut self.arbitrary, path, span, "arbitrary")
+            }
+            PackedAttribute::QuickCheck(path) => {
+                let span = path.span();
+                set_once(&mut self.quickcheck, path, span, "quickcheck")
+            }
+            PackedAttribute::PropTest(path) => {
+                let span = path.span();
+                set_once(&mut self.proptest, path, span, "proptest")
+            }
+            PackedAttribute::Builder(path) => {
+                let span = path.span();
+                set_once(&mut self.builder, path, span, "builder")
+            }
+            PackedAttribute::Ordered(path) => {
+                let span = path.span();
+                set_once(&mut self.ordered, path, span, "ordered")
+            }
+            PackedAttribute::Default(default) => {
+                let span = default.span();
+                set_once(&mut self.default, default, span, "default")
+            }
+            PackedAttribute::Upgrade(path) => {
+                let span = path.span();
+                set_once(&mut self.upgrade, path, span, "upgrade")
+            }
+            PackedAttribute::UpgradeFrom(path) => {
+                let span = path.span();
+                set_once(&mut self.upgrade_from, path, span, "upgrade")
+            }
+            PackedAttribute::Tag(path) => {
+                let span = path.span();
+                set_once(&mut self.tag, path, span, "tag")
+            }
+            PackedAttribute::TagOffset(offset) => {
+                let span = offset.span();
+                set_once(&mut self.tag_offset, offset, span, "tag_offset")
+            }
+            PackedAttribute::Size(size) => {
+                let span = size.span();
+                set_once(&mut self.size, size, span, "size")
+            }
+            PackedAttribute::Offset(offset) => {
+                let span = offset.span();
+                set_once(&mut self.offset, offset, span, "offset")
+            }
+            PackedAttribute::Padding(path) => {
+                let span = path.span();
+                set_once(&mut self.padding, path, span, "padding")
+            }
+            PackedAttribute::Overlay(path) => {
+                let span = path.span();
+                set_once(&mut self.overlay, path, span, "overlay")
+            }
+            PackedAttribute::Check(check) => {
+                let span = check.span();
+                set_once(&mut self.check, check, span, "check")
+            }
+            PackedAttribute::Skip(paths) => {
+                self.skip.extend(paths);
+                Ok(())
+            }
+            PackedAttribute::TagType(ty) => {
+                let span = ty.span();
+                set_once(&mut self.tag_type, ty, span, "tag")
+            }
+            PackedAttribute::Crate(path) => {
+                let span = path.span();
+                set_once(&mut self.krate, path, span, "crate")
+            }
+            PackedAttribute::Version(path) => {
+                let span = path.span();
+                set_once(&mut self.version, path, span, "version")
+            }
+        }
     }
 }
 
 impl Parse for PackedAttributes {
     fn parse(input: ParseStream) -> Result<Self> {
-        let attributes = input
-            .call(syn::Attribute::parse_outer)?
-            .into_iter()
-            .filter(|attr| attr.path.is_ident("packed") || attr.path.is_ident("repr"))
-            .map(|attr| attr.parse_meta())
-            .collect::<Result<Vec<_>>>()?
-            .into_iter()
-            .map(PackedAttribute::from);
-
-        PackedAttributes::from_iter(
-            attributes
-                .collect::<Result<Vec<Vec<_>>>>()?
-                .into_iter()
-                .flatten(),
-        )
+        let attributes = input.call(syn::Attribute::parse_outer)?;
+        Ok(PackedAttributes::from_attributes(&attributes))
     }
 }
 
 impl PackedAttribute {
     const VALUE: &'static str = "value";
     const ACCESSOR: &'static str = "accessor";
+    const ARBITRARY: &'static str = "arbitrary";
+    const QUICKCHECK: &'static str = "quickcheck";
+    const PROPTEST: &'static str = "proptest";
+    const BUILDER: &'static str = "builder";
+    const ORDERED: &'static str = "ordered";
+    const DEFAULT: &'static str = "default";
+    const UPGRADE: &'static str = "upgrade";
+    const TAG: &'static str = "tag";
+    const TAG_OFFSET: &'static str = "tag_offset";
+    const VERSION: &'static str = "version";
+    const SIZE: &'static str = "size";
+    const OFFSET: &'static str = "offset";
+    const PADDING: &'static str = "padding";
+    const OVERLAY: &'static str = "overlay";
+    const CHECK: &'static str = "check";
+    const CRATE: &'static str = "crate";
+    const SKIP: &'static str = "skip";
 
-    fn from(meta: syn::Meta) -> Result<Vec<Self>> {
-        match meta {
-            syn::Meta::Path(path) => Err(syn::Error::new_spanned(
-                path,
-                format!(
-                    "expecting a list of packed parameters ({:?})",
-                    ATTRIBUTE_LIST
-                ),
-            )),
-            syn::Meta::NameValue(meta_named_value) => Err(syn::Error::new_spanned(
-                meta_named_value,
-                format!(
-                    "expecting a list of packed parameters ({:?})",
-                    ATTRIBUTE_LIST
-                ),
-            )),
-            syn::Meta::List(meta_list) => {
-                let mut list = Vec::with_capacity(meta_list.nested.len());
-
-                let is_repr = meta_list.path.is_ident("repr");
-
-                for entry in meta_list.nested.into_iter() {
-                    list.push(Self::from_nested(is_repr, entry).map_err(|mut err| {
-                        err.combine(syn::Error::new(
-                            err.span(),
-                            format!("Expecting one of {:?}", ATTRIBUTE_LIST),
-                        ));
-                        err
-                    })?);
-                }
-
-                Ok(list)
+    /// parse the entries of `#[packed(...)]` or `#[repr(...)]`
+    fn from_attribute(attribute: &syn::Attribute) -> Vec<Result<Self>> {
+        let entries = attribute.parse_args_with(Punctuated::<Entry, Token![,]>::parse_terminated);
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(_) if attribute.tokens.is_empty() || attribute.parse_meta().is_ok() => {
+                return vec![Err(syn::Error::new_spanned(
+                    attribute,
+                    format!(
+                        "expecting a list of packed parameters ({:?})",
+                        ATTRIBUTE_LIST
+                    ),
+                ))]
             }
+            Err(error) => return vec![Err(error)],
+        };
+        let is_repr = attribute.path.is_ident("repr");
+
+        entries
+            .into_iter()
+            .map(|entry| match entry {
+                Entry::TagType(_, ty) if !is_repr => Ok(Self::TagType(ty)),
+                Entry::TagType(path, ty) => Err(syn::Error::new_spanned(
+                    quote::quote!(#path = #ty),
+                    "unexpected tag in repr, use #[packed(tag = ...)]",
+                )),
+                Entry::Nested(nested) => Self::from_nested(is_repr, nested),
+            })
+            .map(|entry| {
+                entry.map_err(|mut err| {
+                    err.combine(syn::Error::new(
+                        err.span(),
+                        format!("Expecting one of {:?}", ATTRIBUTE_LIST),
+                    ));
+                    err
+                })
+            })
+            .collect()
+    }
+
+    /// the path of a list attribute with only one entry: `name(path)`
+    fn single_path(list: syn::MetaList) -> Result<syn::Path> {
+        if list.nested.len() != 1 {
+            return Err(syn::Error::new_spanned(list, "expecting one path"));
+        }
+        let span = list.span();
+
+        match list.nested.into_iter().next() {
+            Some(syn::NestedMeta::Meta(syn::Meta::Path(path))) => Ok(path),
+            Some(nested) => Err(syn::Error::new_spanned(nested, "expecting a path")),
+            None => Err(syn::Error::new(span, "expecting one path")),
         }
     }
 
@@ -146,24 +399,106 @@ impl PackedAttribute {
             meta @ syn::NestedMeta::Lit(_) => {
                 Err(syn::Error::new_spanned(meta, "Unexpected literal"))
             }
+            syn::NestedMeta::Meta(syn::Meta::List(list))
+                if !is_repr && list.path.is_ident(Self::TAG) =>
+            {
+                Self::single_path(list).map(Self::Tag)
+            }
+            syn::NestedMeta::Meta(syn::Meta::List(list))
+                if !is_repr && list.path.is_ident(Self::VERSION) =>
+            {
+                Self::single_path(list).map(Self::Version)
+            }
+            syn::NestedMeta::Meta(syn::Meta::List(list))
+                if !is_repr && list.path.is_ident(Self::UPGRADE) =>
+            {
+                Self::single_path(list).map(Self::UpgradeFrom)
+            }
+            syn::NestedMeta::Meta(syn::Meta::List(list))
+                if !is_repr && list.path.is_ident(Self::SKIP) =>
+            {
+                list.nested
+                    .into_iter()
+                    .map(|nested| match nested {
+                        syn::NestedMeta::Meta(syn::Meta::Path(path)) => Ok(path),
+                        nested => Err(syn::Error::new_spanned(nested, "expecting a path")),
+                    })
+                    .collect::<Result<_>>()
+                    .map(Self::Skip)
+            }
             syn::NestedMeta::Meta(syn::Meta::List(list)) => {
                 if list.path.is_ident(Self::VALUE) {
                     if list.nested.len() > 1 {
                         Err(syn::Error::new_spanned(list, "expecting only one value"))
                     } else if list.nested.is_empty() {
                         Err(syn::Error::new_spanned(list, "expecting one value"))
-                    } else if let Some(syn::NestedMeta::Meta(syn::Meta::Path(path))) =
-                        list.nested.into_iter().next()
-                    {
-                        Ok(Self::Value(ValueType::Const(path)))
                     } else {
-                        // we already tested when the list == 0 before
-                        unreachable!()
+                        match list.nested.into_iter().next() {
+                            Some(syn::NestedMeta::Meta(syn::Meta::Path(path))) => {
+                                Ok(Self::Value(ValueType::Const(path)))
+                            }
+                            Some(syn::NestedMeta::Lit(lit)) => Err(syn::Error::new_spanned(
+                                &lit,
+                                format!(
+                                    "expecting the path to a constant, use #[packed(value = {})] for a literal",
+                                    quote::quote!(#lit)
+                                ),
+                            )),
+                            nested => Err(syn::Error::new_spanned(
+                                nested,
+                                "expecting the path to a constant",
+                            )),
+                        }
                     }
                 } else {
                     Err(syn::Error::new_spanned(list, "unexpected meta list"))
                 }
             }
+            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                if !is_repr && path.is_ident(Self::ARBITRARY) =>
+            {
+                Ok(Self::Arbitrary(path))
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                if !is_repr && path.is_ident(Self::QUICKCHECK) =>
+            {
+                Ok(Self::QuickCheck(path))
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                if !is_repr && path.is_ident(Self::PROPTEST) =>
+            {
+                Ok(Self::PropTest(path))
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                if !is_repr && path.is_ident(Self::BUILDER) =>
+            {
+                Ok(Self::Builder(path))
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                if !is_repr && path.is_ident(Self::ORDERED) =>
+            {
+                Ok(Self::Ordered(path))
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                if !is_repr && path.is_ident(Self::DEFAULT) =>
+            {
+                Ok(Self::Default(DefaultValue::Default(path)))
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                if !is_repr && path.is_ident(Self::UPGRADE) =>
+            {
+                Ok(Self::Upgrade(path))
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                if !is_repr && path.is_ident(Self::PADDING) =>
+            {
+                Ok(Self::Padding(path))
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                if !is_repr && path.is_ident(Self::OVERLAY) =>
+            {
+                Ok(Self::Overlay(path))
+            }
             meta @ syn::NestedMeta::Meta(syn::Meta::Path(_)) if !is_repr => {
                 Err(syn::Error::new_spanned(meta, "unexpected meta path"))
             }
@@ -188,6 +523,65 @@ impl PackedAttribute {
                             "Set the value of the accessor: expecting a string literal",
                         ))
                     }
+                } else if name_value.path.is_ident(Self::DEFAULT) {
+                    if let syn::Lit::Str(expr) = name_value.lit {
+                        let tokens = expr.parse()?;
+                        Ok(Self::Default(DefaultValue::Expr(expr, tokens)))
+                    } else {
+                        Err(syn::Error::new_spanned(
+                            name_value,
+                            "Set the default value: expecting a string literal with an expression",
+                        ))
+                    }
+                } else if name_value.path.is_ident(Self::TAG_OFFSET) {
+                    if let syn::Lit::Int(offset) = name_value.lit {
+                        Ok(Self::TagOffset(offset))
+                    } else {
+                        Err(syn::Error::new_spanned(
+                            name_value,
+                            "Set the offset of the tag: expecting an integer literal",
+                        ))
+                    }
+                } else if name_value.path.is_ident(Self::SIZE) {
+                    if let syn::Lit::Int(size) = name_value.lit {
+                        Ok(Self::Size(size))
+                    } else {
+                        Err(syn::Error::new_spanned(
+                            name_value,
+                            "Set the expected size in bytes: expecting an integer literal",
+                        ))
+                    }
+                } else if name_value.path.is_ident(Self::CRATE) {
+                    if let syn::Lit::Str(path) = name_value.lit {
+                        Ok(Self::Crate(path.parse()?))
+                    } else {
+                        Err(syn::Error::new_spanned(
+                            name_value,
+                            "Set the path to the packtool crate: expecting a string literal",
+                        ))
+                    }
+                } else if name_value.path.is_ident(Self::CHECK) {
+                    match name_value.lit {
+                        syn::Lit::Str(check) if check.value() == "all" => {
+                            Ok(Self::Check(OverlayCheck::All(check)))
+                        }
+                        syn::Lit::Str(check) if check.value() == "any" => {
+                            Ok(Self::Check(OverlayCheck::Any(check)))
+                        }
+                        _ => Err(syn::Error::new_spanned(
+                            name_value,
+                            "Set how the members of the overlay are checked: expecting \"any\" or \"all\"",
+                        )),
+                    }
+                } else if name_value.path.is_ident(Self::OFFSET) {
+                    if let syn::Lit::Int(offset) = name_value.lit {
+                        Ok(Self::Offset(offset))
+                    } else {
+                        Err(syn::Error::new_spanned(
+                            name_value,
+                            "Set the offset of the field in bytes: expecting an integer literal",
+                        ))
+                    }
                 } else {
                     Err(syn::Error::new_spanned(
                         name_value,
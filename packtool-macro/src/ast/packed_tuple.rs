@@ -46,7 +46,6 @@ impl Parse for PackedTuple {
         let fields = content.parse_terminated(PackedField::parse_unnamed)?;
         let _semi = input.parse()?;
 
-        #[allow(clippy::eval_order_dependence)]
         Ok(Self {
             _struct_token,
             ident,
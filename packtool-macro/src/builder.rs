@@ -0,0 +1,245 @@
+/*!
+generate the typestate builders of the packed structures marked with
+`#[packed(builder)]` and the `BuilderField` implementation of every
+packed types.
+
+Only the structures with a builder prefill their constant fields, so
+the fields of the other structures do not need to implement
+`BuilderField`.
+
+The builder of a structure `Header` is `HeaderBuilder<S0, S1, ...>`
+with one typestate parameter for each of the fields. The parameter is
+`Set` or `Unset` and the `build` function is only implemented
+when every one of them is `Set`.
+*/
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{
+    ast::{AccessorType, Container, Data, PackedField},
+    expand::{field_start, unit_constructor},
+};
+
+pub fn expand(container: &Container) -> TokenStream {
+    let builder_field = expand_builder_field(container);
+    let builder = match &container.data {
+        _ if container.attributes.builder.is_none() => quote! {},
+        Data::Tuple(tuple) => expand_builder(container, &tuple.fields),
+        Data::Struct(structure) => expand_builder(container, &structure.fields),
+        // already reported by the checks of the container
+        Data::Unit(_) | Data::Enum(_) => quote! {},
+    };
+
+    quote! {
+        #builder_field
+        #builder
+    }
+}
+
+/// the definition of the builder structure. Unlike the implementations
+/// it is not generated in the anonymous constant so the path to the
+/// `packtool` crate is used directly.
+pub fn expand_definition(container: &Container) -> TokenStream {
+    let fields = match &container.data {
+        _ if container.attributes.builder.is_none() => return quote! {},
+        Data::Tuple(tuple) => tuple.fields.len(),
+        Data::Struct(structure) => structure.fields.len(),
+        // already reported by the checks of the container
+        Data::Unit(_) | Data::Enum(_) => return quote! {},
+    };
+
+    let krate = container.attributes.crate_path();
+    let ident = container.ident();
+    let visibility = &container._visibility;
+    let builder = format_ident!("{}Builder", ident);
+    let states = (0..fields)
+        .map(|index| format_ident!("__S{}", index))
+        .collect::<Vec<_>>();
+    let doc = format!(
+        "typestate builder of [`{ident}`], see [`{ident}::builder`]",
+        ident = ident
+    );
+
+    quote! {
+        #[doc = #doc]
+        #visibility struct #builder< #( #states ),* > {
+            draft: #krate::builder::Draft<#ident>,
+            state: ::core::marker::PhantomData<( #( #states, )* )>,
+        }
+    }
+}
+
+fn expand_builder_field(container: &Container) -> TokenStream {
+    let ident = container.ident();
+
+    let (initial, prefill) = match &container.data {
+        Data::Unit(unit) => {
+            let constructor = unit_constructor(ident, &unit.from);
+            (
+                quote! { __packtool::builder::Set },
+                quote! {
+                    fn prefill(slice: &mut [u8]) {
+                        <Self as __packtool::Packed>::unchecked_write_to_slice(&#constructor, slice)
+                    }
+                },
+            )
+        }
+        Data::Enum(_) => (quote! { __packtool::builder::Unset }, quote! {}),
+        _ if container.attributes.builder.is_none() => {
+            (quote! { __packtool::builder::Unset }, quote! {})
+        }
+        Data::Tuple(tuple) => (
+            quote! { __packtool::builder::Unset },
+            expand_prefill_fields(&tuple.fields),
+        ),
+        Data::Struct(structure) => (
+            quote! { __packtool::builder::Unset },
+            expand_prefill_fields(&structure.fields),
+        ),
+    };
+
+    quote! {
+        impl __packtool::builder::BuilderField for #ident {
+            type Initial = #initial;
+
+            #prefill
+        }
+    }
+}
+
+fn expand_prefill_fields<'a, I>(fields: I) -> TokenStream
+where
+    I: IntoIterator<Item = &'a PackedField>,
+{
+    let mut prefills = Vec::new();
+
+    let mut start = quote! { 0 };
+    for field in fields {
+        start = field_start(field, start);
+        let ty = &field.ty;
+        let end = quote! { #start + <#ty as __packtool::Packed>::SIZE };
+
+        prefills.push(quote! {
+            <#ty as __packtool::builder::BuilderField>::prefill(&mut slice[(#start)..(#end)]);
+        });
+
+        start = end;
+    }
+
+    quote! {
+        fn prefill(slice: &mut [u8]) {
+            #( #prefills )*
+        }
+    }
+}
+
+fn setter_ident(field: &PackedField, index: usize) -> syn::Ident {
+    match &field.attributes.accessor {
+        AccessorType::Custom(ident) => ident.clone(),
+        AccessorType::Default | AccessorType::Ignore => {
+            if let Some(ident) = field.ident.as_ref() {
+                ident.clone()
+            } else {
+                format_ident!("_{}", index)
+            }
+        }
+    }
+}
+
+fn expand_builder<'a, I>(container: &Container, fields: I) -> TokenStream
+where
+    I: IntoIterator<Item = &'a PackedField>,
+{
+    let ident = container.ident();
+    let visibility = &container._visibility;
+    let builder = format_ident!("{}Builder", ident);
+
+    let fields = fields.into_iter().collect::<Vec<_>>();
+    let states = (0..fields.len())
+        .map(|index| format_ident!("__S{}", index))
+        .collect::<Vec<_>>();
+    let initials = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote! { <#ty as __packtool::builder::BuilderField>::Initial }
+    });
+    let sets = fields
+        .iter()
+        .map(|_| quote! { __packtool::builder::Set })
+        .collect::<Vec<_>>();
+
+    let mut setters = Vec::new();
+    let mut start = quote! { 0 };
+    for (index, field) in fields.iter().enumerate() {
+        start = field_start(field, start);
+        let ty = &field.ty;
+        let end = quote! { #start + <#ty as __packtool::Packed>::SIZE };
+        let setter = setter_ident(field, index);
+
+        let next = states.iter().enumerate().map(|(i, state)| {
+            if i == index {
+                quote! { __packtool::builder::Set }
+            } else {
+                quote! { #state }
+            }
+        });
+
+        let doc = format!(
+            "set the value of the field `{}` from a value or a `View` of `{}`",
+            setter,
+            quote!(#ty)
+        );
+        setters.push(quote! {
+            #[doc = #doc]
+            pub fn #setter<V>(mut self, value: V) -> #builder< #( #next ),* >
+            where
+                V: __packtool::builder::FieldValue<#ty>,
+            {
+                self.draft.write::<#ty, V>((#start)..(#end), value);
+                #builder {
+                    draft: self.draft,
+                    state: ::core::marker::PhantomData,
+                }
+            }
+        });
+
+        start = end;
+    }
+
+    let builder_doc = format!(
+        "create a [`{}`] writing the fields directly in the packed bytes",
+        builder
+    );
+
+    quote! {
+        impl #ident {
+            #[doc = #builder_doc]
+            ///
+            /// the fields holding a constant value (unit types) are already set
+            #visibility fn builder() -> #builder< #( #initials ),* > {
+                #builder {
+                    draft: __packtool::builder::Draft::new(),
+                    state: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl< #( #states ),* > #builder< #( #states ),* > {
+            #( #setters )*
+        }
+
+        impl #builder< #( #sets ),* > {
+            /// finish the building of the packet, only available
+            /// once all the fields have been set.
+            pub fn build(self) -> __packtool::Packet<#ident> {
+                match self.draft.finish() {
+                    ::core::result::Result::Ok(packet) => packet,
+                    ::core::result::Result::Err(error) => ::core::panic!(
+                        "a completed builder always produces a valid packet: {}",
+                        error
+                    ),
+                }
+            }
+        }
+    }
+}
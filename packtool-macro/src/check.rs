@@ -0,0 +1,636 @@
+/*!
+the diagnostics of `#[derive(Packed)]`: the container is checked before
+anything is generated and every problem found is reported in the
+[`Context`], so one build shows all of them.
+*/
+
+use proc_macro2::Span;
+use syn::spanned::Spanned as _;
+
+use crate::{
+    ast::{Container, Data, PackedAttributes, PackedEnum, PackedField, ValueType},
+    context::Context,
+    enumeration::SKIPPABLE,
+};
+
+pub fn check(context: &mut Context, container: &Container) {
+    let attributes = &container.attributes;
+
+    check_attributes(context, attributes);
+    check_no_attribute_versions(context, "a packed type", attributes);
+    check_property_testing_features(context, attributes);
+    if let Some(offset) = attributes.offset.as_ref() {
+        context.add_error(syn::Error::new(
+            offset.span(),
+            "Cannot have an offset associated to a type, set it on a field",
+        ));
+    }
+    if !matches!(container.data, Data::Struct(_) | Data::Tuple(_)) {
+        if let Some(path) = attributes.padding.as_ref() {
+            context.add_error_by(path, "Cannot insert padding, only for structures");
+        }
+        if let Some(path) = attributes.overlay.as_ref() {
+            context.add_error_by(path, "Cannot overlay the fields, only for structures");
+        }
+    }
+    check_overlay(context, container);
+    if let Some(default) = attributes.default.as_ref() {
+        context.add_error(syn::Error::new(
+            default.span(),
+            "Cannot have a default value associated to a type, set it on a field",
+        ));
+    }
+    if !matches!(container.data, Data::Enum(_)) {
+        if let Some(tag) = attributes.tag_type.as_ref() {
+            context.add_error_by(tag, "Cannot set the type of the tag, only for enums");
+        }
+    }
+    check_skip(context, container);
+    if !matches!(container.data, Data::Struct(_)) {
+        if let Some(path) = attributes.upgrade_from.as_ref() {
+            context.add_error_by(
+                path,
+                "Cannot upgrade from a previous version, only for named structs",
+            );
+        }
+    }
+
+    match &container.data {
+        Data::Unit(unit) => {
+            check_no_attribute_accessor(context, "Unit", attributes);
+            check_no_attribute_builder(context, "Unit", attributes);
+            if let Some(path) = attributes.ordered.as_ref() {
+                context.add_error_by(
+                    path,
+                    "A unit is always ordered, remove the #[packed(ordered)] attribute",
+                );
+            }
+            // all unit types need to have a value associated, unless
+            // it was set but could not be parsed
+            match attributes.value.as_ref() {
+                None if !attributes.errors.is_empty() => (),
+                None => context.add_error_by(
+                    unit._struct_token,
+                    "Expecting a value associated to this type (#[packed(value = ...)])",
+                ),
+                Some(ValueType::Lit(lit)) => check_value_literal(context, lit),
+                Some(ValueType::Const(_)) => (),
+            }
+        }
+        Data::Tuple(tuple) => {
+            let scope = "an unnamed struct (parenthesis struct)";
+            check_no_attribute_value(context, scope, attributes);
+            check_no_attribute_accessor(context, scope, attributes);
+            check_fields(context, &tuple.fields, false);
+            check_no_default_in_field(context, &tuple.fields);
+            check_no_builder_if_ordered(context, attributes);
+        }
+        Data::Struct(structure) => {
+            let scope = "a named struct (braced struct)";
+            check_no_attribute_value(context, scope, attributes);
+            check_no_attribute_accessor(context, scope, attributes);
+            check_fields(
+                context,
+                &structure.fields,
+                attributes.upgrade_from.is_some(),
+            );
+            match attributes.upgrade_from.as_ref() {
+                None => check_no_default_in_field(context, &structure.fields),
+                Some(upgrade) => check_version_field(context, upgrade, &structure.fields),
+            }
+            check_no_builder_if_ordered(context, attributes);
+        }
+        Data::Enum(enumeration) => {
+            check_no_attribute_value(context, "an enum", attributes);
+            check_no_attribute_accessor(context, "an enum", attributes);
+            check_no_attribute_builder(context, "an enum", attributes);
+            check_enumeration(context, attributes, enumeration);
+        }
+    }
+}
+
+/// report the attributes that could not be parsed
+fn check_attributes(context: &mut Context, attributes: &PackedAttributes) {
+    for error in attributes.errors.iter() {
+        context.add_error(error.clone());
+    }
+}
+
+fn check_enumeration(
+    context: &mut Context,
+    attributes: &PackedAttributes,
+    enumeration: &PackedEnum,
+) {
+    for variant in enumeration.variants.iter() {
+        check_attributes(context, &variant.attributes);
+        for (name, span) in variant.attributes.spans() {
+            context.add_error(syn::Error::new(
+                span,
+                format!("Cannot have the attribute {} on a variant", name),
+            ));
+        }
+
+        if let Some(field) = variant.fields.first() {
+            context.add_error(syn::Error::new(
+                field.ty.span(),
+                format!(
+                    "Cannot pack the variant {} with fields, only unit variants are supported. \
+                    To dispatch on a version tag use #[derive(PackedVersions)] over `View`s, \
+                    to read the same bytes with different types use a structure with #[packed(overlay)]",
+                    variant.ident,
+                ),
+            ));
+        }
+    }
+
+    if !enumeration.only_unit_variants() {
+        return;
+    }
+    match (attributes.tag_type.as_ref(), attributes.repr.as_ref()) {
+        (Some(tag), _) if !is_integer(tag) => context.add_error_by(
+            tag,
+            "Expecting an integer type for the tag (u8, u16, u32, u64, i8, i16, ...)",
+        ),
+        (Some(_), _) => (),
+        (None, Some(repr)) if !INTEGERS.iter().any(|integer| repr.is_ident(integer)) => context
+            .add_error_by(
+                repr,
+                "Expecting an integer repr(...) or the type of the tag (#[packed(tag = u8)])",
+            ),
+        (None, Some(_)) => (),
+        (None, None) => context.add_error_by(
+            enumeration._struct_token,
+            "Pure enumeration variants should have a repr(...) attributes to set the size, \
+            or the type of the tag (#[packed(tag = u8)])",
+        ),
+    }
+}
+
+const INTEGERS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+fn is_integer(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => {
+            INTEGERS.iter().any(|integer| path.path.is_ident(integer))
+        }
+        _ => false,
+    }
+}
+
+/// only the conversions and metadata of the enumerations can be skipped
+fn check_skip(context: &mut Context, container: &Container) {
+    let skip = &container.attributes.skip;
+    if !matches!(container.data, Data::Enum(_)) {
+        if let Some(path) = skip.first() {
+            context.add_error_by(
+                path,
+                "Cannot skip the conversions of a type, only for enums",
+            );
+        }
+        return;
+    }
+
+    for path in skip {
+        if !SKIPPABLE.iter().any(|name| path.is_ident(name)) {
+            context.add_error_by(
+                path,
+                format!("Cannot skip this, expecting one of {:?}", SKIPPABLE),
+            );
+        }
+    }
+}
+
+/// the literal of `#[packed(value = ...)]` needs to have a known type
+/// and a byte representation
+fn check_value_literal(context: &mut Context, lit: &syn::Lit) {
+    match lit {
+        syn::Lit::Int(int) if int.suffix().is_empty() => context.add_error_by(
+            int,
+            format!(
+                "expect to know the exact type of the value, add suffix (like in `{}u8`)",
+                int
+            ),
+        ),
+        syn::Lit::Float(_) => context.add_error_by(
+            lit,
+            "floating point values are not supported, use a constant (#[packed(value(CONSTANT))])",
+        ),
+        syn::Lit::Bool(_) => context.add_error_by(
+            lit,
+            "boolean values are not supported, use a constant (#[packed(value(CONSTANT))])",
+        ),
+        syn::Lit::Verbatim(_) => context.add_error_by(lit, "verbatim values are not supported"),
+        syn::Lit::Str(_)
+        | syn::Lit::ByteStr(_)
+        | syn::Lit::Byte(_)
+        | syn::Lit::Char(_)
+        | syn::Lit::Int(_) => (),
+    }
+}
+
+fn check_property_testing_features(context: &mut Context, attributes: &PackedAttributes) {
+    if let Some(path) = attributes.arbitrary.as_ref() {
+        if !cfg!(feature = "arbitrary") {
+            context.add_error_by(
+                path,
+                "the `arbitrary` feature of `packtool` needs to be enabled",
+            );
+        }
+    }
+    if let Some(path) = attributes.quickcheck.as_ref() {
+        if !cfg!(feature = "quickcheck") {
+            context.add_error_by(
+                path,
+                "the `quickcheck` feature of `packtool` needs to be enabled",
+            );
+        }
+    }
+    if let Some(path) = attributes.proptest.as_ref() {
+        if !cfg!(feature = "proptest") {
+            context.add_error_by(
+                path,
+                "the `proptest` feature of `packtool` needs to be enabled",
+            );
+        }
+    }
+}
+
+fn check_no_attribute_accessor(context: &mut Context, scope: &str, attributes: &PackedAttributes) {
+    if let Some(span) = attributes.accessor_span {
+        context.add_error(syn::Error::new(
+            span,
+            format!(
+                "Cannot have an accessor associated to {scope}",
+                scope = scope
+            ),
+        ));
+    }
+}
+
+fn check_no_attribute_value(context: &mut Context, scope: &str, attributes: &PackedAttributes) {
+    if let Some(value) = attributes.value.as_ref() {
+        context.add_error(syn::Error::new(
+            value.span(),
+            format!("Cannot have a value associated to {scope}", scope = scope),
+        ));
+    }
+}
+
+/// `upgrade` is set when the structure upgrades from a previous
+/// version, the version tag of the structure is then set on a field
+fn check_fields<'a, I>(context: &mut Context, fields: I, upgrade: bool)
+where
+    I: IntoIterator<Item = &'a PackedField>,
+{
+    for field in fields {
+        let scope = "a field of a structure";
+        check_attributes(context, &field.attributes);
+        check_no_attribute_value(context, scope, &field.attributes);
+        check_no_attribute_property_testing(context, scope, &field.attributes);
+        check_no_attribute_builder(context, scope, &field.attributes);
+        check_no_attribute_versions_family(context, scope, &field.attributes);
+        if let (Some(path), false) = (field.attributes.version.as_ref(), upgrade) {
+            context.add_error_by(
+                path,
+                "The version tag of a structure is only set when upgrading from a previous version (#[packed(upgrade(...))])",
+            );
+        }
+        if let Some(path) = field.attributes.ordered.as_ref() {
+            context.add_error_by(
+                path,
+                "Cannot set the ordered encoding of a field, set it on the type instead",
+            );
+        }
+        if let Some(size) = field.attributes.size.as_ref() {
+            context.add_error(syn::Error::new(
+                size.span(),
+                "Cannot set the size of a field, set it on the type instead",
+            ));
+        }
+        if let Some(path) = field.attributes.padding.as_ref() {
+            context.add_error_by(
+                path,
+                "Cannot set the padding of a field, set it on the structure instead",
+            );
+        }
+        if let Some(path) = field.attributes.upgrade_from.as_ref() {
+            context.add_error_by(
+                path,
+                "Cannot upgrade a field, set it on the structure instead",
+            );
+        }
+        if let Some(path) = field.attributes.overlay.as_ref() {
+            context.add_error_by(
+                path,
+                "Cannot overlay the members of a field, set it on the structure instead",
+            );
+        }
+        if let Some(tag) = field.attributes.tag_type.as_ref() {
+            context.add_error_by(
+                tag,
+                "Cannot set the type of the tag of a field, set it on the enum instead",
+            );
+        }
+        if let Some(path) = field.attributes.skip.first() {
+            context.add_error_by(
+                path,
+                "Cannot skip the conversions of a field, set it on the enum instead",
+            );
+        }
+        if let Some(path) = field.attributes.krate.as_ref() {
+            context.add_error_by(
+                path,
+                "Cannot set the path to packtool on a field, set it on the type instead",
+            );
+        }
+    }
+}
+
+fn check_overlay(context: &mut Context, container: &Container) {
+    let attributes = &container.attributes;
+    if attributes.overlay.is_none() {
+        if let Some(check) = attributes.check.as_ref() {
+            context.add_error(syn::Error::new(
+                check.span(),
+                "Deciding how the members are checked is only for overlays (#[packed(overlay)])",
+            ));
+        }
+        return;
+    }
+
+    let conflicts = [
+        attributes.ordered.as_ref(),
+        attributes.padding.as_ref(),
+        attributes.builder.as_ref(),
+        attributes.quickcheck.as_ref(),
+        attributes.proptest.as_ref(),
+    ];
+    for conflict in conflicts.iter().flatten() {
+        context.add_error_by(conflict, "Cannot have this attribute on an overlay");
+    }
+
+    let any = crate::overlay::is_any(container);
+    let fields: Vec<&PackedField> = match &container.data {
+        Data::Tuple(tuple) => tuple.fields.iter().collect(),
+        Data::Struct(structure) => structure.fields.iter().collect(),
+        Data::Unit(_) | Data::Enum(_) => Vec::new(),
+    };
+    for field in fields {
+        if let Some(offset) = field.attributes.offset.as_ref() {
+            context.add_error(syn::Error::new(
+                offset.span(),
+                "Every field of an overlay starts at byte 0",
+            ));
+        }
+        if any && crate::overlay::option_type(&field.ty).is_none() {
+            context.add_error(syn::Error::new(
+                field.ty.span(),
+                "Expecting an `Option`: with check = \"any\" a member is `None` when it is not valid",
+            ));
+        }
+    }
+}
+
+/// the structures upgrading from a previous version set their version
+/// tag: exactly one field holds it
+fn check_version_field<'a, I>(context: &mut Context, upgrade: &syn::Path, fields: I)
+where
+    I: IntoIterator<Item = &'a PackedField>,
+{
+    let mut versions = fields
+        .into_iter()
+        .filter_map(|field| field.attributes.version.as_ref());
+
+    if versions.next().is_none() {
+        context.add_error_by(
+            upgrade,
+            "Expecting the field holding the version tag of the structure (#[packed(version(...))])",
+        );
+    }
+    for version in versions {
+        context.add_error_by(version, "Cannot have more than one version tag");
+    }
+}
+
+fn check_no_default_in_field<'a, I>(context: &mut Context, fields: I)
+where
+    I: IntoIterator<Item = &'a PackedField>,
+{
+    for field in fields {
+        if let Some(default) = field.attributes.default.as_ref() {
+            context.add_error(syn::Error::new(
+                default.span(),
+                "A default value is only used when upgrading from a previous version (#[packed(upgrade(...))])",
+            ));
+        }
+    }
+}
+
+/// the attributes of `#[derive(PackedVersions)]` have no meaning for
+/// the `Packed` types
+pub fn check_no_attribute_versions(
+    context: &mut Context,
+    scope: &str,
+    attributes: &PackedAttributes,
+) {
+    check_no_attribute_versions_family(context, scope, attributes);
+    if let Some(path) = attributes.version.as_ref() {
+        context.add_error_by(
+            path,
+            format!(
+                "Cannot have this attribute on {scope}, only with #[derive(PackedVersions)]",
+                scope = scope
+            ),
+        );
+    }
+}
+
+/// the attributes of the `#[derive(PackedVersions)]` enum itself
+fn check_no_attribute_versions_family(
+    context: &mut Context,
+    scope: &str,
+    attributes: &PackedAttributes,
+) {
+    let spans = [
+        attributes.tag.as_ref().map(|path| path.span()),
+        attributes.tag_offset.as_ref().map(|offset| offset.span()),
+        attributes.upgrade.as_ref().map(|path| path.span()),
+    ];
+
+    for span in spans.iter().flatten() {
+        context.add_error(syn::Error::new(
+            *span,
+            format!(
+                "Cannot have this attribute on {scope}, only with #[derive(PackedVersions)]",
+                scope = scope
+            ),
+        ));
+    }
+}
+
+fn check_no_attribute_builder(context: &mut Context, scope: &str, attributes: &PackedAttributes) {
+    if let Some(path) = attributes.builder.as_ref() {
+        context.add_error_by(
+            path,
+            format!(
+                "Cannot generate a builder for {scope}, only for structures",
+                scope = scope
+            ),
+        );
+    }
+}
+
+fn check_no_builder_if_ordered(context: &mut Context, attributes: &PackedAttributes) {
+    if let (Some(path), Some(_)) = (attributes.builder.as_ref(), attributes.ordered.as_ref()) {
+        context.add_error_by(path, "Cannot generate a builder for an ordered structure");
+    }
+}
+
+fn check_no_attribute_property_testing(
+    context: &mut Context,
+    scope: &str,
+    attributes: &PackedAttributes,
+) {
+    let paths = [
+        attributes.arbitrary.as_ref(),
+        attributes.quickcheck.as_ref(),
+        attributes.proptest.as_ref(),
+    ];
+    for path in paths.iter().flatten() {
+        context.add_error_by(
+            path,
+            format!(
+                "Cannot generate property testing for {scope}, set it on the type instead",
+                scope = scope
+            ),
+        );
+    }
+}
+
+impl PackedAttributes {
+    /// the name and location of every `#[packed(...)]` attribute that
+    /// was set, used to reject them where they have no meaning
+    pub fn spans(&self) -> Vec<(&'static str, Span)> {
+        let spans = [
+            ("value", self.value.as_ref().map(ValueType::span)),
+            ("accessor", self.accessor_span),
+            ("arbitrary", self.arbitrary.as_ref().map(|p| p.span())),
+            ("quickcheck", self.quickcheck.as_ref().map(|p| p.span())),
+            ("proptest", self.proptest.as_ref().map(|p| p.span())),
+            ("builder", self.builder.as_ref().map(|p| p.span())),
+            ("ordered", self.ordered.as_ref().map(|p| p.span())),
+            ("default", self.default.as_ref().map(|d| d.span())),
+            ("upgrade", self.upgrade.as_ref().map(|p| p.span())),
+            ("upgrade(...)", self.upgrade_from.as_ref().map(|p| p.span())),
+            ("tag", self.tag.as_ref().map(|p| p.span())),
+            ("tag = ...", self.tag_type.as_ref().map(|t| t.span())),
+            ("tag_offset", self.tag_offset.as_ref().map(|o| o.span())),
+            ("version", self.version.as_ref().map(|p| p.span())),
+            ("size", self.size.as_ref().map(|s| s.span())),
+            ("offset", self.offset.as_ref().map(|o| o.span())),
+            ("padding", self.padding.as_ref().map(|p| p.span())),
+            ("overlay", self.overlay.as_ref().map(|p| p.span())),
+            ("check", self.check.as_ref().map(|c| c.span())),
+            ("crate", self.krate.as_ref().map(|p| p.span())),
+            ("skip", self.skip.first().map(|p| p.span())),
+        ];
+
+        spans
+            .iter()
+            .filter_map(|(name, span)| span.map(|span| (*name, span)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(input: &str) -> Vec<String> {
+        let container: Container = syn::parse_str(input).unwrap();
+        let mut context = Context::default();
+        check(&mut context, &container);
+        match context.check() {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn valid() {
+        assert!(errors("struct Header { a: u8, b: u32 }").is_empty());
+        assert!(errors("#[repr(u8)] enum E { A = 1, B = 2 }").is_empty());
+    }
+
+    #[test]
+    fn every_field_is_reported() {
+        let errors = errors(
+            "struct Header {
+                #[packed(value = 1u8)] a: u8,
+                #[packed(size = 2)] b: u8,
+                #[packed(builder, unknown)] c: u8,
+            }",
+        );
+
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+    }
+
+    #[test]
+    fn every_attribute_is_reported() {
+        let errors =
+            errors("#[packed(value = 1u8, value = 2u8, ordered = 1, accessor = 1)] struct Unit;");
+
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+    }
+
+    #[test]
+    fn every_variant_is_reported() {
+        let errors = errors("enum E { A(u8), B { b: u16 }, C }");
+
+        // two variants with fields
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].contains("#[derive(PackedVersions)]"));
+    }
+
+    #[test]
+    fn enumeration_tag() {
+        assert!(errors("#[packed(tag = u16)] enum E { A, B = 4, C }").is_empty());
+        assert!(errors("#[repr(u8)] #[packed(tag = u16)] enum E { A, B }").is_empty());
+        assert_eq!(errors("enum E { A, B }").len(), 1);
+        assert_eq!(errors("#[repr(C)] enum E { A, B }").len(), 1);
+        assert_eq!(errors("#[packed(tag = [u8; 2])] enum E { A, B }").len(), 1);
+        assert_eq!(errors("#[packed(tag = u8)] struct S(u8);").len(), 1);
+    }
+
+    #[test]
+    fn skip() {
+        assert!(errors("#[repr(u8)] #[packed(skip(display, name))] enum E { A }").is_empty());
+        assert_eq!(
+            errors("#[repr(u8)] #[packed(skip(debug))] enum E { A }").len(),
+            1
+        );
+        assert_eq!(errors("#[packed(skip(display))] struct S(u8);").len(), 1);
+    }
+
+    #[test]
+    fn value_literals() {
+        assert_eq!(errors("#[packed(value = 1)] struct Unit;").len(), 1);
+        assert_eq!(errors("#[packed(value = 1.0)] struct Unit;").len(), 1);
+        assert_eq!(errors("#[packed(value = true)] struct Unit;").len(), 1);
+        assert_eq!(errors("#[packed(value(1))] struct Unit;").len(), 1);
+        assert!(errors("#[packed(value = 1u8)] struct Unit;").is_empty());
+    }
+
+    #[test]
+    fn generics() {
+        let error = syn::parse_str::<Container>("struct Header<T> { a: T }")
+            .err()
+            .unwrap();
+
+        assert!(error
+            .to_string()
+            .starts_with("generic types cannot be packed"));
+    }
+}
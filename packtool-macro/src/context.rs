@@ -26,7 +26,7 @@ impl Context {
         if self.errors.is_empty() {
             Ok(())
         } else {
-            Err(mem::replace(&mut self.errors, Vec::new()))
+            Err(mem::take(&mut self.errors))
         }
     }
 }
@@ -0,0 +1,181 @@
+/*!
+generate the conversions and the metadata of the packed enumerations:
+
+* `TryFrom<Tag>` and `From<Enum> for Tag`, the `Tag` being the type of
+  the packed discriminant (`#[packed(tag = ...)]` or the `repr(...)`);
+* `VARIANTS`, the variants in declaration order;
+* `name()` and `from_name()`, the name of the variants;
+* `Display`, that writes the name of the variant.
+
+each one of them can be skipped with `#[packed(skip(...))]` when the
+enum already implements it: `try_from`, `into`, `variants`, `name` and
+`display`.
+*/
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    ast::{Container, Data, PackedEnum},
+    expand::{enumeration_tag, expand_discriminant, expand_discriminant_options},
+};
+
+pub const SKIPPABLE: &[&str] = &["try_from", "into", "variants", "name", "display"];
+
+pub fn expand(container: &Container) -> TokenStream {
+    let enumeration = match &container.data {
+        Data::Enum(enumeration) => enumeration,
+        Data::Unit(_) | Data::Tuple(_) | Data::Struct(_) => return quote! {},
+    };
+    let skipped = |name: &str| {
+        container
+            .attributes
+            .skip
+            .iter()
+            .any(|path| path.is_ident(name))
+    };
+    let tag = enumeration_tag(&container.attributes);
+
+    let try_from = if skipped("try_from") {
+        quote! {}
+    } else {
+        expand_try_from(enumeration, &tag)
+    };
+    let into = if skipped("into") {
+        quote! {}
+    } else {
+        expand_into(enumeration, &tag)
+    };
+    let variants = if skipped("variants") {
+        quote! {}
+    } else {
+        expand_variants(enumeration)
+    };
+    let name = if skipped("name") {
+        quote! {}
+    } else {
+        expand_name(enumeration)
+    };
+    let display = if skipped("display") {
+        quote! {}
+    } else {
+        expand_display(enumeration)
+    };
+
+    let ident = enumeration.ident();
+    quote! {
+        impl #ident {
+            #variants
+            #name
+        }
+
+        #try_from
+        #into
+        #display
+    }
+}
+
+pub fn names(enumeration: &PackedEnum) -> impl Iterator<Item = (&syn::Ident, String)> {
+    enumeration.variants.iter().map(|variant| {
+        let name = variant.ident.to_string();
+        let name = name.trim_start_matches("r#").to_owned();
+        (&variant.ident, name)
+    })
+}
+
+fn expand_try_from(enumeration: &PackedEnum, tag: &TokenStream) -> TokenStream {
+    let ident = enumeration.ident();
+    let options = expand_discriminant_options(enumeration);
+    let variants = enumeration.variants.iter().map(|variant| {
+        let discriminant = expand_discriminant(ident, variant, tag);
+        let variant = &variant.ident;
+
+        quote! {
+            if value == #discriminant {
+                ::core::result::Result::Ok(#ident::#variant)
+            }
+        }
+    });
+
+    quote! {
+        impl ::core::convert::TryFrom<#tag> for #ident {
+            type Error = __packtool::Error;
+
+            fn try_from(value: #tag) -> ::core::result::Result<Self, Self::Error> {
+                #( #variants else )* {
+                    ::core::result::Result::Err(__packtool::Error::invalid_discriminant::<Self, _>(
+                        value,
+                        #options,
+                    ))
+                }
+            }
+        }
+    }
+}
+
+fn expand_into(enumeration: &PackedEnum, tag: &TokenStream) -> TokenStream {
+    let ident = enumeration.ident();
+    let variants = enumeration.variants.iter().map(|variant| {
+        let discriminant = expand_discriminant(ident, variant, tag);
+        let variant = &variant.ident;
+
+        quote! { #ident::#variant => #discriminant }
+    });
+
+    quote! {
+        impl ::core::convert::From<#ident> for #tag {
+            fn from(value: #ident) -> Self {
+                match value {
+                    #( #variants, )*
+                }
+            }
+        }
+    }
+}
+
+fn expand_variants(enumeration: &PackedEnum) -> TokenStream {
+    let ident = enumeration.ident();
+    let variants = enumeration.variants.iter().map(|variant| &variant.ident);
+
+    quote! {
+        /// all the variants, in declaration order
+        pub const VARIANTS: &'static [Self] = &[ #( #ident::#variants ),* ];
+    }
+}
+
+fn expand_name(enumeration: &PackedEnum) -> TokenStream {
+    let ident = enumeration.ident();
+    let (variants, names): (Vec<_>, Vec<_>) = names(enumeration).unzip();
+
+    quote! {
+        /// the name of the variant
+        pub fn name(&self) -> &'static ::core::primitive::str {
+            match self {
+                #( #ident::#variants => #names, )*
+            }
+        }
+
+        /// the variant with the given `name`
+        pub fn from_name(name: &::core::primitive::str) -> ::core::option::Option<Self> {
+            match name {
+                #( #names => ::core::option::Option::Some(#ident::#variants), )*
+                _ => ::core::option::Option::None,
+            }
+        }
+    }
+}
+
+fn expand_display(enumeration: &PackedEnum) -> TokenStream {
+    let ident = enumeration.ident();
+    let (variants, names): (Vec<_>, Vec<_>) = names(enumeration).unzip();
+
+    quote! {
+        impl ::core::fmt::Display for #ident {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(match self {
+                    #( #ident::#variants => #names, )*
+                })
+            }
+        }
+    }
+}
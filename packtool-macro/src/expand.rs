@@ -1,156 +1,210 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::Result;
 
-use crate::ast::{
-    AccessorType, Container, Data, PackedAttributes, PackedEnum, PackedField, PackedStruct,
-    PackedTuple, PackedUnitOrigin, PackedVariant, ValueType,
+use crate::{
+    ast::{
+        AccessorType, Container, Data, PackedAttributes, PackedEnum, PackedField, PackedStruct,
+        PackedTuple, PackedUnitOrigin, PackedVariant, ValueType,
+    },
+    context::Context,
 };
 
-pub fn packed_definitions(container: Container) -> TokenStream {
-    let ident = container.ident();
+pub fn packed_definitions(container: Container) -> Result<TokenStream, Vec<syn::Error>> {
+    let mut context = Context::default();
+    crate::check::check(&mut context, &container);
+    context.check()?;
 
-    if let Err(error) = check(&container) {
-        return error.to_compile_error();
-    }
+    let container = crate::overlay::normalize(container);
+    let ident = container.ident();
 
     let size = expand_size(&container);
     let check = expand_check(&container);
+    let check_all = expand_check_all(&container);
+    let inspect = expand_inspect(&container);
     let unchecked_read_from_slice = expand_read_from_slice(&container);
     let unchecked_write_to_slice = expand_write_to_slice(&container);
     let accessors = expand_accessors(&container);
-
-    quote! {
+    let arbitrary = crate::arbitrary::expand(&container);
+    let quickcheck = crate::quickcheck::expand(&container);
+    let proptest = crate::proptest::expand(&container);
+    let builder = crate::builder::expand(&container);
+    let builder_definition = crate::builder::expand_definition(&container);
+    let ordered = expand_ordered(&container);
+    let upgrade = crate::versions::expand_upgrade(&container);
+    let layout = expand_layout_asserts(&container);
+    let discriminants = expand_discriminant_asserts(&container);
+    let enumeration = crate::enumeration::expand(&container);
+
+    let definitions = wrap(
+        &container.attributes,
+        quote! {
         impl #ident {
             #accessors
         }
 
-        impl Packed for #ident {
+        impl __packtool::Packed for #ident {
             const SIZE: usize = #size;
+            type Bytes = [u8; <#ident as __packtool::Packed>::SIZE];
 
             #unchecked_read_from_slice
             #unchecked_write_to_slice
 
             #check
+            #check_all
+            #inspect
         }
-    }
-}
 
-fn check(container: &Container) -> Result<()> {
-    match &container.data {
-        Data::Unit(unit) => {
-            check_no_attribute_accessor("Unit", &container.attributes)?;
-            // all unit types need to have a value associated
-            if container.attributes.value.is_none() {
-                return Err(syn::Error::new_spanned(
-                    unit._struct_token,
-                    "Expecting a value associated to this type (#[packed(valued = ...)])",
-                ));
-            }
-        }
-        Data::Tuple(t) => {
-            check_no_attribute_value(
-                "an unnamed struct (parenthesis struct)",
-                &container.attributes,
-            )?;
-            check_no_attribute_accessor(
-                "an unnamed struct (parenthesis struct)",
-                &container.attributes,
-            )?;
-            check_no_value_in_field(&t.fields)?;
-        }
-        Data::Struct(s) => {
-            check_no_attribute_value("a named struct (braced struct)", &container.attributes)?;
-            check_no_attribute_accessor("a named struct (braced struct)", &container.attributes)?;
-            check_no_value_in_field(&s.fields)?;
-        }
-        Data::Enum(enumeration) => {
-            check_no_attribute_value("an enum", &container.attributes)?;
-            check_no_attribute_accessor("an enum", &container.attributes)?;
-            if enumeration.only_unit_variants() {
-                check_only_enum_variants_have_discriminant(enumeration)?;
-                if container.attributes.repr.is_none() {
-                    return Err(syn::Error::new_spanned(
-                        &enumeration._struct_token,
-                        "Pure enumeration variants should have a repr(...) attributes to set the size",
-                    ));
-                }
-            }
-        }
-    }
+        #ordered
+        #arbitrary
+        #quickcheck
+        #proptest
+        #builder
+        #upgrade
+        #layout
+        #discriminants
+        #enumeration
+        },
+    );
 
-    Ok(())
+    Ok(quote! {
+        #builder_definition
+        #definitions
+    })
 }
 
-fn check_only_enum_variants_have_discriminant(enumeration: &PackedEnum) -> Result<()> {
-    assert!(enumeration.only_unit_variants());
+/// put the generated implementations in an anonymous constant where
+/// `__packtool` is the path to the `packtool` crate (see
+/// `#[packed(crate = "...")]`)
+pub fn wrap(attributes: &PackedAttributes, definitions: TokenStream) -> TokenStream {
+    let krate = attributes.crate_path();
 
-    for variant in enumeration.variants.iter() {
-        if variant.discriminant.is_none() {
-            return Err(syn::Error::new_spanned(
-                &variant.ident,
-                "Missing explicit discriminant for packed enum",
-            ));
-        }
-    }
+    quote! {
+        const _: () = {
+            use #krate as __packtool;
 
-    Ok(())
+            #definitions
+        };
+    }
 }
 
-fn check_no_attribute_accessor(scope: &str, attributes: &PackedAttributes) -> Result<()> {
-    if !matches!(attributes.accessor, AccessorType::Default) {
-        return Err(syn::Error::new(
-            proc_macro2::Span::call_site(),
-            format!(
-                "Cannot have an accessor associated to {scope}",
-                scope = scope
-            ),
-        ));
+/// the packed type used to check and view a field of type `ty`: the type
+/// itself or, for the `#[packed(ordered)]` types, its order preserving
+/// encoding.
+fn field_packed_type(ty: &syn::Type, ordered: bool) -> TokenStream {
+    if ordered {
+        quote! { <#ty as __packtool::OrderedPacked>::Ordered }
+    } else {
+        quote! { #ty }
     }
-    Ok(())
 }
 
-fn check_no_attribute_value(scope: &str, attributes: &PackedAttributes) -> Result<()> {
-    if let Some(value) = attributes.value.as_ref() {
-        return Err(syn::Error::new(
-            value.span(),
-            format!("Cannot have a value associated to {scope}", scope = scope),
-        ));
+/// the offset of `field`: its declared `#[packed(offset = ...)]` or
+/// `end`, the end of the previous field.
+///
+/// when the declared offset is not the end of the previous field the
+/// layout is rejected at compile time (see [`expand_layout_asserts`]),
+/// unless the container has `#[packed(padding)]`.
+pub fn field_start(field: &PackedField, end: TokenStream) -> TokenStream {
+    if let Some(offset) = field.attributes.offset.as_ref() {
+        quote! { #offset }
+    } else {
+        end
     }
-    Ok(())
 }
 
-fn check_no_value_in_field<'a, I>(fields: I) -> Result<()>
+fn expand_size_from_types<'a, I>(attributes: &PackedAttributes, fields: I) -> TokenStream
 where
     I: IntoIterator<Item = &'a PackedField>,
 {
-    for field in fields {
-        check_no_attribute_value("a field of a structure", &field.attributes)?;
+    if let (Some(size), Some(_)) = (attributes.size.as_ref(), attributes.padding.as_ref()) {
+        return quote! { #size };
     }
 
-    Ok(())
+    let mut end = quote! { 0 };
+    for field in fields {
+        let ty = &field.ty;
+        let start = field_start(field, end);
+        end = quote! { #start + <#ty as __packtool::Packed>::SIZE };
+    }
+    end
 }
 
-fn expand_size_from_types<'a, I>(fields: I) -> TokenStream
-where
-    I: IntoIterator<Item = &'a PackedField>,
-{
-    let fields = fields.into_iter().map(|f| &f.ty);
-    quote! { #( < #fields as Packed >::SIZE )+* }
-}
+/// compile time checks of the declared `size` of the container and
+/// `offset` of the fields against the computed layout
+fn expand_layout_asserts(container: &Container) -> TokenStream {
+    let ident = container.ident();
+    let padding = container.attributes.padding.is_some();
+    let mut asserts = Vec::new();
 
-fn expand_size_from_enumeration(enumeration: &PackedEnum) -> TokenStream {
-    assert!(
-        !enumeration.variants.is_empty(),
-        "unit enums should have been converted to a packed_unit"
-    );
+    if let Some(fields) = crate::overlay::fields(container) {
+        asserts.push(crate::overlay::expand_layout_asserts(container, &fields));
+    }
 
-    if enumeration.only_unit_variants() {
-        let ident = enumeration.ident();
-        quote! { ::core::mem::size_of::<#ident>() }
-    } else {
-        todo!("variadic size enumeration not working yet")
+    let fields = match &container.data {
+        // the offsets of the fields of an overlay are all 0
+        _ if container.attributes.overlay.is_some() => Vec::new(),
+        Data::Tuple(tuple) => tuple.fields.iter().collect(),
+        Data::Struct(structure) => structure.fields.iter().collect(),
+        Data::Unit(_) | Data::Enum(_) => Vec::new(),
+    };
+
+    let mut end = quote! { 0 };
+    for (index, field) in fields.into_iter().enumerate() {
+        let ty = &field.ty;
+        let name = field
+            .ident
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| index.to_string());
+
+        if let Some(offset) = field.attributes.offset.as_ref() {
+            if padding {
+                let message = format!(
+                    "field `{}` of `{}` overlaps the previous field: it cannot start at byte {}",
+                    name, ident, offset
+                );
+                asserts.push(quote! {
+                    const _: () = ::core::assert!(#end <= #offset, #message);
+                });
+            } else {
+                let message = format!(
+                    "field `{}` of `{}` does not start at byte {}, use #[packed(padding)] to fill the gaps",
+                    name, ident, offset
+                );
+                asserts.push(quote! {
+                    const _: () = ::core::assert!(#end == #offset, #message);
+                });
+            }
+        }
+
+        let start = field_start(field, end);
+        end = quote! { #start + <#ty as __packtool::Packed>::SIZE };
+    }
+
+    if let Some(size) = container.attributes.size.as_ref() {
+        let message = if padding {
+            format!("the fields of `{}` do not fit in {} bytes", ident, size)
+        } else {
+            format!("`{}` is not {} bytes long", ident, size)
+        };
+        let comparison = if padding {
+            quote! { #end <= #size }
+        } else {
+            quote! { <#ident as __packtool::Packed>::SIZE == #size }
+        };
+        asserts.push(quote! {
+            const _: () = ::core::assert!(#comparison, #message);
+        });
     }
+
+    quote! { #( #asserts )* }
+}
+
+/// the size of the wire type of the discriminant (the variants with
+/// fields are rejected by the checks)
+fn expand_size_from_enumeration(attributes: &PackedAttributes) -> TokenStream {
+    let tag = enumeration_tag(attributes);
+    quote! { ::core::mem::size_of::<#tag>() }
 }
 
 fn expand_size_from_value_type(ident: &syn::Ident, value: &ValueType) -> TokenStream {
@@ -158,7 +212,7 @@ fn expand_size_from_value_type(ident: &syn::Ident, value: &ValueType) -> TokenSt
         ValueType::Lit(lit) => expand_size_from_lit(ident, lit),
         ValueType::Const(con) => {
             //
-            quote! { ::std::mem::size_of_val(& #con) }
+            quote! { ::core::mem::size_of_val(& #con) }
         }
     }
 }
@@ -202,6 +256,10 @@ fn expand_size_from_lit(ident: &syn::Ident, value: &syn::Lit) -> TokenStream {
 }
 
 fn expand_size(container: &Container) -> TokenStream {
+    if let Some(fields) = crate::overlay::fields(container) {
+        return crate::overlay::expand_size(&fields);
+    }
+
     match &container.data {
         Data::Unit(_) => expand_size_from_value_type(
             container.ident(),
@@ -211,9 +269,9 @@ fn expand_size(container: &Container) -> TokenStream {
                 .as_ref()
                 .expect("all units must have a packed(value = %)"),
         ),
-        Data::Tuple(tuple) => expand_size_from_types(&tuple.fields),
-        Data::Struct(structure) => expand_size_from_types(&structure.fields),
-        Data::Enum(enumeration) => expand_size_from_enumeration(enumeration),
+        Data::Tuple(tuple) => expand_size_from_types(&container.attributes, &tuple.fields),
+        Data::Struct(structure) => expand_size_from_types(&container.attributes, &structure.fields),
+        Data::Enum(_) => expand_size_from_enumeration(&container.attributes),
     }
 }
 
@@ -222,11 +280,11 @@ fn expand_check_data_unit_value(ident: &syn::Ident, value: &ValueType) -> TokenS
         ValueType::Lit(lit) => expand_check_data_unit(ident, lit),
         ValueType::Const(con) => {
             quote! {
-                fn check(slice: &[u8]) -> ::std::result::Result<(), ::packtool::Error> {
-                    fn check_<C: Packed + ::std::fmt::Debug + PartialEq>(con: C, slice: &[u8]) -> ::std::result::Result<(), ::packtool::Error> {
-                        let value = <C as Packed>::unchecked_read_from_slice(slice);
+                fn check(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
+                    fn check_<C: __packtool::Packed + ::core::fmt::Debug + ::core::cmp::PartialEq>(con: C, slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
+                        let value = <C as __packtool::Packed>::unchecked_read_from_slice(slice);
 
-                        ::packtool::ensure!(
+                        __packtool::ensure!(
                             #ident,
                             value == con,
                             "Invalid value, expected {expected:?} but received {received:?}",
@@ -234,7 +292,7 @@ fn expand_check_data_unit_value(ident: &syn::Ident, value: &ValueType) -> TokenS
                             received = slice,
                         );
 
-                        Ok(())
+                        ::core::result::Result::Ok(())
                     }
 
                     check_(#con, slice)
@@ -248,9 +306,11 @@ fn expand_check_data_unit_value(ident: &syn::Ident, value: &ValueType) -> TokenS
 fn expand_check_data_unit(ident: &syn::Ident, value: &syn::Lit) -> TokenStream {
     match value {
         syn::Lit::Str(string) => {
+            // the lossy conversion allocates, it is the only path to
+            // `std` with the `Box` of the quickcheck shrinkers
             quote! {
-                fn check(slice: &[u8]) -> ::std::result::Result<(), ::packtool::Error> {
-                    ::packtool::ensure!(
+                fn check(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
+                    __packtool::ensure!(
                         #ident,
                         slice == #string.as_bytes(),
                         "Invalid string, expected {expected} but received {received}",
@@ -258,14 +318,14 @@ fn expand_check_data_unit(ident: &syn::Ident, value: &syn::Lit) -> TokenStream {
                         received = ::std::string::String::from_utf8_lossy(slice),
                     );
 
-                    Ok(())
+                    ::core::result::Result::Ok(())
                 }
             }
         }
         syn::Lit::ByteStr(bytes) => {
             quote! {
-                fn check(slice: &[u8]) -> ::std::result::Result<(), ::packtool::Error> {
-                    ::packtool::ensure!(
+                fn check(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
+                    __packtool::ensure!(
                         #ident,
                         slice == #bytes,
                         "Invalid string, expected {expected:?} but received {received:?}",
@@ -273,33 +333,35 @@ fn expand_check_data_unit(ident: &syn::Ident, value: &syn::Lit) -> TokenStream {
                         received = slice,
                     );
 
-                    Ok(())
+                    ::core::result::Result::Ok(())
                 }
             }
         }
         syn::Lit::Byte(byte) => {
             quote! {
-                fn check(slice: &[u8]) -> ::std::result::Result<(), ::packtool::Error> {
-                    ::packtool::ensure!(
+                fn check(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
+                    let byte = slice[0];
+                    __packtool::ensure!(
                         #ident,
-                        slice[0] == Some(#byte),
-                        "Invalid byte string, expected {expected:X} but received {received:X}",
+                        byte == #byte,
+                        "Invalid byte, expected {expected:#04X} but received {received:#04X}",
                         expected = #byte,
-                        received = slice[0],
+                        received = byte,
                     );
 
-                    Ok(())
+                    ::core::result::Result::Ok(())
                 }
             }
         }
         syn::Lit::Char(char) => {
             quote! {
-                fn check(slice: &[u8]) -> ::std::result::Result<(), ::packtool::Error> {
-                    use ::packtool::Context as _;
-                    let c = ::std::str::from_utf8(slice)
+                fn check(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
+                    use ::core::{iter::Iterator as _, option::Option::Some};
+                    use __packtool::Context as _;
+                    let c = ::core::str::from_utf8(slice)
                         .context("Failed to parse valid utf8 char from the slice")?;
 
-                    ::packtool::ensure!(
+                    __packtool::ensure!(
                         #ident,
                         c.chars().next() == Some(#char),
                         "Invalid UTF8 encoded char, expected {expected} but received {received}",
@@ -307,7 +369,7 @@ fn expand_check_data_unit(ident: &syn::Ident, value: &syn::Lit) -> TokenStream {
                         received = c,
                     );
 
-                    Ok(())
+                    ::core::result::Result::Ok(())
                 }
             }
         }
@@ -321,15 +383,15 @@ fn expand_check_data_unit(ident: &syn::Ident, value: &syn::Lit) -> TokenStream {
             } else {
                 let ident = syn::Ident::new(int.suffix(), int.span());
                 quote! {
-                    fn check(slice: &[u8]) -> ::std::result::Result<(), ::packtool::Error> {
-                        use ::packtool::Context as _;
+                    fn check(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
+                        use __packtool::Context as _;
                         use ::core::convert::TryInto as _;
                         let int = <#ident>::from_le_bytes(
                             slice.try_into()
                                 .context("expecting to parse integer value")?
                         );
 
-                        ::packtool::ensure!(
+                        __packtool::ensure!(
                             #ident,
                             int == #int,
                             "Invalid packed integer, expected {expected} but received {received}",
@@ -337,7 +399,7 @@ fn expand_check_data_unit(ident: &syn::Ident, value: &syn::Lit) -> TokenStream {
                             received = int,
                         );
 
-                        Ok(())
+                        ::core::result::Result::Ok(())
                     }
                 }
             }
@@ -355,24 +417,26 @@ fn expand_check_data_unit(ident: &syn::Ident, value: &syn::Lit) -> TokenStream {
     }
 }
 
-fn expand_check_data_field(
+pub fn expand_check_data_field(
     field: &PackedField,
     index: usize,
     start: TokenStream,
+    ordered: bool,
 ) -> (TokenStream, TokenStream) {
     let ty = &field.ty;
+    let packed = field_packed_type(ty, ordered);
     let on_error = if let Some(ident) = field.ident.as_ref() {
         quote! {
             context(
-                ::packtool::Error::invalid_field::<#ty>(
-                    stringify!(#ident)
+                __packtool::Error::invalid_field::<#ty>(
+                    ::core::stringify!(#ident)
                 )
             )
         }
     } else {
         quote! {
             context(
-                ::packtool::Error::invalid_tuple::<#ty>(
+                __packtool::Error::invalid_tuple::<#ty>(
                     #index
                 )
             )
@@ -380,16 +444,16 @@ fn expand_check_data_field(
     };
 
     let end = quote! {
-        #start + <#ty as Packed>::SIZE
+        #start + <#ty as __packtool::Packed>::SIZE
     };
     let quote = quote! {
-        <#ty as Packed>::check(&slice[(#start)..(#end)]).#on_error?;
+        <#packed as __packtool::Packed>::check(&slice[(#start)..(#end)]).#on_error?;
     };
 
     (quote, end)
 }
 
-fn expand_check_data_fields<'a, I>(fields: I) -> TokenStream
+fn expand_check_data_fields<'a, I>(fields: I, ordered: bool) -> TokenStream
 where
     I: IntoIterator<Item = &'a PackedField>,
 {
@@ -397,7 +461,8 @@ where
 
     let mut start = quote! { 0 };
     for (index, field) in fields.into_iter().enumerate() {
-        let (check, end) = expand_check_data_field(field, index, start.clone());
+        start = field_start(field, start);
+        let (check, end) = expand_check_data_field(field, index, start.clone(), ordered);
         checks.push(check);
         start = end;
     }
@@ -405,28 +470,106 @@ where
     quote! { #(#checks)* }
 }
 
-fn expand_check_data_variants<'a, I>(repr: &syn::Path, variants: I) -> TokenStream
-where
-    I: IntoIterator<Item = &'a PackedVariant>,
-{
-    let mut discriminants = Vec::new();
+/// the wire type of the discriminant of an enumeration: the type set
+/// with `#[packed(tag = ...)]` or the `repr(...)` of the enum
+pub fn enumeration_tag(attributes: &PackedAttributes) -> TokenStream {
+    match (attributes.tag_type.as_ref(), attributes.repr.as_ref()) {
+        (Some(tag), _) => quote! { #tag },
+        (None, Some(repr)) => quote! { #repr },
+        // rejected by the checks, this is the type of the
+        // discriminant when there is no repr
+        (None, None) => quote! { isize },
+    }
+}
 
-    for variant in variants.into_iter() {
-        let discriminant = if let Some(discriminant) = variant.discriminant.as_ref() {
-            discriminant
-        } else {
-            panic!("should always be a discriminant")
+/// the discriminant of the `variant` in the wire type of the tag
+pub fn expand_discriminant(
+    ident: &syn::Ident,
+    variant: &PackedVariant,
+    tag: &TokenStream,
+) -> TokenStream {
+    let variant = &variant.ident;
+    quote! { (#ident::#variant as #tag) }
+}
+
+/// the value of a literal discriminant: `42`, `0xFF` or `-1`
+fn literal_discriminant(expr: &syn::Expr) -> Option<i128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => int.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_discriminant(expr).map(|value| -value),
+        syn::Expr::Paren(paren) => literal_discriminant(&paren.expr),
+        _ => None,
+    }
+}
+
+/// the discriminants of the enumeration as presented in the error
+/// messages: the implicit discriminants follow the previous one and
+/// start at `0`, like in Rust.
+pub fn expand_discriminant_options(enumeration: &PackedEnum) -> String {
+    let mut options = String::new();
+    // the last explicit discriminant and the number of variants since
+    let mut base: Option<(Option<i128>, String)> = None;
+    let mut count = 0i128;
+
+    for variant in enumeration.variants.iter() {
+        if let Some((_, expr)) = variant.discriminant.as_ref() {
+            base = Some((literal_discriminant(expr), quote!(#expr).to_string()));
+            count = 0;
+        }
+
+        let option = match base.as_ref() {
+            None => count.to_string(),
+            Some((Some(value), _)) => (value + count).to_string(),
+            Some((None, expr)) if count == 0 => expr.clone(),
+            Some((None, expr)) => format!("{} + {}", expr, count),
         };
-        discriminants.push(&discriminant.1);
+        options.push_str(&option);
+        options.push_str(", ");
+        count += 1;
     }
 
-    let value = if repr.is_ident("u8") {
-        quote! { slice[0] }
-    } else if repr.is_ident("i8") {
-        quote! { slice[0] as i8 }
+    options
+}
+
+/// read the discriminant of an enumeration from the `slice`
+fn expand_read_discriminant(tag: &TokenStream, ordered: bool) -> TokenStream {
+    if ordered {
+        quote! { <#tag as __packtool::OrderedPacked>::unchecked_read_ordered(slice) }
+    } else {
+        quote! {
+            <#tag>::from_le_bytes(
+                slice.try_into().unwrap()
+            )
+        }
+    }
+}
+
+fn expand_check_data_variants<'a, I>(
+    ident: &syn::Ident,
+    tag: &TokenStream,
+    variants: I,
+    options: &str,
+    ordered: bool,
+) -> TokenStream
+where
+    I: IntoIterator<Item = &'a PackedVariant>,
+{
+    let discriminants = variants
+        .into_iter()
+        .map(|variant| expand_discriminant(ident, variant, tag));
+
+    let value = if ordered {
+        expand_read_discriminant(tag, ordered)
     } else {
         quote! {
-            <#repr>::from_le_bytes(
+            <#tag>::from_le_bytes(
                 slice.try_into()
                     .context("invalid length")?
             )
@@ -434,66 +577,74 @@ where
     };
 
     quote! {
-        match #value {
-            #[allow(clippy::unused_unit)]
-            # ( #discriminants )|* => {
-                ()
-            }
-            found => return Err(
-                ::packtool::Error::invalid_discriminant::<Self, _>(
-                    found,
-                    ::core::concat!(#(#discriminants , ", "),*),
-                )
-            ),
+        let found = #value;
+        if #( found != #discriminants )&&* {
+            return ::core::result::Result::Err(
+                __packtool::Error::invalid_discriminant::<Self, _>(found, #options),
+            );
         }
     }
 }
 
-fn expand_check_data_tuple(tuple: &PackedTuple) -> TokenStream {
-    let fields = expand_check_data_fields(&tuple.fields);
+fn expand_check_data_tuple(tuple: &PackedTuple, ordered: bool) -> TokenStream {
+    let fields = expand_check_data_fields(&tuple.fields, ordered);
     quote! {
-        fn check(slice: &[u8]) -> ::std::result::Result<(), ::packtool::Error> {
+        fn check(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
             use ::core::convert::TryInto as _;
-            use ::packtool::Context as _;
+            use __packtool::Context as _;
 
             #fields
 
-            Ok(())
+            ::core::result::Result::Ok(())
         }
     }
 }
 
-fn expand_check_data_structure(structure: &PackedStruct) -> TokenStream {
-    let fields = expand_check_data_fields(&structure.fields);
+fn expand_check_data_structure(structure: &PackedStruct, ordered: bool) -> TokenStream {
+    let fields = expand_check_data_fields(&structure.fields, ordered);
 
     quote! {
-        fn check(slice: &[u8]) -> ::std::result::Result<(), ::packtool::Error> {
+        fn check(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
             use ::core::convert::TryInto as _;
-            use ::packtool::Context as _;
+            use __packtool::Context as _;
 
             #fields
 
-            Ok(())
+            ::core::result::Result::Ok(())
         }
     }
 }
 
-fn expand_check_data_enumeration(repr: &syn::Path, enumeration: &PackedEnum) -> TokenStream {
-    let variants = expand_check_data_variants(repr, &enumeration.variants);
+fn expand_check_data_enumeration(
+    attributes: &PackedAttributes,
+    enumeration: &PackedEnum,
+) -> TokenStream {
+    let variants = expand_check_data_variants(
+        enumeration.ident(),
+        &enumeration_tag(attributes),
+        &enumeration.variants,
+        &expand_discriminant_options(enumeration),
+        attributes.ordered.is_some(),
+    );
 
     quote! {
-        fn check(slice: &[u8]) -> ::std::result::Result<(), ::packtool::Error> {
+        fn check(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
             use ::core::convert::TryInto as _;
-            use ::packtool::Context as _;
+            use __packtool::Context as _;
 
             #variants
 
-            Ok(())
+            ::core::result::Result::Ok(())
         }
     }
 }
 
 fn expand_check(container: &Container) -> TokenStream {
+    if let Some(fields) = crate::overlay::fields(container) {
+        return crate::overlay::expand_check(container, &fields);
+    }
+
+    let ordered = container.attributes.ordered.is_some();
     match &container.data {
         Data::Unit(_) => expand_check_data_unit_value(
             container.ident(),
@@ -503,25 +654,167 @@ fn expand_check(container: &Container) -> TokenStream {
                 .as_ref()
                 .expect("all units must have a packed(value = %)"),
         ),
-        Data::Tuple(tuple) => expand_check_data_tuple(tuple),
-        Data::Struct(structure) => expand_check_data_structure(structure),
-        Data::Enum(enumeration) => expand_check_data_enumeration(
-            container
-                .attributes
-                .repr
-                .as_ref()
-                .expect("Should have a repr on every enums"),
-            enumeration,
-        ),
+        Data::Tuple(tuple) => expand_check_data_tuple(tuple, ordered),
+        Data::Struct(structure) => expand_check_data_structure(structure, ordered),
+        Data::Enum(enumeration) => {
+            expand_check_data_enumeration(&container.attributes, enumeration)
+        }
     }
 }
 
-fn expand_read_from_slice_data_unit(ident: &syn::Ident, from: &PackedUnitOrigin) -> TokenStream {
-    let constructor = match from {
+pub fn expand_check_all_data_field(
+    field: &PackedField,
+    index: usize,
+    start: TokenStream,
+    ordered: bool,
+) -> (TokenStream, TokenStream) {
+    let ty = &field.ty;
+    let packed = field_packed_type(ty, ordered);
+    let segment = if let Some(ident) = field.ident.as_ref() {
+        quote! { __packtool::Segment::Field(::core::stringify!(#ident)) }
+    } else {
+        quote! { __packtool::Segment::Index(#index) }
+    };
+
+    let end = quote! {
+        #start + <#ty as __packtool::Packed>::SIZE
+    };
+    let quote = quote! {
+        report.nested(#segment, #start, |report| {
+            <#packed as __packtool::Packed>::check_all(&slice[(#start)..(#end)], report)
+        });
+    };
+
+    (quote, end)
+}
+
+fn expand_check_all_data_fields<'a, I>(fields: I, ordered: bool) -> TokenStream
+where
+    I: IntoIterator<Item = &'a PackedField>,
+{
+    let mut checks = Vec::new();
+
+    let mut start = quote! { 0 };
+    for (index, field) in fields.into_iter().enumerate() {
+        start = field_start(field, start);
+        let (check, end) = expand_check_all_data_field(field, index, start.clone(), ordered);
+        checks.push(check);
+        start = end;
+    }
+
+    quote! {
+        fn check_all(slice: &[u8], report: &mut __packtool::Report) {
+            #(#checks)*
+        }
+    }
+}
+
+fn expand_check_all(container: &Container) -> TokenStream {
+    if let Some(fields) = crate::overlay::fields(container) {
+        return crate::overlay::expand_check_all(container, &fields);
+    }
+
+    let ordered = container.attributes.ordered.is_some();
+    match &container.data {
+        // units and enumerations are checked as a whole, the default
+        // implementation of `check_all` is enough
+        Data::Unit(_) | Data::Enum(_) => quote! {},
+        Data::Tuple(tuple) => expand_check_all_data_fields(&tuple.fields, ordered),
+        Data::Struct(structure) => expand_check_all_data_fields(&structure.fields, ordered),
+    }
+}
+
+pub fn expand_inspect_data_field(
+    field: &PackedField,
+    index: usize,
+    start: TokenStream,
+    ordered: bool,
+) -> (TokenStream, TokenStream) {
+    let ty = &field.ty;
+    let packed = field_packed_type(ty, ordered);
+    let segment = if let Some(ident) = field.ident.as_ref() {
+        quote! { __packtool::Segment::Field(::core::stringify!(#ident)) }
+    } else {
+        quote! { __packtool::Segment::Index(#index) }
+    };
+
+    let end = quote! {
+        #start + <#ty as __packtool::Packed>::SIZE
+    };
+    let quote = quote! {
+        inspector.nested(#segment, #start, <#ty as __packtool::Packed>::SIZE, |inspector| {
+            <#packed as __packtool::Packed>::inspect(&slice[(#start)..(#end)], inspector)
+        });
+    };
+
+    (quote, end)
+}
+
+fn expand_inspect_data_fields<'a, I>(fields: I, ordered: bool) -> TokenStream
+where
+    I: IntoIterator<Item = &'a PackedField>,
+{
+    let mut inspects = Vec::new();
+
+    let mut start = quote! { 0 };
+    for (index, field) in fields.into_iter().enumerate() {
+        start = field_start(field, start);
+        let (inspect, end) = expand_inspect_data_field(field, index, start.clone(), ordered);
+        inspects.push(inspect);
+        start = end;
+    }
+
+    quote! {
+        fn inspect(slice: &[u8], inspector: &mut __packtool::Inspector) {
+            #(#inspects)*
+        }
+    }
+}
+
+fn expand_inspect_data_enumeration(enumeration: &PackedEnum) -> TokenStream {
+    let ident = enumeration.ident();
+    let (variants, names): (Vec<_>, Vec<_>) = crate::enumeration::names(enumeration).unzip();
+
+    quote! {
+        fn inspect(slice: &[u8], inspector: &mut __packtool::Inspector) {
+            match <Self as __packtool::Packed>::check(slice) {
+                ::core::result::Result::Ok(()) => {
+                    inspector.value(match <Self as __packtool::Packed>::unchecked_read_from_slice(slice) {
+                        #( #ident::#variants => #names, )*
+                    })
+                }
+                ::core::result::Result::Err(error) => inspector.error(error),
+            }
+        }
+    }
+}
+
+fn expand_inspect(container: &Container) -> TokenStream {
+    if let Some(fields) = crate::overlay::fields(container) {
+        return crate::overlay::expand_inspect(&fields);
+    }
+
+    let ordered = container.attributes.ordered.is_some();
+    match &container.data {
+        // units are checked as a whole, the default implementation of
+        // `inspect` is enough
+        Data::Unit(_) => quote! {},
+        Data::Enum(enumeration) => expand_inspect_data_enumeration(enumeration),
+        Data::Tuple(tuple) => expand_inspect_data_fields(&tuple.fields, ordered),
+        Data::Struct(structure) => expand_inspect_data_fields(&structure.fields, ordered),
+    }
+}
+
+pub fn unit_constructor(ident: &syn::Ident, from: &PackedUnitOrigin) -> TokenStream {
+    match from {
         PackedUnitOrigin::Unit => quote! { #ident },
         PackedUnitOrigin::Tuple => quote! { #ident () },
         PackedUnitOrigin::Brace => quote! { #ident {} },
-    };
+    }
+}
+
+fn expand_read_from_slice_data_unit(ident: &syn::Ident, from: &PackedUnitOrigin) -> TokenStream {
+    let constructor = unit_constructor(ident, from);
 
     quote! {
         fn unchecked_read_from_slice(_view: &[u8]) -> Self {
@@ -531,56 +824,45 @@ fn expand_read_from_slice_data_unit(ident: &syn::Ident, from: &PackedUnitOrigin)
 }
 
 fn expand_read_from_slice_data_variants<'a, I>(
-    repr: &syn::Path,
+    tag: &TokenStream,
     ident: &syn::Ident,
     variants: I,
+    ordered: bool,
 ) -> TokenStream
 where
     I: IntoIterator<Item = &'a PackedVariant>,
 {
-    let mut discriminants = Vec::new();
-
-    for variant in variants.into_iter() {
-        let (_, discriminant) = if let Some(discriminant) = variant.discriminant.as_ref() {
-            discriminant
-        } else {
-            panic!("should always be a discriminant")
-        };
+    let variants = variants.into_iter().map(|variant| {
+        let discriminant = expand_discriminant(ident, variant, tag);
         let variant = &variant.ident;
 
-        discriminants.push({
-            quote! {
-                #discriminant => { #ident :: #variant }
-            }
-        });
-    }
-
-    let value = if repr.is_ident("u8") {
-        quote! { slice[0] }
-    } else if repr.is_ident("i8") {
-        quote! { slice[0] as i8 }
-    } else {
         quote! {
-            <#repr>::from_le_bytes(
-                slice.try_into().unwrap()
-            )
+            if value == #discriminant {
+                #ident :: #variant
+            }
         }
-    };
+    });
+
+    let value = expand_read_discriminant(tag, ordered);
 
     quote! {
-        match #value {
-            #( #discriminants ),*
-            _ => panic!("Invalid discriminant"),
+        let value = #value;
+        #( #variants else )* {
+            ::core::panic!("Invalid discriminant")
         }
     }
 }
 
 fn expand_read_from_slice_data_enumeration(
-    repr: &syn::Path,
-    ident: &syn::Ident,
+    attributes: &PackedAttributes,
     enumeration: &PackedEnum,
 ) -> TokenStream {
-    let variants = expand_read_from_slice_data_variants(repr, ident, &enumeration.variants);
+    let variants = expand_read_from_slice_data_variants(
+        &enumeration_tag(attributes),
+        enumeration.ident(),
+        &enumeration.variants,
+        attributes.ordered.is_some(),
+    );
 
     quote! {
         fn unchecked_read_from_slice(slice: &[u8]) -> Self {
@@ -594,26 +876,32 @@ fn expand_read_from_slice_data_enumeration(
 fn expand_read_from_slice_data_field(
     field: &PackedField,
     start: TokenStream,
+    ordered: bool,
 ) -> (TokenStream, TokenStream) {
     let ty = &field.ty;
 
     let end = quote! {
-        #start + <#ty as Packed>::SIZE
+        #start + <#ty as __packtool::Packed>::SIZE
+    };
+    let read = if ordered {
+        quote! { <#ty as __packtool::OrderedPacked>::unchecked_read_ordered }
+    } else {
+        quote! { <#ty as __packtool::Packed>::unchecked_read_from_slice }
     };
     let quote = if let Some(ident) = field.ident.as_ref() {
         quote! {
-            #ident : <#ty as Packed>::unchecked_read_from_slice(&slice[(#start)..(#end)])
+            #ident : #read(&slice[(#start)..(#end)])
         }
     } else {
         quote! {
-            <#ty as Packed>::unchecked_read_from_slice(&slice[(#start)..(#end)])
+            #read(&slice[(#start)..(#end)])
         }
     };
 
     (quote, end)
 }
 
-fn expand_read_from_slice_data_fields<'a, I>(fields: I) -> TokenStream
+fn expand_read_from_slice_data_fields<'a, I>(fields: I, ordered: bool) -> TokenStream
 where
     I: IntoIterator<Item = &'a PackedField>,
 {
@@ -621,7 +909,8 @@ where
 
     let mut start = quote! { 0 };
     for field in fields.into_iter() {
-        let (check, end) = expand_read_from_slice_data_field(field, start.clone());
+        start = field_start(field, start);
+        let (check, end) = expand_read_from_slice_data_field(field, start.clone(), ordered);
         checks.push(check);
         start = end;
     }
@@ -629,9 +918,9 @@ where
     quote! { #(#checks),* }
 }
 
-fn expand_read_from_slice_data_tuple(tuple: &PackedTuple) -> TokenStream {
+fn expand_read_from_slice_data_tuple(tuple: &PackedTuple, ordered: bool) -> TokenStream {
     let ident = tuple.ident();
-    let fields = expand_read_from_slice_data_fields(&tuple.fields);
+    let fields = expand_read_from_slice_data_fields(&tuple.fields, ordered);
     quote! {
         fn unchecked_read_from_slice(slice: &[u8]) -> Self {
             use ::core::convert::TryInto as _;
@@ -643,8 +932,8 @@ fn expand_read_from_slice_data_tuple(tuple: &PackedTuple) -> TokenStream {
     }
 }
 
-fn expand_read_from_slice_data_structure(structure: &PackedStruct) -> TokenStream {
-    let fields = expand_read_from_slice_data_fields(&structure.fields);
+fn expand_read_from_slice_data_structure(structure: &PackedStruct, ordered: bool) -> TokenStream {
+    let fields = expand_read_from_slice_data_fields(&structure.fields, ordered);
     let ident = structure.ident();
 
     quote! {
@@ -659,19 +948,20 @@ fn expand_read_from_slice_data_structure(structure: &PackedStruct) -> TokenStrea
 }
 
 fn expand_read_from_slice(container: &Container) -> TokenStream {
+    if let Some(fields) = crate::overlay::fields(container) {
+        if crate::overlay::is_any(container) {
+            return crate::overlay::expand_read_any(container, &fields);
+        }
+    }
+
+    let ordered = container.attributes.ordered.is_some();
     match &container.data {
         Data::Unit(unit) => expand_read_from_slice_data_unit(container.ident(), &unit.from),
-        Data::Tuple(tuple) => expand_read_from_slice_data_tuple(tuple),
-        Data::Struct(structure) => expand_read_from_slice_data_structure(structure),
-        Data::Enum(enumeration) => expand_read_from_slice_data_enumeration(
-            container
-                .attributes
-                .repr
-                .as_ref()
-                .expect("Should have a repr on every enums"),
-            container.ident(),
-            enumeration,
-        ),
+        Data::Tuple(tuple) => expand_read_from_slice_data_tuple(tuple, ordered),
+        Data::Struct(structure) => expand_read_from_slice_data_structure(structure, ordered),
+        Data::Enum(enumeration) => {
+            expand_read_from_slice_data_enumeration(&container.attributes, enumeration)
+        }
     }
 }
 
@@ -714,7 +1004,7 @@ fn expand_write_to_slice_data_unit(value: &syn::Lit) -> TokenStream {
         syn::Lit::Char(char) => {
             quote! {
                 fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
-                    slice.copy_from_slice(String::from(#char).as_bytes());
+                    slice.copy_from_slice(#char.encode_utf8(&mut [0; 4]).as_bytes());
                 }
             }
         }
@@ -749,53 +1039,45 @@ fn expand_write_to_slice_data_unit(value: &syn::Lit) -> TokenStream {
 }
 
 fn expand_write_to_slice_data_variants<'a, I>(
-    repr: &syn::Path,
+    tag: &TokenStream,
     ident: &syn::Ident,
     variants: I,
+    ordered: bool,
 ) -> TokenStream
 where
     I: IntoIterator<Item = &'a PackedVariant>,
 {
-    let mut discriminants = Vec::new();
+    let write = if ordered {
+        quote! { __packtool::OrderedPacked::unchecked_write_ordered(&discriminant, slice) }
+    } else {
+        quote! { slice.copy_from_slice(&<#tag>::to_le_bytes(discriminant)) }
+    };
 
-    for variant in variants.into_iter() {
-        let (_, discriminant) = if let Some(discriminant) = variant.discriminant.as_ref() {
-            discriminant
-        } else {
-            panic!("should always be a discriminant")
-        };
+    let variants = variants.into_iter().map(|variant| {
+        let discriminant = expand_discriminant(ident, variant, tag);
         let variant = &variant.ident;
 
-        let value = if repr.is_ident("u8") {
-            quote! { slice[0] = #discriminant; }
-        } else if repr.is_ident("i8") {
-            quote! { slice[0] = #discriminant as i8; }
-        } else {
-            quote! {
-                slice.copy_from_slice(&<#repr>::to_le_bytes(#discriminant));
-            }
-        };
-
-        discriminants.push({
-            quote! {
-                #ident :: #variant => { #value }
-            }
-        });
-    }
+        quote! { #ident :: #variant => #discriminant }
+    });
 
     quote! {
-        match self {
-            #( #discriminants ),*
-        }
+        let discriminant: #tag = match self {
+            #( #variants, )*
+        };
+        #write;
     }
 }
 
 fn expand_write_to_slice_data_enumeration(
-    repr: &syn::Path,
-    ident: &syn::Ident,
+    attributes: &PackedAttributes,
     enumeration: &PackedEnum,
 ) -> TokenStream {
-    let variants = expand_write_to_slice_data_variants(repr, ident, &enumeration.variants);
+    let variants = expand_write_to_slice_data_variants(
+        &enumeration_tag(attributes),
+        enumeration.ident(),
+        &enumeration.variants,
+        attributes.ordered.is_some(),
+    );
 
     quote! {
         fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
@@ -804,30 +1086,36 @@ fn expand_write_to_slice_data_enumeration(
     }
 }
 
-fn expand_write_to_slice_data_field(
+pub fn expand_write_to_slice_data_field(
     field: &PackedField,
     index: syn::Index,
     start: TokenStream,
+    ordered: bool,
 ) -> (TokenStream, TokenStream) {
     let ty = &field.ty;
 
     let end = quote! {
-        #start + <#ty as Packed>::SIZE
+        #start + <#ty as __packtool::Packed>::SIZE
+    };
+    let write = if ordered {
+        quote! { <#ty as __packtool::OrderedPacked>::unchecked_write_ordered }
+    } else {
+        quote! { <#ty as __packtool::Packed>::unchecked_write_to_slice }
     };
     let quote = if let Some(ident) = field.ident.as_ref() {
         quote! {
-            self.#ident.unchecked_write_to_slice(&mut slice[(#start)..(#end)])
+            #write(&self.#ident, &mut slice[(#start)..(#end)])
         }
     } else {
         quote! {
-            self.#index.unchecked_write_to_slice(&mut slice[(#start)..(#end)])
+            #write(&self.#index, &mut slice[(#start)..(#end)])
         }
     };
 
     (quote, end)
 }
 
-fn expand_write_to_slice_data_fields<'a, I>(fields: I) -> TokenStream
+fn expand_write_to_slice_data_fields<'a, I>(fields: I, ordered: bool) -> TokenStream
 where
     I: IntoIterator<Item = &'a PackedField>,
 {
@@ -835,8 +1123,13 @@ where
 
     let mut start = quote! { 0 };
     for (index, field) in fields.into_iter().enumerate() {
-        let (check, end) =
-            expand_write_to_slice_data_field(field, syn::Index::from(index), start.clone());
+        start = field_start(field, start);
+        let (check, end) = expand_write_to_slice_data_field(
+            field,
+            syn::Index::from(index),
+            start.clone(),
+            ordered,
+        );
         checks.push(check);
         start = end;
     }
@@ -844,8 +1137,8 @@ where
     quote! { #(#checks);* }
 }
 
-fn expand_write_to_slice_data_tuple(tuple: &PackedTuple) -> TokenStream {
-    let fields = expand_write_to_slice_data_fields(&tuple.fields);
+fn expand_write_to_slice_data_tuple(tuple: &PackedTuple, ordered: bool) -> TokenStream {
+    let fields = expand_write_to_slice_data_fields(&tuple.fields, ordered);
     quote! {
         fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
             use ::core::convert::TryInto as _;
@@ -855,8 +1148,8 @@ fn expand_write_to_slice_data_tuple(tuple: &PackedTuple) -> TokenStream {
     }
 }
 
-fn expand_write_to_slice_data_structure(structure: &PackedStruct) -> TokenStream {
-    let fields = expand_write_to_slice_data_fields(&structure.fields);
+fn expand_write_to_slice_data_structure(structure: &PackedStruct, ordered: bool) -> TokenStream {
+    let fields = expand_write_to_slice_data_fields(&structure.fields, ordered);
 
     quote! {
         fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
@@ -866,21 +1159,20 @@ fn expand_write_to_slice_data_structure(structure: &PackedStruct) -> TokenStream
 }
 
 fn expand_write_to_slice(container: &Container) -> TokenStream {
+    if let Some(fields) = crate::overlay::fields(container) {
+        return crate::overlay::expand_write(container, &fields);
+    }
+
+    let ordered = container.attributes.ordered.is_some();
     match &container.data {
         Data::Unit(_) => {
             expand_write_to_slice_data_unit_value(container.attributes.value.as_ref().unwrap())
         }
-        Data::Tuple(tuple) => expand_write_to_slice_data_tuple(tuple),
-        Data::Struct(structure) => expand_write_to_slice_data_structure(structure),
-        Data::Enum(enumeration) => expand_write_to_slice_data_enumeration(
-            container
-                .attributes
-                .repr
-                .as_ref()
-                .expect("Should have a repr on every enums"),
-            container.ident(),
-            enumeration,
-        ),
+        Data::Tuple(tuple) => expand_write_to_slice_data_tuple(tuple, ordered),
+        Data::Struct(structure) => expand_write_to_slice_data_structure(structure, ordered),
+        Data::Enum(enumeration) => {
+            expand_write_to_slice_data_enumeration(&container.attributes, enumeration)
+        }
     }
 }
 
@@ -888,34 +1180,41 @@ fn expand_field_accessor(
     field: &PackedField,
     index: usize,
     start: TokenStream,
+    ordered: bool,
 ) -> (TokenStream, TokenStream) {
     let ty = &field.ty;
+    let packed = field_packed_type(ty, ordered);
     let end = quote! {
-        #start + <#ty as Packed>::SIZE
+        #start + <#ty as __packtool::Packed>::SIZE
     };
 
-    let ident = match &field.attributes.accessor {
-        AccessorType::Ignore => return (quote! {}, end),
-        AccessorType::Custom(ident) => ident.clone(),
-        AccessorType::Default => {
-            if let Some(ident) = field.ident.as_ref() {
-                ident.clone()
-            } else {
-                syn::Ident::new(&format!("_{}", index), proc_macro2::Span::call_site())
-            }
-        }
+    let ident = match accessor_ident(field, index) {
+        Some(ident) => ident,
+        None => return (quote! {}, end),
     };
 
     let accessor = quote! {
-        pub fn #ident<'a>(view: ::packtool::View<'a, Self>) -> ::packtool::View<'a, #ty> {
-            ::packtool::View::unchecked_from_slice(&view.as_slice()[#start..#end])
+        pub fn #ident<'a>(view: __packtool::View<'a, Self>) -> __packtool::View<'a, #packed> {
+            __packtool::View::unchecked_from_slice(&view.as_slice()[#start..#end])
         }
     };
 
     (accessor, end)
 }
 
-fn expand_fields_accessors<'a, I>(fields: I) -> TokenStream
+/// the name of the accessor of the field, `None` if it has no accessor
+pub fn accessor_ident(field: &PackedField, index: usize) -> Option<syn::Ident> {
+    match &field.attributes.accessor {
+        AccessorType::Ignore => None,
+        AccessorType::Custom(ident) => Some(ident.clone()),
+        AccessorType::Default => Some(match field.ident.as_ref() {
+            Some(ident) => ident.clone(),
+            None => syn::Ident::new(&format!("_{}", index), proc_macro2::Span::call_site()),
+        }),
+    }
+}
+
+fn expand_fields_accessors<'a, I>(fields: I, ordered: bool) -> TokenStream
 where
     I: IntoIterator<Item = &'a PackedField>,
 {
@@ -923,7 +1222,8 @@ where
 
     let mut start = quote! { 0 };
     for (index, field) in fields.into_iter().enumerate() {
-        let (accessor, end) = expand_field_accessor(field, index, start.clone());
+        start = field_start(field, start);
+        let (accessor, end) = expand_field_accessor(field, index, start.clone(), ordered);
         fields_accessors.push(accessor);
         start = end;
     }
@@ -933,16 +1233,16 @@ where
     }
 }
 
-fn expand_tuple_accessors(tuple: &PackedTuple) -> TokenStream {
-    let fields_accessors = expand_fields_accessors(&tuple.fields);
+fn expand_tuple_accessors(tuple: &PackedTuple, ordered: bool) -> TokenStream {
+    let fields_accessors = expand_fields_accessors(&tuple.fields, ordered);
 
     quote! {
          #fields_accessors
     }
 }
 
-fn expand_structure_accessors(structure: &PackedStruct) -> TokenStream {
-    let fields_accessors = expand_fields_accessors(&structure.fields);
+fn expand_structure_accessors(structure: &PackedStruct, ordered: bool) -> TokenStream {
+    let fields_accessors = expand_fields_accessors(&structure.fields, ordered);
 
     quote! {
          #fields_accessors
@@ -950,6 +1250,13 @@ fn expand_structure_accessors(structure: &PackedStruct) -> TokenStream {
 }
 
 fn expand_accessors(container: &Container) -> TokenStream {
+    if let Some(fields) = crate::overlay::fields(container) {
+        if crate::overlay::is_any(container) {
+            return crate::overlay::expand_accessors_any(&fields);
+        }
+    }
+
+    let ordered = container.attributes.ordered.is_some();
     match &container.data {
         Data::Unit(_) => {
             // no accessor for the unit type
@@ -959,7 +1266,100 @@ fn expand_accessors(container: &Container) -> TokenStream {
             // no accessor for the enum type
             quote! {}
         }
-        Data::Tuple(tuple) => expand_tuple_accessors(tuple),
-        Data::Struct(structure) => expand_structure_accessors(structure),
+        Data::Tuple(tuple) => expand_tuple_accessors(tuple, ordered),
+        Data::Struct(structure) => expand_structure_accessors(structure, ordered),
+    }
+}
+
+fn expand_ordered_enumeration_order(
+    attributes: &PackedAttributes,
+    enumeration: &PackedEnum,
+) -> TokenStream {
+    let tag = enumeration_tag(attributes);
+    let discriminants = enumeration
+        .variants
+        .iter()
+        .map(|variant| expand_discriminant(enumeration.ident(), variant, &tag))
+        .collect::<Vec<_>>();
+    let pairs = discriminants
+        .iter()
+        .zip(discriminants.iter().skip(1))
+        .map(|(a, b)| quote! { #a < #b });
+
+    quote! {
+        const _: () = ::core::assert!(
+            true #( && #pairs )*,
+            "the variants of an ordered enum must be declared by increasing discriminant",
+        );
+    }
+}
+
+/// compile time checks that the discriminants of the enumeration fit
+/// in the type set with `#[packed(tag = ...)]`
+fn expand_discriminant_asserts(container: &Container) -> TokenStream {
+    let (tag, enumeration) = match (&container.attributes.tag_type, &container.data) {
+        (Some(tag), Data::Enum(enumeration)) => (tag, enumeration),
+        _ => return quote! {},
+    };
+    let ident = enumeration.ident();
+
+    let asserts = enumeration.variants.iter().map(|variant| {
+        let variant = &variant.ident;
+        let message = format!(
+            "the discriminant of {}::{} does not fit in the tag {}",
+            ident,
+            variant,
+            quote!(#tag),
+        );
+
+        quote::quote_spanned! {variant.span()=>
+            #[allow(clippy::eq_op)]
+            const _: () = ::core::assert!(
+                ((#ident::#variant as i128) >= 0 || <#tag>::MIN != 0)
+                    && (#ident::#variant as #tag) as i128 == #ident::#variant as i128,
+                #message,
+            );
+        }
+    });
+
+    quote! { #( #asserts )* }
+}
+
+/// the implementation of `OrderedPacked`: the types deriving
+/// `#[packed(ordered)]` and the units are already using the
+/// order preserving encoding.
+fn expand_ordered(container: &Container) -> TokenStream {
+    let ident = container.ident();
+
+    let order = match &container.data {
+        Data::Unit(_) => quote! {},
+        _ if container.attributes.ordered.is_none() => return quote! {},
+        Data::Enum(enumeration) => {
+            expand_ordered_enumeration_order(&container.attributes, enumeration)
+        }
+        Data::Tuple(_) | Data::Struct(_) => quote! {},
+    };
+
+    quote! {
+        #order
+
+        impl __packtool::OrderedPacked for #ident {
+            type Ordered = Self;
+
+            #[inline]
+            fn unchecked_read_ordered(slice: &[u8]) -> Self {
+                <Self as __packtool::Packed>::unchecked_read_from_slice(slice)
+            }
+
+            #[inline]
+            fn unchecked_write_ordered(&self, slice: &mut [u8]) {
+                <Self as __packtool::Packed>::unchecked_write_to_slice(self, slice)
+            }
+
+            #[inline]
+            fn check_ordered(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
+                <Self as __packtool::Packed>::check(slice)
+            }
+        }
     }
 }
@@ -1,5 +1,14 @@
+mod arbitrary;
 mod ast;
+mod builder;
+mod check;
+mod context;
+mod enumeration;
 mod expand;
+mod overlay;
+mod proptest;
+mod quickcheck;
+mod versions;
 
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
@@ -10,5 +19,23 @@ use crate::ast::Container;
 pub fn derive_packed(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as Container);
 
-    expand::packed_definitions(input).into()
+    expand::packed_definitions(input)
+        .unwrap_or_else(to_compile_errors)
+        .into()
+}
+
+#[proc_macro_derive(PackedVersions, attributes(packed))]
+pub fn derive_packed_versions(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+
+    versions::versions_definitions(input)
+        .unwrap_or_else(to_compile_errors)
+        .into()
+}
+
+/// every diagnostic of the derive as a `compile_error!`
+fn to_compile_errors(errors: Vec<syn::Error>) -> proc_macro2::TokenStream {
+    let errors = errors.iter().map(syn::Error::to_compile_error);
+
+    quote::quote! { #( #errors )* }
 }
@@ -0,0 +1,316 @@
+/*!
+generate the overlays: the structures marked with `#[packed(overlay)]`
+where every field is a different interpretation of the same bytes.
+
+All the fields start at byte 0 and the size of the overlay is the size
+of its largest field. The accessors, the reading and the checks reuse
+the expansion of the structures: the overlay is normalised so that all
+its fields have an offset of 0. Only the first field is written, it
+has to be the largest one so the other fields view written bytes.
+
+With `#[packed(check = "any")]` only one member needs to be valid:
+every field is an `Option`, `None` when the member is not valid. The
+accessors check their member and the first member set is written.
+*/
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+use crate::{
+    ast::{Container, Data, OverlayCheck, PackedField},
+    expand::{
+        accessor_ident, expand_check_all_data_field, expand_check_data_field,
+        expand_inspect_data_field, expand_write_to_slice_data_field,
+    },
+};
+
+/// `true` if only one member of the overlay needs to be valid
+pub fn is_any(container: &Container) -> bool {
+    matches!(container.attributes.check, Some(OverlayCheck::Any(_)))
+}
+
+/// the `T` of a field declared as `Option<T>`
+pub fn option_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let path = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+            match arguments.args.first()? {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// the fields of the container if it is an overlay
+pub fn fields(container: &Container) -> Option<Vec<&PackedField>> {
+    container.attributes.overlay.as_ref()?;
+
+    match &container.data {
+        Data::Tuple(tuple) => Some(tuple.fields.iter().collect()),
+        Data::Struct(structure) => Some(structure.fields.iter().collect()),
+        // already reported by the checks of the container
+        Data::Unit(_) | Data::Enum(_) => None,
+    }
+}
+
+/// set the offset of every field of the overlay to 0, with
+/// `check = "any"` the type of the fields is the type of the member
+/// (`T` instead of `Option<T>`)
+pub fn normalize(mut container: Container) -> Container {
+    if container.attributes.overlay.is_none() {
+        return container;
+    }
+    let any = is_any(&container);
+
+    let fields: Vec<&mut PackedField> = match &mut container.data {
+        Data::Tuple(tuple) => tuple.fields.iter_mut().collect(),
+        Data::Struct(structure) => structure.fields.iter_mut().collect(),
+        Data::Unit(_) | Data::Enum(_) => Vec::new(),
+    };
+    for field in fields {
+        field.attributes.offset = Some(syn::LitInt::new("0", Span::call_site()));
+        if any {
+            // checked to be an `Option` before the expansion
+            if let Some(ty) = option_type(&field.ty) {
+                field.ty = ty.clone();
+            }
+        }
+    }
+
+    container
+}
+
+pub fn expand_size(fields: &[&PackedField]) -> TokenStream {
+    let sizes = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote! {
+            if <#ty as __packtool::Packed>::SIZE > size {
+                size = <#ty as __packtool::Packed>::SIZE;
+            }
+        }
+    });
+
+    quote! {
+        {
+            let mut size = 0;
+            #( #sizes )*
+            size
+        }
+    }
+}
+
+pub fn expand_check(container: &Container, fields: &[&PackedField]) -> TokenStream {
+    let checks = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| expand_check_data_field(field, index, quote! { 0 }, false).0);
+
+    if is_any(container) {
+        quote! {
+            fn check(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
+                let mut error = ::core::option::Option::None;
+
+                #(
+                    {
+                        fn member(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
+                            use ::core::convert::TryInto as _;
+                            use __packtool::Context as _;
+
+                            #checks
+
+                            ::core::result::Result::Ok(())
+                        }
+
+                        match member(slice) {
+                            ::core::result::Result::Ok(()) => return ::core::result::Result::Ok(()),
+                            ::core::result::Result::Err(e) => {
+                                error.get_or_insert(e);
+                            }
+                        }
+                    }
+                )*
+
+                ::core::result::Result::Err(error.expect("an overlay has at least one member"))
+            }
+        }
+    } else {
+        quote! {
+            fn check(slice: &[u8]) -> ::core::result::Result<(), __packtool::Error> {
+                use ::core::convert::TryInto as _;
+                use __packtool::Context as _;
+
+                #( #checks )*
+
+                ::core::result::Result::Ok(())
+            }
+        }
+    }
+}
+
+pub fn expand_check_all(container: &Container, fields: &[&PackedField]) -> TokenStream {
+    let checks = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| expand_check_all_data_field(field, index, quote! { 0 }, false).0);
+
+    // with `check = "any"` the issues of every member are reported
+    // only if none of them is valid
+    let any = if is_any(container) {
+        quote! {
+            if <Self as __packtool::Packed>::check(slice).is_ok() {
+                return;
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        fn check_all(slice: &[u8], report: &mut __packtool::Report) {
+            #any
+            #( #checks )*
+        }
+    }
+}
+
+pub fn expand_inspect(fields: &[&PackedField]) -> TokenStream {
+    // every member is decoded, even the invalid ones
+    let inspects = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| expand_inspect_data_field(field, index, quote! { 0 }, false).0);
+
+    quote! {
+        fn inspect(slice: &[u8], inspector: &mut __packtool::Inspector) {
+            #( #inspects )*
+        }
+    }
+}
+
+pub fn expand_write(container: &Container, fields: &[&PackedField]) -> TokenStream {
+    if !is_any(container) {
+        let (write, _) =
+            expand_write_to_slice_data_field(fields[0], syn::Index::from(0), quote! { 0 }, false);
+
+        return quote! {
+            fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
+                #write
+            }
+        };
+    }
+
+    let writes = fields.iter().enumerate().map(|(index, field)| {
+        let ty = &field.ty;
+        let member = member(field, index);
+        quote! {
+            if let ::core::option::Option::Some(member) = &self.#member {
+                <#ty as __packtool::Packed>::unchecked_write_to_slice(
+                    member,
+                    &mut slice[..<#ty as __packtool::Packed>::SIZE],
+                );
+                return;
+            }
+        }
+    });
+
+    quote! {
+        fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
+            #( #writes )*
+        }
+    }
+}
+
+/// with `check = "any"`, only the valid members are read
+pub fn expand_read_any(container: &Container, fields: &[&PackedField]) -> TokenStream {
+    let reads = fields.iter().map(|field| {
+        let ty = &field.ty;
+        let read = quote! {
+            {
+                let member = &slice[..<#ty as __packtool::Packed>::SIZE];
+                match <#ty as __packtool::Packed>::check(member) {
+                    ::core::result::Result::Ok(()) => ::core::option::Option::Some(
+                        <#ty as __packtool::Packed>::unchecked_read_from_slice(member),
+                    ),
+                    ::core::result::Result::Err(_) => ::core::option::Option::None,
+                }
+            }
+        };
+        match field.ident.as_ref() {
+            Some(ident) => quote! { #ident: #read },
+            None => read,
+        }
+    });
+
+    let read = match &container.data {
+        Data::Tuple(_) => quote! { Self( #( #reads ),* ) },
+        _ => quote! { Self { #( #reads ),* } },
+    };
+
+    quote! {
+        fn unchecked_read_from_slice(slice: &[u8]) -> Self {
+            #read
+        }
+    }
+}
+
+/// with `check = "any"`, the accessors check their member
+pub fn expand_accessors_any(fields: &[&PackedField]) -> TokenStream {
+    let accessors = fields.iter().enumerate().filter_map(|(index, field)| {
+        let ty = &field.ty;
+        let ident = accessor_ident(field, index)?;
+
+        Some(quote! {
+            pub fn #ident<'a>(
+                view: __packtool::View<'a, Self>,
+            ) -> ::core::result::Result<__packtool::View<'a, #ty>, __packtool::Error> {
+                __packtool::View::try_from_slice(
+                    &view.as_slice()[..<#ty as __packtool::Packed>::SIZE],
+                )
+            }
+        })
+    });
+
+    quote! { #( #accessors )* }
+}
+
+/// the first member is the only one written: it is the largest so the
+/// other members view the written bytes
+pub fn expand_layout_asserts(container: &Container, fields: &[&PackedField]) -> TokenStream {
+    if is_any(container) {
+        return quote! {};
+    }
+
+    let ident = container.ident();
+    let first = &fields[0].ty;
+    let message = format!(
+        "the first member of the overlay `{}` is the one written, it must be the largest member",
+        ident
+    );
+
+    quote! {
+        const _: () = ::core::assert!(
+            <#first as __packtool::Packed>::SIZE == <#ident as __packtool::Packed>::SIZE,
+            #message,
+        );
+    }
+}
+
+fn member(field: &PackedField, index: usize) -> TokenStream {
+    match field.ident.as_ref() {
+        Some(ident) => quote! { #ident },
+        None => {
+            let index = syn::Index::from(index);
+            quote! { #index }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+/*!
+generate the `proptest::arbitrary::Arbitrary` implementation of the
+packed types marked with `#[packed(proptest)]`
+
+the generated strategies only produce valid packed values: enumerations
+only pick (and shrink toward) the declared variants and the unit types
+are always set to their expected `value`.
+*/
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    ast::{Container, Data, PackedField},
+    expand::unit_constructor,
+};
+
+pub fn expand(container: &Container) -> TokenStream {
+    if container.attributes.proptest.is_none() {
+        return quote! {};
+    }
+
+    let ident = container.ident();
+    let strategy = match &container.data {
+        Data::Unit(unit) => {
+            let constructor = unit_constructor(ident, &unit.from);
+            quote! {
+                __packtool::proptest::strategy::Just(()).prop_map(|()| #constructor)
+            }
+        }
+        Data::Tuple(tuple) => {
+            let (strategy, pattern, idents) = expand_fields(&tuple.fields);
+            quote! {
+                #strategy.prop_map(|#pattern| #ident ( #( #idents ),* ))
+            }
+        }
+        Data::Struct(structure) => {
+            let (strategy, pattern, idents) = expand_fields(&structure.fields);
+            quote! {
+                #strategy.prop_map(|#pattern| #ident { #( #idents ),* })
+            }
+        }
+        Data::Enum(enumeration) => {
+            let len = enumeration.variants.len();
+            let variants = enumeration
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(index, variant)| {
+                    let variant = &variant.ident;
+                    quote! { #index => #ident :: #variant }
+                });
+
+            // shrinking the index shrinks toward the first declared variant
+            quote! {
+                (0..#len).prop_map(|index| match index {
+                    #( #variants, )*
+                    _ => ::core::unreachable!(),
+                })
+            }
+        }
+    };
+
+    quote! {
+        impl __packtool::proptest::arbitrary::Arbitrary for #ident {
+            type Parameters = ();
+            type Strategy = __packtool::proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                use __packtool::proptest::strategy::Strategy as _;
+
+                #strategy.boxed()
+            }
+        }
+    }
+}
+
+/// the strategy of the fields is a nested tuple of strategy
+/// `(any::<A>(), (any::<B>(), Just(())))` so there is no limit
+/// on the number of fields.
+fn expand_fields<'a, I>(fields: I) -> (TokenStream, TokenStream, Vec<TokenStream>)
+where
+    I: IntoIterator<Item = &'a PackedField>,
+{
+    let mut strategy = quote! { __packtool::proptest::strategy::Just(()) };
+    let mut pattern = quote! { () };
+    let mut idents = Vec::new();
+
+    let fields = fields.into_iter().enumerate().collect::<Vec<_>>();
+    for (index, field) in fields.into_iter().rev() {
+        let ty = &field.ty;
+        let value = syn::Ident::new(&format!("field_{}", index), proc_macro2::Span::call_site());
+
+        strategy = quote! { (__packtool::proptest::arbitrary::any::<#ty>(), #strategy) };
+        pattern = quote! { (#value, #pattern) };
+        idents.push(if let Some(ident) = field.ident.as_ref() {
+            quote! { #ident: #value }
+        } else {
+            quote! { #value }
+        });
+    }
+    idents.reverse();
+
+    (strategy, pattern, idents)
+}
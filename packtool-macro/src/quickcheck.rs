@@ -0,0 +1,139 @@
+/*!
+generate the `quickcheck::Arbitrary` implementation of the packed types
+marked with `#[packed(quickcheck)]`
+
+the generated values and the shrunk values are always valid packed
+values: enumerations only shrink toward the variants declared before
+them and the unit types are never shrunk.
+*/
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    ast::{Container, Data, PackedField},
+    expand::unit_constructor,
+};
+
+pub fn expand(container: &Container) -> TokenStream {
+    if container.attributes.quickcheck.is_none() {
+        return quote! {};
+    }
+
+    let ident = container.ident();
+    let (arbitrary, shrink) = match &container.data {
+        Data::Unit(unit) => (unit_constructor(ident, &unit.from), None),
+        Data::Tuple(tuple) => {
+            let fields = expand_arbitrary_fields(&tuple.fields);
+            let shrink = expand_shrink_fields(&tuple.fields);
+            (quote! { #ident ( #fields ) }, Some(shrink))
+        }
+        Data::Struct(structure) => {
+            let fields = expand_arbitrary_fields(&structure.fields);
+            let shrink = expand_shrink_fields(&structure.fields);
+            (quote! { #ident { #fields } }, Some(shrink))
+        }
+        Data::Enum(enumeration) => {
+            let variants = enumeration
+                .variants
+                .iter()
+                .map(|variant| {
+                    let variant = &variant.ident;
+                    quote! { #ident :: #variant }
+                })
+                .collect::<Vec<_>>();
+            let indices = 0..variants.len();
+
+            let arbitrary = quote! {
+                g.choose(&[ #( #variants ),* ]).unwrap().clone()
+            };
+            // `Box` is the only path to `std`: it is in the signature
+            // of `quickcheck::Arbitrary::shrink`
+            let shrink = quote! {
+                let index = match self {
+                    #( #variants => #indices, )*
+                };
+                ::std::boxed::Box::new(
+                    ::core::iter::IntoIterator::into_iter([ #( #variants ),* ]).take(index)
+                )
+            };
+
+            (arbitrary, Some(shrink))
+        }
+    };
+
+    let shrink = shrink.map(|shrink| {
+        quote! {
+            fn shrink(&self) -> ::std::boxed::Box<dyn ::core::iter::Iterator<Item = Self>> {
+                use ::core::{
+                    clone::Clone as _,
+                    iter::{IntoIterator as _, Iterator as _},
+                };
+
+                #shrink
+            }
+        }
+    });
+
+    quote! {
+        impl __packtool::quickcheck::Arbitrary for #ident {
+            #[allow(unused_variables)]
+            fn arbitrary(g: &mut __packtool::quickcheck::Gen) -> Self {
+                use ::core::clone::Clone as _;
+
+                #arbitrary
+            }
+
+            #shrink
+        }
+    }
+}
+
+fn expand_arbitrary_fields<'a, I>(fields: I) -> TokenStream
+where
+    I: IntoIterator<Item = &'a PackedField>,
+{
+    let fields = fields.into_iter().map(|field| {
+        let value = quote! { __packtool::quickcheck::Arbitrary::arbitrary(g) };
+        if let Some(ident) = field.ident.as_ref() {
+            quote! { #ident: #value }
+        } else {
+            value
+        }
+    });
+
+    quote! { #( #fields ),* }
+}
+
+/// shrink every field one after the other, keeping the other fields
+/// to their current value
+fn expand_shrink_fields<'a, I>(fields: I) -> TokenStream
+where
+    I: IntoIterator<Item = &'a PackedField>,
+{
+    let shrinks = fields.into_iter().enumerate().map(|(index, field)| {
+        let member = if let Some(ident) = field.ident.as_ref() {
+            quote! { #ident }
+        } else {
+            let index = syn::Index::from(index);
+            quote! { #index }
+        };
+
+        quote! {
+            .chain({
+                let this = self.clone();
+                __packtool::quickcheck::Arbitrary::shrink(&self.#member).map(move |value| {
+                    let mut this = this.clone();
+                    this.#member = value;
+                    this
+                })
+            })
+        }
+    });
+
+    quote! {
+        ::std::boxed::Box::new(
+            ::core::iter::empty() #( #shrinks )*
+        )
+    }
+}
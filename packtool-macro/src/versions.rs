@@ -0,0 +1,338 @@
+/*!
+generate the versioned record families: `#[derive(PackedVersions)]`
+dispatches a slice to the `View` of the structure matching the version
+tag and `#[packed(upgrade(Previous))]` generates the `From` conversion
+from the previous version of a structure.
+
+```ignore
+#[derive(PackedVersions)]
+#[packed(tag(Version), tag_offset = 4, upgrade)]
+enum AnyHeader<'a> {
+    #[packed(version(Version::V1))]
+    V1(View<'a, HeaderV1>),
+    #[packed(version(Version::V2))]
+    V2(View<'a, HeaderV2>),
+}
+```
+*/
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Result;
+
+use crate::{
+    ast::{Container, Data, DefaultValue, PackedAttributes},
+    context::Context,
+};
+
+/// the `From<Previous>` implementation of a structure marked with
+/// `#[packed(upgrade(Previous))]`, the field marked with
+/// `#[packed(version(...))]` is set to the version of the structure
+pub fn expand_upgrade(container: &Container) -> TokenStream {
+    let (previous, structure) = match (&container.attributes.upgrade_from, &container.data) {
+        (Some(previous), Data::Struct(structure)) => (previous, structure),
+        // already reported by the checks of the container
+        _ => return quote! {},
+    };
+    let ident = container.ident();
+
+    let fields = structure.fields.iter().map(|field| {
+        let name = &field.ident;
+        if let Some(version) = field.attributes.version.as_ref() {
+            return quote! { #name: #version };
+        }
+        match &field.attributes.default {
+            None => quote! { #name: ::core::convert::Into::into(previous.#name) },
+            Some(DefaultValue::Default(_)) => {
+                quote! { #name: ::core::default::Default::default() }
+            }
+            Some(DefaultValue::Expr(_, expr)) => quote! { #name: #expr },
+        }
+    });
+
+    quote! {
+        impl ::core::convert::From<#previous> for #ident {
+            #[allow(clippy::useless_conversion, unused_variables)]
+            fn from(previous: #previous) -> Self {
+                Self {
+                    #( #fields, )*
+                }
+            }
+        }
+    }
+}
+
+struct Version<'a> {
+    ident: &'a syn::Ident,
+    tag: syn::Path,
+    ty: syn::Type,
+}
+
+pub fn versions_definitions(
+    input: syn::DeriveInput,
+) -> std::result::Result<TokenStream, Vec<syn::Error>> {
+    let mut context = Context::default();
+    let attributes = PackedAttributes::from_attributes(&input.attrs);
+    check(&mut context, &attributes);
+
+    let variants = match &input.data {
+        syn::Data::Enum(enumeration) => enumeration.variants.iter().collect(),
+        _ => {
+            context.add_error_by(&input.ident, "PackedVersions is only available on enums");
+            Vec::new()
+        }
+    };
+    if attributes.tag.is_none() {
+        context.add_error_by(
+            &input.ident,
+            "Expecting the type of the version tag (#[packed(tag(...))])",
+        );
+    }
+    if input.generics.lifetimes().next().is_none() {
+        context.add_error_by(
+            &input.generics,
+            "Expecting the lifetime of the views (`enum Any<'a> { ... }`)",
+        );
+    }
+
+    let versions = variants
+        .into_iter()
+        .filter_map(|variant| match version(variant) {
+            Ok(version) => Some(version),
+            Err(error) => {
+                context.add_error(error);
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    if versions.is_empty() && matches!(input.data, syn::Data::Enum(_)) {
+        context.add_error_by(&input.ident, "Expecting at least one version");
+    }
+    context.check()?;
+
+    Ok(expand(&input, &attributes, &versions))
+}
+
+fn expand(
+    input: &syn::DeriveInput,
+    attributes: &PackedAttributes,
+    versions: &[Version],
+) -> TokenStream {
+    // all checked in `versions_definitions`
+    let (tag, latest, lifetime) = match (
+        attributes.tag.as_ref(),
+        versions.last(),
+        input.generics.lifetimes().next(),
+    ) {
+        (Some(tag), Some(latest), Some(lifetime)) => (tag, latest, &lifetime.lifetime),
+        _ => return quote! {},
+    };
+    let tag_offset = match attributes.tag_offset.as_ref() {
+        Some(offset) => quote! { #offset },
+        None => quote! { 0 },
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let options = versions
+        .iter()
+        .map(|version| {
+            let tag = &version.tag;
+            format!("{}, ", quote!(#tag).to_string().replace(' ', ""))
+        })
+        .collect::<String>();
+    let dispatches = versions.iter().map(|version| {
+        let variant = version.ident;
+        let tag = &version.tag;
+        let ty = &version.ty;
+        quote! {
+            #tag => __packtool::View::<#lifetime, #ty>::try_from_slice(slice).map(Self::#variant)
+        }
+    });
+    let tags = versions.iter().map(|version| {
+        let variant = version.ident;
+        let tag = &version.tag;
+        quote! { Self::#variant(_) => #tag }
+    });
+    let slices = versions.iter().map(|version| {
+        let variant = version.ident;
+        quote! { Self::#variant(view) => view.as_slice() }
+    });
+    let froms = versions.iter().map(|version| {
+        let variant = version.ident;
+        let ty = &version.ty;
+        quote! {
+            impl #impl_generics ::core::convert::From<__packtool::View<#lifetime, #ty>> for #ident #ty_generics #where_clause {
+                fn from(view: __packtool::View<#lifetime, #ty>) -> Self {
+                    Self::#variant(view)
+                }
+            }
+        }
+    });
+    let asserts = versions.iter().map(|version| {
+        let ty = &version.ty;
+        let message = format!(
+            "the version tag does not fit in {}",
+            quote!(#ty)
+        );
+        quote! {
+            const _: () = ::core::assert!(
+                #tag_offset + <#tag as __packtool::Packed>::SIZE <= <#ty as __packtool::Packed>::SIZE,
+                #message
+            );
+        }
+    });
+
+    let upgrade = if attributes.upgrade.is_some() {
+        let latest_ty = &latest.ty;
+        let upgrades = versions.iter().enumerate().map(|(index, version)| {
+            let variant = version.ident;
+            let steps = versions[index + 1..].iter().map(|next| {
+                let ty = &next.ty;
+                quote! { let value = <#ty as ::core::convert::From<_>>::from(value); }
+            });
+            quote! {
+                Self::#variant(view) => {
+                    let value = view.unpack();
+                    #( #steps )*
+                    value
+                }
+            }
+        });
+        let doc = format!(
+            "unpack the record and upgrade it to the latest version: [`{}`]",
+            quote!(#latest_ty)
+        );
+
+        quote! {
+            #[doc = #doc]
+            pub fn upgrade(self) -> #latest_ty {
+                match self {
+                    #( #upgrades )*
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let definitions = quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// read the version tag and check the slice against the
+            /// matching version of the record
+            #[allow(unreachable_patterns)]
+            pub fn try_from_slice(slice: &#lifetime [u8]) -> ::core::result::Result<Self, __packtool::Error> {
+                const START: usize = #tag_offset;
+                const END: usize = START + <#tag as __packtool::Packed>::SIZE;
+
+                if slice.len() < END {
+                    return ::core::result::Result::Err(__packtool::Error::invalid_size::<Self>(slice.len(), END));
+                }
+                let tag = __packtool::View::<#tag>::try_from_slice(&slice[START..END])?.unpack();
+
+                match tag {
+                    #( #dispatches, )*
+                    _ => ::core::result::Result::Err(__packtool::Error::invalid_discriminant::<Self, _>(
+                        slice[START..END].to_vec(),
+                        #options,
+                    )),
+                }
+            }
+
+            /// the version tag of the record
+            pub fn tag(&self) -> #tag {
+                match self {
+                    #( #tags, )*
+                }
+            }
+
+            /// the bytes of the record, whatever its version
+            pub fn as_slice(&self) -> &#lifetime [u8] {
+                match self {
+                    #( #slices, )*
+                }
+            }
+
+            #upgrade
+        }
+
+        #( #froms )*
+        #( #asserts )*
+    };
+
+    crate::expand::wrap(attributes, definitions)
+}
+
+fn check(context: &mut Context, attributes: &PackedAttributes) {
+    for error in attributes.errors.iter() {
+        context.add_error(error.clone());
+    }
+
+    let allowed = ["tag", "tag_offset", "upgrade", "crate"];
+    for (name, span) in attributes.spans() {
+        if !allowed.contains(&name) {
+            context.add_error(syn::Error::new(
+                span,
+                "Cannot have this attribute on the versions, only tag, tag_offset and upgrade",
+            ));
+        }
+    }
+}
+
+/// a variant of the versions: `#[packed(version(Tag::V1))] V1(View<'a, HeaderV1>)`
+fn version(variant: &syn::Variant) -> Result<Version<'_>> {
+    let attributes = PackedAttributes::from_attributes(&variant.attrs);
+    if let Some(error) = attributes.errors.into_iter().reduce(|mut error, next| {
+        error.combine(next);
+        error
+    }) {
+        return Err(error);
+    }
+    let tag = attributes.version.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &variant.ident,
+            "Expecting the version tag of this variant (#[packed(version(...))])",
+        )
+    })?;
+
+    let field = match &variant.fields {
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+        _ => {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "Expecting a single `View` of the record: `V1(View<'a, HeaderV1>)`",
+            ))
+        }
+    };
+
+    Ok(Version {
+        ident: &variant.ident,
+        tag,
+        ty: viewed_type(&field.ty)?,
+    })
+}
+
+/// the `T` in `View<'a, T>`
+fn viewed_type(ty: &syn::Type) -> Result<syn::Type> {
+    let error = || syn::Error::new_spanned(ty, "Expecting a `View<'a, T>`");
+
+    let segment = match ty {
+        syn::Type::Path(path) => path.path.segments.last().ok_or_else(error)?,
+        _ => return Err(error()),
+    };
+    if segment.ident != "View" {
+        return Err(error());
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) => arguments
+            .args
+            .iter()
+            .find_map(|argument| match argument {
+                syn::GenericArgument::Type(ty) => Some(ty.clone()),
+                _ => None,
+            })
+            .ok_or_else(error),
+        _ => Err(error()),
+    }
+}
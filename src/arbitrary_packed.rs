@@ -0,0 +1,37 @@
+use crate::{ByteArray, OrderedPacked, Packed};
+use arbitrary::Unstructured;
+
+/// packed types whose valid bytes can be generated from
+/// [`arbitrary::Unstructured`] data, without building the value first
+///
+/// see [`Packet::arbitrary_valid`]. The bytes written always pass the
+/// [`Packed::check`] of the type: the enumerations only write their
+/// declared discriminants and the unit types their `value`, the other
+/// bytes are taken from the unstructured data as they are. The overlays
+/// are written one member at a time, and return
+/// [`arbitrary::Error::IncorrectFormat`] if no member gives bytes valid
+/// for the other ones.
+///
+/// `#[derive(Packed)]` implements it with the `#[packed(arbitrary)]`
+/// attribute, along with [`arbitrary::Arbitrary`].
+///
+/// [`Packet::arbitrary_valid`]: crate::Packet::arbitrary_valid
+pub trait ArbitraryPacked: Packed {
+    /// write random but valid bytes in `slice`
+    ///
+    /// the `slice` is [`Packed::SIZE`] bytes long and set to `0`, the
+    /// bytes left untouched (the padding) remain `0`.
+    fn arbitrary_write(u: &mut Unstructured<'_>, slice: &mut [u8]) -> arbitrary::Result<()>;
+
+    /// write random but valid bytes of the order preserving encoding of
+    /// `Self` in `slice`, see [`OrderedPacked`]
+    fn arbitrary_write_ordered(u: &mut Unstructured<'_>, slice: &mut [u8]) -> arbitrary::Result<()>
+    where
+        Self: OrderedPacked,
+    {
+        let mut bytes = <Self::Bytes as ByteArray>::zeroed(Self::SIZE);
+        Self::arbitrary_write(u, bytes.as_mut())?;
+        Self::unchecked_read_from_slice(bytes.as_ref()).unchecked_write_ordered(slice);
+        Ok(())
+    }
+}
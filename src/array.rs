@@ -1,8 +1,12 @@
-use crate::{Error, Packed};
+use crate::{
+    builder::{BuilderField, Unset},
+    Error, Packed,
+};
 use std::convert::TryInto as _;
 
 impl<const SIZE: usize> Packed for [u8; SIZE] {
     const SIZE: usize = SIZE;
+    type Bytes = [u8; SIZE];
 
     #[inline]
     fn check(_slice: &[u8]) -> Result<(), Error> {
@@ -25,6 +29,21 @@ impl<const SIZE: usize> Packed for [u8; SIZE] {
     }
 }
 
+impl<const SIZE: usize> BuilderField for [u8; SIZE] {
+    type Initial = Unset;
+}
+
+#[cfg(feature = "arbitrary")]
+impl<const SIZE: usize> crate::ArbitraryPacked for [u8; SIZE] {
+    #[inline]
+    fn arbitrary_write(
+        u: &mut arbitrary::Unstructured<'_>,
+        slice: &mut [u8],
+    ) -> arbitrary::Result<()> {
+        u.fill_buffer(slice)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use quickcheck::{Arbitrary, Gen};
@@ -0,0 +1,192 @@
+/*!
+typestate builders for the packed structures.
+
+With the `#[packed(builder)]` attribute, `#[derive(Packed)]` generates
+a builder that writes each field directly into the packed bytes. The
+fields holding a constant value (the unit types) are already filled
+and the `build` function is only available once every other field has
+been set.
+
+```
+use packtool::{Packed, Packet, View};
+
+#[derive(Packed)]
+#[packed(value = "packcoin")]
+pub struct Tag;
+
+#[derive(Packed, Debug, PartialEq)]
+#[repr(u8)]
+pub enum Version {
+    V1 = 1,
+    V2 = 2,
+}
+
+#[derive(Packed)]
+#[packed(builder)]
+pub struct Header {
+    tag: Tag,
+    version: Version,
+    block_number: u32,
+}
+
+# let other = Packet::pack(&Version::V2);
+let header: Packet<Header> = Header::builder()
+    .version(Version::V1)
+    .block_number(42)
+    .build();
+# assert_eq!(Header::version(header.view()).unpack(), Version::V1);
+# assert_eq!(Header::block_number(header.view()).unpack(), 42);
+
+// a field can also be set from a view
+let header = Header::builder()
+    .block_number(Header::block_number(header.view()))
+    .version(other.view())
+    .build();
+# assert_eq!(Header::version(header.view()).unpack(), Version::V2);
+# assert_eq!(Header::block_number(header.view()).unpack(), 42);
+```
+
+Forgetting to set a field is a compilation error:
+
+```compile_fail
+# use packtool::{Packed, Packet, View};
+#
+# #[derive(Packed)]
+# #[repr(u8)]
+# pub enum Version {
+#     V1 = 1,
+#     V2 = 2,
+# }
+#
+#[derive(Packed)]
+#[packed(builder)]
+pub struct Header {
+    version: Version,
+    block_number: u32,
+}
+
+let header: Packet<Header> = Header::builder()
+    .block_number(42)
+    .build();
+```
+*/
+
+use crate::{Error, Packed, Packet, View};
+use std::{fmt, marker, ops::Range};
+
+/// typestate of a field that has been set in the builder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Set;
+
+/// typestate of a field that has not been set yet in the builder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Unset;
+
+/// defines how a [`Packed`] type starts in a generated builder.
+///
+/// This is implemented by `#[derive(Packed)]` for every packed type:
+/// unit types are [`Set`] (their value is constant) and any other type
+/// is [`Unset`]. Implement it for your own [`Packed`] types in order to
+/// use them as fields of a structure with a builder.
+pub trait BuilderField: Packed {
+    /// [`Set`] if the type holds a constant value, [`Unset`] otherwise
+    type Initial;
+
+    /// write the constant value of the type, if any, in the slice
+    ///
+    /// the slice is zeroed and is exactly [`Packed::SIZE`] long.
+    #[inline]
+    fn prefill(_slice: &mut [u8]) {}
+}
+
+/// a value that can be set in a field of type `T` of a builder
+///
+/// Implemented for `T` itself and for any [`View`] of `T`.
+pub trait FieldValue<T: Packed> {
+    /// write the value in the given slice of [`Packed::SIZE`] bytes
+    fn write_to_slice(self, slice: &mut [u8]);
+}
+
+/// the bytes of a packed object in construction
+///
+/// used by the generated builders, the [`Packet`] only comes out of
+/// [`Draft::finish`] once its bytes are valid.
+pub struct Draft<T> {
+    bytes: Box<[u8]>,
+    marker: marker::PhantomData<T>,
+}
+
+impl<T> FieldValue<T> for T
+where
+    T: Packed,
+{
+    #[inline]
+    fn write_to_slice(self, slice: &mut [u8]) {
+        self.unchecked_write_to_slice(slice)
+    }
+}
+
+impl<'a, T> FieldValue<T> for View<'a, T>
+where
+    T: Packed,
+{
+    #[inline]
+    fn write_to_slice(self, slice: &mut [u8]) {
+        slice.copy_from_slice(self.as_slice())
+    }
+}
+
+impl<T> Draft<T>
+where
+    T: BuilderField,
+{
+    /// allocate the bytes of `T` and fill the constant values
+    pub fn new() -> Self {
+        let mut bytes = vec![0; T::SIZE].into_boxed_slice();
+        T::prefill(&mut bytes);
+
+        Self {
+            bytes,
+            marker: marker::PhantomData,
+        }
+    }
+
+    /// write the value of the field `U` at the given range
+    #[inline]
+    pub fn write<U, V>(&mut self, range: Range<usize>, value: V)
+    where
+        U: Packed,
+        V: FieldValue<U>,
+    {
+        debug_assert_eq!(range.len(), U::SIZE);
+        value.write_to_slice(&mut self.bytes[range])
+    }
+
+    /// check the bytes and turn them into a [`Packet`]
+    ///
+    /// the generated builder only calls this function once all the
+    /// fields have been set, the bytes are checked anyway as the
+    /// [`Draft`] can be used without the builder.
+    pub fn finish(self) -> Result<Packet<T>, Error> {
+        T::check(&self.bytes)?;
+        Ok(Packet::new(self.bytes))
+    }
+}
+
+impl<T> Default for Draft<T>
+where
+    T: BuilderField,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for Draft<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ty = std::any::type_name::<T>();
+        f.debug_struct(&format!("Draft<{}>", ty))
+            .field("bytes", &self.bytes)
+            .finish()
+    }
+}
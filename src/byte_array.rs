@@ -0,0 +1,32 @@
+/// the fixed size byte storage of a packed object
+///
+/// see [`Packed::Bytes`]. It is implemented for `[u8; N]` so the
+/// encoding of a derived type lives on the stack, and for `Box<[u8]>`
+/// as a fallback for the generic types (such as the tuples) for which
+/// the size cannot be expressed as an array length on stable rust.
+///
+/// [`Packed::Bytes`]: crate::Packed::Bytes
+pub trait ByteArray: AsRef<[u8]> + AsMut<[u8]> + Clone {
+    /// create a storage of `size` bytes, all set to `0`
+    ///
+    /// `size` is always the [`Packed::SIZE`] of the type the storage
+    /// is used for.
+    ///
+    /// [`Packed::SIZE`]: crate::Packed::SIZE
+    fn zeroed(size: usize) -> Self;
+}
+
+impl<const N: usize> ByteArray for [u8; N] {
+    #[inline]
+    fn zeroed(size: usize) -> Self {
+        debug_assert_eq!(size, N);
+        [0; N]
+    }
+}
+
+impl ByteArray for Box<[u8]> {
+    #[inline]
+    fn zeroed(size: usize) -> Self {
+        vec![0; size].into_boxed_slice()
+    }
+}
@@ -0,0 +1,207 @@
+/*!
+[`tokio_util::codec`] support for the fixed size packed frames
+
+every frame on the stream is exactly `T::SIZE` bytes long. The
+decoded frames are checked with [`Packed::check`] before being
+returned.
+
+```
+use futures::{SinkExt as _, StreamExt as _};
+use packtool::{codec::PackedCodec, Packed};
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+#[derive(Packed)]
+pub struct Ping {
+    nonce: u64,
+}
+
+# #[tokio::main(flavor = "current_thread")]
+# async fn main() -> Result<(), packtool::Error> {
+let (client, server) = tokio::io::duplex(64);
+let mut writer = FramedWrite::new(client, PackedCodec::<Ping>::new());
+let mut reader = FramedRead::new(server, PackedCodec::<Ping>::new());
+
+writer.send(&Ping { nonce: 42 }).await?;
+
+let ping = reader.next().await.unwrap()?;
+assert_eq!(Ping::nonce(ping.view()).unpack(), 42);
+# Ok(()) }
+```
+*/
+
+use crate::{Error, Packed, Packet, View};
+use bytes::{BufMut as _, Bytes, BytesMut};
+use std::{fmt, marker};
+use tokio_util::codec::{Decoder, Encoder};
+
+/// what the [`PackedCodec`] does when it receives an invalid frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidFrame {
+    /// stop and return the error of the check
+    #[default]
+    Error,
+    /// drop the whole frame and continue with the next one
+    Skip,
+    /// drop one byte at a time until a valid frame is found
+    ///
+    /// useful on streams where the frame boundaries may have been
+    /// lost, assuming the frames hold some constant value (magic
+    /// number or discriminant) to synchronize on.
+    Resync,
+}
+
+/// [`Decoder`] and [`Encoder`] of the fixed size packed frames `T`
+///
+/// the decoded frames are [`Packet`]s sharing the memory of the read
+/// buffer: no copy is performed.
+///
+/// the zero sized types would be decoded endlessly without consuming
+/// the stream, they are rejected when the decoder is instantiated:
+///
+/// ```compile_fail
+/// # use packtool::codec::PackedCodec;
+/// # use tokio_util::codec::Decoder as _;
+/// let mut codec = PackedCodec::<()>::new();
+/// let _ = codec.decode(&mut bytes::BytesMut::new());
+/// ```
+pub struct PackedCodec<T> {
+    on_invalid: InvalidFrame,
+    discarded: usize,
+    marker: marker::PhantomData<fn() -> T>,
+}
+
+impl<T> PackedCodec<T> {
+    /// create a codec returning an error on invalid frames
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            on_invalid: InvalidFrame::Error,
+            discarded: 0,
+            marker: marker::PhantomData,
+        }
+    }
+
+    /// set the behaviour of the decoder when an invalid frame is received
+    #[inline]
+    pub fn on_invalid(self, on_invalid: InvalidFrame) -> Self {
+        Self { on_invalid, ..self }
+    }
+
+    /// the number of bytes that have been dropped because they
+    /// were not part of a valid frame
+    #[inline]
+    pub fn discarded(&self) -> usize {
+        self.discarded
+    }
+}
+
+/// compile time check that the frames `T` are not empty
+struct NotEmpty<T>(marker::PhantomData<T>);
+
+impl<T: Packed> NotEmpty<T> {
+    const ASSERT: () = assert!(T::SIZE > 0, "cannot decode the frames of a zero sized type");
+}
+
+impl<T> Decoder for PackedCodec<T>
+where
+    T: Packed,
+{
+    type Item = Packet<T, Bytes>;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        #[allow(clippy::let_unit_value)]
+        let () = NotEmpty::<T>::ASSERT;
+
+        loop {
+            if src.len() < T::SIZE {
+                src.reserve(T::SIZE - src.len());
+                return Ok(None);
+            }
+
+            match T::check(&src[..T::SIZE]) {
+                Ok(()) => {
+                    let frame = src.split_to(T::SIZE).freeze();
+                    return Ok(Some(Packet::unchecked_from_storage(frame)));
+                }
+                Err(error) => {
+                    let dropped = match self.on_invalid {
+                        InvalidFrame::Error => T::SIZE,
+                        InvalidFrame::Skip => T::SIZE,
+                        InvalidFrame::Resync => 1,
+                    };
+                    let _ = src.split_to(dropped);
+                    self.discarded += dropped;
+
+                    if self.on_invalid == InvalidFrame::Error {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(frame) => Ok(Some(frame)),
+            None if src.is_empty() => Ok(None),
+            None => {
+                let remaining = src.len();
+                src.clear();
+                Err(Error::truncated::<T>(0, remaining))
+            }
+        }
+    }
+}
+
+impl<'a, T> Encoder<&'a T> for PackedCodec<T>
+where
+    T: Packed,
+{
+    type Error = Error;
+
+    fn encode(&mut self, item: &'a T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let start = dst.len();
+        dst.put_bytes(0, T::SIZE);
+        item.unchecked_write_to_slice(&mut dst[start..]);
+        Ok(())
+    }
+}
+
+impl<'a, T> Encoder<View<'a, T>> for PackedCodec<T>
+where
+    T: Packed,
+{
+    type Error = Error;
+
+    fn encode(&mut self, item: View<'a, T>, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        dst.extend_from_slice(item.as_slice());
+        Ok(())
+    }
+}
+
+impl<T> Default for PackedCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for PackedCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            on_invalid: self.on_invalid,
+            discarded: self.discarded,
+            marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for PackedCodec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ty = std::any::type_name::<T>();
+        f.debug_struct(&format!("PackedCodec<{}>", ty))
+            .field("on_invalid", &self.on_invalid)
+            .field("discarded", &self.discarded)
+            .finish()
+    }
+}
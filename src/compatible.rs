@@ -0,0 +1,39 @@
+use crate::Packed;
+use std::marker::PhantomData;
+
+/// marker trait for the packed types whose valid bytes are always valid
+/// bytes of `U`
+///
+/// Implementing this trait allows [`View::cast`] to reinterpret a view
+/// without running `U::check`. Only implement it when every slice
+/// accepted by `Self::check` is also accepted by `U::check` (for
+/// example an opaque `[u8; 32]` and a `Hash` wrapping it). Both types
+/// must have the same size, this is verified at compile time.
+///
+/// ```
+/// use packtool::{Compatible, Packed, Packet};
+///
+/// #[derive(Packed)]
+/// pub struct Hash([u8; 32]);
+///
+/// impl Compatible<[u8; 32]> for Hash {}
+///
+/// let hash = Packet::pack(&Hash([1; 32]));
+/// let bytes = hash.view().cast::<[u8; 32]>();
+/// assert_eq!(bytes.unpack(), [1; 32]);
+/// ```
+///
+/// [`View::cast`]: crate::View::cast
+pub trait Compatible<U: Packed>: Packed {}
+
+impl<T: Packed> Compatible<T> for T {}
+
+/// compile time check that `T` and `U` have the same size
+pub(crate) struct SameSize<T, U>(PhantomData<(T, U)>);
+
+impl<T: Packed, U: Packed> SameSize<T, U> {
+    pub(crate) const ASSERT: () = assert!(
+        T::SIZE == U::SIZE,
+        "cannot reinterpret a packed type as a type of a different size"
+    );
+}
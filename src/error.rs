@@ -30,10 +30,10 @@ use thiserror::Error;
 macro_rules! ensure {
     ($Type:ty, $assumption:expr, $fmt:expr, $($arg:tt)*) => {
         if !$assumption {
-            return ::std::result::Result::Err(
+            return ::core::result::Result::Err(
                 $crate::Error::Assumption {
-                    ty: ::std::any::type_name::<$Type>(),
-                    assumption: ::std::stringify!($assumption),
+                    ty: ::core::any::type_name::<$Type>(),
+                    assumption: ::core::stringify!($assumption),
                     message: ::std::format!($fmt, $($arg)*),
                 }
             );
@@ -85,6 +85,85 @@ pub enum Error {
         found: Box<dyn fmt::Debug + Send + Sync>,
     },
 
+    /// error when a slice holding a sequence of records does not end
+    /// on a record boundary
+    #[error("Truncated record of {ty}: {remaining} trailing byte(s) after {records} record(s)")]
+    Truncated {
+        /// the stringified type associated to this error
+        ty: &'static str,
+        /// the number of complete records found before the trailing bytes
+        records: usize,
+        /// the number of trailing bytes
+        remaining: usize,
+    },
+
+    /// error when the records of a [`SortedTable`](crate::SortedTable)
+    /// are not sorted by their key
+    #[error("Records of {ty} are not sorted: record {index} is lower than the previous one")]
+    NotSorted {
+        /// the stringified type associated to this error
+        ty: &'static str,
+        /// the index of the first record out of order
+        index: usize,
+    },
+
+    /// error when the bytes of a [`FixedStr`](crate::FixedStr) are not
+    /// a valid string, or when a string cannot be stored in it
+    #[error("Invalid string for {ty}: {reason}")]
+    InvalidString {
+        /// the stringified type associated to this error
+        ty: &'static str,
+        /// why the string is not valid
+        reason: Cow<'static, str>,
+    },
+
+    /// error when a string does not fit in a [`FixedStr`](crate::FixedStr)
+    #[error("String too long for {ty}: {length} bytes but the capacity is {capacity} bytes")]
+    StringTooLong {
+        /// the stringified type associated to this error
+        ty: &'static str,
+        /// the length of the encoded string
+        length: usize,
+        /// the size of the fixed string
+        capacity: usize,
+    },
+
+    /// error when the digits of a [`Number`](crate::number::Number)
+    /// are not valid
+    #[error("Invalid number for {ty}: {reason}")]
+    InvalidNumber {
+        /// the stringified type associated to this error
+        ty: &'static str,
+        /// why the digits are not valid
+        reason: Cow<'static, str>,
+    },
+
+    /// error when a value needs more digits than a
+    /// [`Number`](crate::number::Number) has
+    #[error("Number too large for {ty}: {value} is greater than {max}")]
+    NumberTooLarge {
+        /// the stringified type associated to this error
+        ty: &'static str,
+        /// the value that does not fit
+        value: u64,
+        /// the largest value of the number
+        max: u64,
+    },
+
+    /// error when an [`Offset`](crate::Offset) points outside of its
+    /// [`Buffer`](crate::Buffer)
+    #[error("Out of bounds {ty}: {size} bytes at {position} in a buffer of {length} bytes")]
+    OutOfBounds {
+        /// the stringified type associated to this error
+        ty: &'static str,
+        /// the position the offset points to
+        position: i128,
+        /// the size of the record pointed to
+        size: usize,
+        /// the length of the buffer
+        length: usize,
+    },
+
     #[error("Field {field} of {ty} is not valid")]
     InvalidField {
         /// the stringified type associated to this error
@@ -99,6 +178,11 @@ pub enum Error {
         index: usize,
     },
 
+    /// error from the underlying I/O, for example when reading
+    /// packed frames from a stream
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
     #[error("{0}")]
     Message(Cow<'static, str>),
 
@@ -150,6 +234,81 @@ impl Error {
         }
     }
 
+    /// convenient function to create an [`Error::Truncated`]
+    /// with the type_name of `T` being captured
+    #[inline]
+    pub fn truncated<T: ?Sized>(records: usize, remaining: usize) -> Self {
+        Self::Truncated {
+            ty: type_name::<T>(),
+            records,
+            remaining,
+        }
+    }
+
+    /// convenient function to create an [`Error::NotSorted`]
+    /// with the type_name of `T` being captured
+    #[inline]
+    pub fn not_sorted<T: ?Sized>(index: usize) -> Self {
+        Self::NotSorted {
+            ty: type_name::<T>(),
+            index,
+        }
+    }
+
+    /// convenient function to create an [`Error::InvalidString`]
+    /// with the type_name of `T` being captured
+    #[inline]
+    pub fn invalid_string<T: ?Sized>(reason: impl Into<Cow<'static, str>>) -> Self {
+        Self::InvalidString {
+            ty: type_name::<T>(),
+            reason: reason.into(),
+        }
+    }
+
+    /// convenient function to create an [`Error::StringTooLong`]
+    /// with the type_name of `T` being captured
+    #[inline]
+    pub fn string_too_long<T: ?Sized>(length: usize, capacity: usize) -> Self {
+        Self::StringTooLong {
+            ty: type_name::<T>(),
+            length,
+            capacity,
+        }
+    }
+
+    /// convenient function to create an [`Error::InvalidNumber`]
+    /// with the type_name of `T` being captured
+    #[inline]
+    pub fn invalid_number<T: ?Sized>(reason: impl Into<Cow<'static, str>>) -> Self {
+        Self::InvalidNumber {
+            ty: type_name::<T>(),
+            reason: reason.into(),
+        }
+    }
+
+    /// convenient function to create an [`Error::NumberTooLarge`]
+    /// with the type_name of `T` being captured
+    #[inline]
+    pub fn number_too_large<T: ?Sized>(value: u64, max: u64) -> Self {
+        Self::NumberTooLarge {
+            ty: type_name::<T>(),
+            value,
+            max,
+        }
+    }
+
+    /// convenient function to create an [`Error::OutOfBounds`]
+    /// with the type_name of `T` being captured
+    #[inline]
+    pub fn out_of_bounds<T: ?Sized>(position: i128, size: usize, length: usize) -> Self {
+        Self::OutOfBounds {
+            ty: type_name::<T>(),
+            position,
+            size,
+            length,
+        }
+    }
+
     #[inline]
     pub fn invalid_field<T>(field: &'static str) -> Self
     where
@@ -0,0 +1,9 @@
+/*!
+readers and writers of some well known formats, built with the packed
+types of this crate. Each format is behind its own feature:
+
+* `tar`: [`tar`] archives (ustar, GNU and PAX)
+*/
+
+#[cfg(feature = "tar")]
+pub mod tar;
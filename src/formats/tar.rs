@@ -0,0 +1,755 @@
+/*!
+tar archives
+
+[`Archive::entries`] iterates over the entries of an archive held in
+memory, every [`Header`] is checked (octal fields and checksum) and the
+data of the entries are borrowed from the archive. The GNU long names
+(`L` and `K` entries) and the PAX extended headers (`x` and `g`) are
+applied to the entry that follows them.
+
+the names are read as raw bytes (GNU tar writes them in the encoding
+of the locale): [`Entry::path_bytes`] returns them as they are and
+[`Entry::path`] replaces the invalid UTF-8 sequences.
+
+[`Writer`] writes POSIX ustar archives. The paths that do not fit in
+the header and the values too large for their octal field are written
+in a PAX extended header.
+
+```
+use packtool::formats::tar::{Archive, Metadata, Writer};
+
+let mut writer = Writer::new(Vec::new());
+writer.append(&Metadata::directory("docs"), &[]).unwrap();
+writer.append(&Metadata::file("docs/readme.txt"), b"hello").unwrap();
+let archive = writer.finish().unwrap();
+assert_eq!(archive.len() % 512, 0);
+
+let mut entries = Archive::new(&archive).entries();
+let directory = entries.next().unwrap().unwrap();
+assert_eq!(directory.path(), "docs/");
+
+let file = entries.next().unwrap().unwrap();
+assert_eq!(file.path(), "docs/readme.txt");
+assert_eq!(file.data(), b"hello");
+assert!(entries.next().is_none());
+```
+
+the base-256 encoding GNU uses for the numbers too large for the octal
+fields is not supported, nor are the sparse files. The typeflags other
+than the ones of [`EntryType`] (e.g. the GNU sparse files `S` or the
+vendor specific `A` to `Z`) fail the check of the [`Header`]: the
+iteration stops at these entries.
+*/
+
+use crate::{ensure, string::Utf8Lossy, CStr, Error, Octal, Packed, View};
+use std::{borrow::Cow, convert::TryFrom, io};
+
+/// the size of the blocks of an archive
+pub const BLOCK: usize = 512;
+
+const CHECKSUM: std::ops::Range<usize> = 148..156;
+
+/// the type of an entry, the `typeflag` of the [`Header`]
+#[derive(Packed, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[packed(crate = "crate")]
+#[repr(u8)]
+pub enum EntryType {
+    /// regular file of the pre-POSIX archives
+    OldRegular = 0,
+    Regular = b'0',
+    HardLink = b'1',
+    Symlink = b'2',
+    Char = b'3',
+    Block = b'4',
+    Directory = b'5',
+    Fifo = b'6',
+    Contiguous = b'7',
+    /// PAX extended header of the next entry
+    PaxExtended = b'x',
+    /// PAX extended header of all the following entries
+    PaxGlobal = b'g',
+    /// GNU long link name of the next entry
+    GnuLongLink = b'K',
+    /// GNU long name of the next entry
+    GnuLongName = b'L',
+    GnuDumpDir = b'D',
+    GnuMultiVolume = b'M',
+    GnuVolumeLabel = b'V',
+}
+
+/// the magic and the version of the [`Header`]
+#[derive(Packed, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[packed(crate = "crate")]
+#[repr(u64)]
+pub enum Magic {
+    /// `ustar\0` `00`
+    Posix = u64::from_le_bytes(*b"ustar\x0000"),
+    /// `ustar ` ` \0`
+    Gnu = u64::from_le_bytes(*b"ustar  \x00"),
+    /// no magic, the headers of the pre-POSIX (v7) archives
+    V7 = 0,
+}
+
+/// the header block of an entry
+///
+/// the GNU headers store other fields in place of the `prefix`, it is
+/// only used by the [`Magic::Posix`] headers.
+#[derive(Packed, Debug, Clone, PartialEq, Eq)]
+#[packed(crate = "crate", size = 512, padding)]
+pub struct Header {
+    pub name: CStr<100, Utf8Lossy>,
+    pub mode: Octal<8>,
+    pub uid: Octal<8>,
+    pub gid: Octal<8>,
+    pub size: Octal<12>,
+    pub mtime: Octal<12>,
+    #[packed(offset = 148)]
+    pub checksum: Octal<8>,
+    #[packed(offset = 156)]
+    pub entry_type: EntryType,
+    pub link_name: CStr<100, Utf8Lossy>,
+    #[packed(offset = 257)]
+    pub magic: Magic,
+    pub user_name: CStr<32, Utf8Lossy>,
+    pub group_name: CStr<32, Utf8Lossy>,
+    pub device_major: Octal<8>,
+    pub device_minor: Octal<8>,
+    #[packed(offset = 345)]
+    pub prefix: CStr<155, Utf8Lossy>,
+}
+
+impl Header {
+    /// the checksum of the header `block`: the sum of its bytes, the
+    /// checksum field counting as spaces
+    pub fn compute_checksum(block: &[u8]) -> u64 {
+        block
+            .iter()
+            .enumerate()
+            .map(|(index, byte)| {
+                if CHECKSUM.contains(&index) {
+                    u64::from(b' ')
+                } else {
+                    u64::from(*byte)
+                }
+            })
+            .sum()
+    }
+
+    /// some old archives sum the bytes as `i8`
+    fn compute_signed_checksum(block: &[u8]) -> u64 {
+        block
+            .iter()
+            .enumerate()
+            .map(|(index, byte)| {
+                if CHECKSUM.contains(&index) {
+                    i64::from(b' ')
+                } else {
+                    i64::from(*byte as i8)
+                }
+            })
+            .sum::<i64>() as u64
+    }
+
+    /// check the header `block`, including its checksum
+    pub fn verify(block: &[u8]) -> Result<View<'_, Self>, Error> {
+        let header = View::<Self>::try_from_slice(block)?;
+        let checksum = Self::checksum(header).unpack().value();
+        let computed = Self::compute_checksum(block);
+        ensure!(
+            Self,
+            checksum == computed || checksum == Self::compute_signed_checksum(block),
+            "the checksum is {:o} but the header sums to {:o}",
+            checksum,
+            computed
+        );
+        Ok(header)
+    }
+
+    /// the header block, with its checksum
+    pub fn to_block(&self) -> [u8; BLOCK] {
+        let mut block = self.to_bytes();
+        let checksum = Octal::<8>::new(Self::compute_checksum(&block))
+            .expect("the checksum of a block always fits in its field");
+        checksum.unchecked_write_to_slice(&mut block[CHECKSUM]);
+        block
+    }
+}
+
+/// the bytes of a tar archive
+#[derive(Debug, Clone, Copy)]
+pub struct Archive<'a> {
+    bytes: &'a [u8],
+}
+
+/// the iterator over the [`Entry`]s of an [`Archive`], it stops at the
+/// first error
+#[derive(Debug, Clone)]
+pub struct Entries<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    globals: Extensions<'a>,
+    done: bool,
+}
+
+/// an entry of an [`Archive`]: its header, with the GNU long names and
+/// the PAX extended headers applied, and its data
+#[derive(Debug, Clone)]
+pub struct Entry<'a> {
+    header: View<'a, Header>,
+    position: usize,
+    path: Cow<'a, [u8]>,
+    link_name: Cow<'a, [u8]>,
+    // the names with the invalid UTF-8 sequences replaced
+    lossy_path: Cow<'a, str>,
+    lossy_link_name: Cow<'a, str>,
+    size: u64,
+    uid: u64,
+    gid: u64,
+    mtime: u64,
+    data: &'a [u8],
+}
+
+/// the fields of the header overridden by the GNU long names or the
+/// PAX records
+#[derive(Debug, Clone, Default)]
+struct Extensions<'a> {
+    path: Option<Cow<'a, [u8]>>,
+    link_name: Option<Cow<'a, [u8]>>,
+    size: Option<u64>,
+    uid: Option<u64>,
+    gid: Option<u64>,
+    mtime: Option<u64>,
+}
+
+impl<'a> Archive<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn entries(&self) -> Entries<'a> {
+        Entries {
+            bytes: self.bytes,
+            position: 0,
+            globals: Extensions::default(),
+            done: false,
+        }
+    }
+}
+
+impl<'a> Entries<'a> {
+    fn next_entry(&mut self) -> Result<Option<Entry<'a>>, Error> {
+        let mut locals = Extensions::default();
+
+        loop {
+            let position = self.position;
+            let block = match self.bytes.get(position..position + BLOCK) {
+                Some(block) => block,
+                None => {
+                    ensure!(
+                        Header,
+                        position == self.bytes.len(),
+                        "the archive is truncated: {} trailing byte(s) at {}",
+                        self.bytes.len() - position,
+                        position
+                    );
+                    return Ok(None);
+                }
+            };
+            // the archive ends with blocks of zeros
+            if block.iter().all(|byte| *byte == 0) {
+                return Ok(None);
+            }
+
+            let header = Header::verify(block)?;
+            let entry_type = Header::entry_type(header).unpack();
+            let size = match entry_type {
+                EntryType::GnuLongName
+                | EntryType::GnuLongLink
+                | EntryType::PaxExtended
+                | EntryType::PaxGlobal => None,
+                _ => locals.size.or(self.globals.size),
+            }
+            .unwrap_or_else(|| Header::size(header).unpack().value());
+
+            let start = position + BLOCK;
+            let data = usize::try_from(size)
+                .ok()
+                .and_then(|size| self.bytes.get(start..start.checked_add(size)?))
+                .ok_or_else(|| {
+                    Error::out_of_bounds::<Entry>(start as i128, size as usize, self.bytes.len())
+                })?;
+            self.position = start + padded(data.len());
+
+            match entry_type {
+                EntryType::GnuLongName => locals.path = Some(Cow::Borrowed(c_string(data))),
+                EntryType::GnuLongLink => locals.link_name = Some(Cow::Borrowed(c_string(data))),
+                EntryType::PaxExtended => locals.parse(data)?,
+                EntryType::PaxGlobal => self.globals.parse(data)?,
+                _ => {
+                    return Ok(Some(Entry::new(
+                        header,
+                        position,
+                        &self.globals,
+                        locals,
+                        data,
+                    )))
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = Result<Entry<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let entry = self.next_entry().transpose();
+        if !matches!(entry, Some(Ok(_))) {
+            self.done = true;
+        }
+        entry
+    }
+}
+
+impl<'a> Entry<'a> {
+    fn new(
+        header: View<'a, Header>,
+        position: usize,
+        globals: &Extensions<'a>,
+        locals: Extensions<'a>,
+        data: &'a [u8],
+    ) -> Self {
+        let name = Header::name(header).content();
+        let prefix = Header::prefix(header).content();
+        let path = match Header::magic(header).unpack() {
+            Magic::Posix if !prefix.is_empty() => Cow::Owned([prefix, b"/", name].concat()),
+            Magic::Posix | Magic::Gnu | Magic::V7 => Cow::Borrowed(name),
+        };
+        let path = locals.path.or_else(|| globals.path.clone()).unwrap_or(path);
+        let link_name = locals
+            .link_name
+            .or_else(|| globals.link_name.clone())
+            .unwrap_or(Cow::Borrowed(Header::link_name(header).content()));
+
+        Self {
+            header,
+            position,
+            lossy_path: lossy(&path),
+            lossy_link_name: lossy(&link_name),
+            path,
+            link_name,
+            size: data.len() as u64,
+            uid: locals
+                .uid
+                .or(globals.uid)
+                .unwrap_or_else(|| Header::uid(header).unpack().value()),
+            gid: locals
+                .gid
+                .or(globals.gid)
+                .unwrap_or_else(|| Header::gid(header).unpack().value()),
+            mtime: locals
+                .mtime
+                .or(globals.mtime)
+                .unwrap_or_else(|| Header::mtime(header).unpack().value()),
+            data,
+        }
+    }
+
+    /// the header block of the entry
+    pub fn header(&self) -> View<'a, Header> {
+        self.header
+    }
+
+    /// the position of the header block in the archive
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// the path, the invalid UTF-8 sequences are replaced by `U+FFFD`
+    pub fn path(&self) -> &str {
+        &self.lossy_path
+    }
+
+    /// the path as stored in the archive
+    pub fn path_bytes(&self) -> &[u8] {
+        &self.path
+    }
+
+    /// the target of the links, empty for the other entries
+    pub fn link_name(&self) -> &str {
+        &self.lossy_link_name
+    }
+
+    /// the target of the links as stored in the archive
+    pub fn link_name_bytes(&self) -> &[u8] {
+        &self.link_name
+    }
+
+    pub fn entry_type(&self) -> EntryType {
+        Header::entry_type(self.header).unpack()
+    }
+
+    pub fn mode(&self) -> u64 {
+        Header::mode(self.header).unpack().value()
+    }
+
+    pub fn uid(&self) -> u64 {
+        self.uid
+    }
+
+    pub fn gid(&self) -> u64 {
+        self.gid
+    }
+
+    /// the modification time, in seconds since the epoch
+    pub fn mtime(&self) -> u64 {
+        self.mtime
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// the data of the entry, borrowed from the archive
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+impl<'a> Extensions<'a> {
+    /// apply the PAX records: `"<length> <key>=<value>\n"`
+    fn parse(&mut self, mut records: &'a [u8]) -> Result<(), Error> {
+        while !records.is_empty() {
+            let (length, record) = match records.iter().position(|byte| *byte == b' ') {
+                Some(space) => (&records[..space], &records[space + 1..]),
+                None => return Err(invalid_record(records)),
+            };
+            // the length counts the digits and the space too
+            let prefix = records.len() - record.len();
+            let length = std::str::from_utf8(length)
+                .ok()
+                .and_then(|length| length.parse::<usize>().ok())
+                .filter(|length| *length > prefix && *length <= records.len())
+                .ok_or_else(|| invalid_record(records))?;
+            let record = &record[..length - prefix];
+            records = &records[length..];
+
+            let record = record
+                .strip_suffix(b"\n")
+                .and_then(|record| std::str::from_utf8(record).ok())
+                .ok_or_else(|| invalid_record(record))?;
+            let (key, value) = record
+                .split_once('=')
+                .ok_or_else(|| invalid_record(record.as_bytes()))?;
+
+            match key {
+                "path" => self.path = Some(Cow::Borrowed(value.as_bytes())),
+                "linkpath" => self.link_name = Some(Cow::Borrowed(value.as_bytes())),
+                "size" => self.size = Some(pax_number(key, value)?),
+                "uid" => self.uid = Some(pax_number(key, value)?),
+                "gid" => self.gid = Some(pax_number(key, value)?),
+                // the sub-second part is dropped
+                "mtime" => {
+                    let seconds = value.split('.').next().unwrap_or(value);
+                    self.mtime = Some(pax_number(key, seconds)?)
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid_record(record: &[u8]) -> Error {
+    Error::Message(Cow::Owned(format!(
+        "invalid PAX record: {:?}",
+        String::from_utf8_lossy(record)
+    )))
+}
+
+fn pax_number(key: &str, value: &str) -> Result<u64, Error> {
+    value.parse().map_err(|_| {
+        Error::Message(Cow::Owned(format!(
+            "invalid PAX record: {} is not a number ({:?})",
+            key, value
+        )))
+    })
+}
+
+/// the name of the GNU long name entries, NUL terminated
+fn c_string(data: &[u8]) -> &[u8] {
+    let end = data
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(data.len());
+    &data[..end]
+}
+
+/// the `name`, with the invalid UTF-8 sequences replaced
+fn lossy<'a>(name: &Cow<'a, [u8]>) -> Cow<'a, str> {
+    match name {
+        Cow::Borrowed(name) => String::from_utf8_lossy(name),
+        Cow::Owned(name) => Cow::Owned(String::from_utf8_lossy(name).into_owned()),
+    }
+}
+
+/// the size rounded up to the next block
+fn padded(size: usize) -> usize {
+    size.div_ceil(BLOCK) * BLOCK
+}
+
+/// the metadata of an entry to write with [`Writer::append`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    pub path: String,
+    pub entry_type: EntryType,
+    pub link_name: String,
+    pub mode: u64,
+    pub uid: u64,
+    pub gid: u64,
+    pub mtime: u64,
+    pub user_name: String,
+    pub group_name: String,
+}
+
+impl Metadata {
+    fn new(path: impl Into<String>, entry_type: EntryType, mode: u64) -> Self {
+        Self {
+            path: path.into(),
+            entry_type,
+            link_name: String::new(),
+            mode,
+            uid: 0,
+            gid: 0,
+            mtime: 0,
+            user_name: String::new(),
+            group_name: String::new(),
+        }
+    }
+
+    /// a regular file, `rw-r--r--`
+    pub fn file(path: impl Into<String>) -> Self {
+        Self::new(path, EntryType::Regular, 0o644)
+    }
+
+    /// a directory, `rwxr-xr-x`. The path ends with a `/`
+    pub fn directory(path: impl Into<String>) -> Self {
+        let mut path = path.into();
+        if !path.ends_with('/') {
+            path.push('/');
+        }
+        Self::new(path, EntryType::Directory, 0o755)
+    }
+
+    /// a symbolic link to `target`
+    pub fn symlink(path: impl Into<String>, target: impl Into<String>) -> Self {
+        let mut metadata = Self::new(path, EntryType::Symlink, 0o777);
+        metadata.link_name = target.into();
+        metadata
+    }
+}
+
+/// write a POSIX ustar archive
+#[derive(Debug)]
+pub struct Writer<W> {
+    writer: W,
+}
+
+impl<W: io::Write> Writer<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// write an entry and its `data`, preceded by a PAX extended
+    /// header if some of the metadata do not fit in the [`Header`]
+    pub fn append(&mut self, metadata: &Metadata, data: &[u8]) -> Result<(), Error> {
+        ensure!(
+            Metadata,
+            !metadata.path.is_empty()
+                && ![
+                    &metadata.path,
+                    &metadata.link_name,
+                    &metadata.user_name,
+                    &metadata.group_name
+                ]
+                .iter()
+                .any(|string| string.contains('\0')),
+            "the path is empty or a string contains a NUL: {:?}",
+            metadata.path
+        );
+
+        let mut records = Vec::new();
+        let header = ustar_header(metadata, data.len() as u64, &mut records);
+
+        if !records.is_empty() {
+            let mut extended = String::new();
+            for (key, value) in records {
+                extended.push_str(&pax_record(key, &value));
+            }
+            let name = format!(
+                "PaxHeaders/{}",
+                metadata
+                    .path
+                    .trim_end_matches('/')
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or_default()
+            );
+            let mut pax = Metadata::new(truncate(&name, 100), EntryType::PaxExtended, 0o644);
+            pax.mtime = metadata.mtime;
+            // the extended header has no extended header itself
+            let pax = ustar_header(&pax, extended.len() as u64, &mut Vec::new());
+            self.write_entry(&pax, extended.as_bytes())?;
+        }
+
+        self.write_entry(&header, data)
+    }
+
+    fn write_entry(&mut self, header: &Header, data: &[u8]) -> Result<(), Error> {
+        self.writer.write_all(&header.to_block())?;
+        self.writer.write_all(data)?;
+        self.writer
+            .write_all(&[0; BLOCK][..padded(data.len()) - data.len()])?;
+        Ok(())
+    }
+
+    /// write the end of the archive, two blocks of zeros
+    pub fn finish(mut self) -> Result<W, Error> {
+        self.writer.write_all(&[0; 2 * BLOCK])?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// the header of the entry, the fields that do not fit are pushed to
+/// the PAX `records`
+fn ustar_header(
+    metadata: &Metadata,
+    size: u64,
+    records: &mut Vec<(&'static str, String)>,
+) -> Header {
+    fn octal<const N: usize>(
+        key: &'static str,
+        value: u64,
+        records: &mut Vec<(&'static str, String)>,
+    ) -> Octal<N> {
+        Octal::new(value).unwrap_or_else(|_| {
+            records.push((key, value.to_string()));
+            Octal::default()
+        })
+    }
+    fn string<const N: usize>(
+        key: &'static str,
+        value: &str,
+        records: &mut Vec<(&'static str, String)>,
+    ) -> CStr<N, Utf8Lossy> {
+        CStr::new(value).unwrap_or_else(|_| {
+            records.push((key, value.to_owned()));
+            CStr::new(truncate(value, N)).unwrap_or_default()
+        })
+    }
+
+    let (prefix, name) = match split_path(&metadata.path) {
+        Some((prefix, name)) => (
+            CStr::new(prefix).unwrap_or_default(),
+            CStr::new(name).unwrap_or_default(),
+        ),
+        None => (CStr::default(), string("path", &metadata.path, records)),
+    };
+
+    Header {
+        name,
+        // there is no PAX record for the mode, only the permissions and
+        // the set-id bits are kept
+        mode: Octal::new(metadata.mode & 0o7777).unwrap_or_default(),
+        uid: octal("uid", metadata.uid, records),
+        gid: octal("gid", metadata.gid, records),
+        size: octal("size", size, records),
+        mtime: octal("mtime", metadata.mtime, records),
+        checksum: Octal::default(),
+        entry_type: metadata.entry_type,
+        link_name: string("linkpath", &metadata.link_name, records),
+        magic: Magic::Posix,
+        user_name: string("uname", &metadata.user_name, records),
+        group_name: string("gname", &metadata.group_name, records),
+        device_major: Octal::default(),
+        device_minor: Octal::default(),
+        prefix,
+    }
+}
+
+/// split the `path` in the `prefix` and the `name` of the ustar header
+fn split_path(path: &str) -> Option<(&str, &str)> {
+    if path.len() <= 100 {
+        return Some(("", path));
+    }
+
+    path.char_indices()
+        .filter(|(_, c)| *c == '/')
+        .map(|(index, _)| (&path[..index], &path[index + 1..]))
+        .find(|(prefix, name)| prefix.len() <= 155 && !name.is_empty() && name.len() <= 100)
+}
+
+/// the longest start of `string` that fits in `len` bytes
+fn truncate(string: &str, len: usize) -> &str {
+    let mut end = len.min(string.len());
+    while !string.is_char_boundary(end) {
+        end -= 1;
+    }
+    &string[..end]
+}
+
+/// the PAX record of `key`, its length includes the length itself
+fn pax_record(key: &str, value: &str) -> String {
+    let base = key.len() + value.len() + 3;
+    let mut length = base;
+    loop {
+        let total = base + length.to_string().len();
+        if total == length {
+            break;
+        }
+        length = total;
+    }
+    format!("{} {}={}\n", length, key, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pax_record_length() {
+        assert_eq!(pax_record("path", "a"), "9 path=a\n");
+        assert_eq!(pax_record("path", "abcdefg"), "16 path=abcdefg\n");
+        // 10 bytes with a 1 digit length, 11 with 2 digits
+        assert_eq!(pax_record("path", "ab"), "11 path=ab\n");
+
+        let mut extensions = Extensions::default();
+        let records = pax_record("path", "dir/file") + &pax_record("size", "42");
+        extensions.parse(records.as_bytes()).unwrap();
+        assert_eq!(extensions.path.as_deref(), Some(&b"dir/file"[..]));
+        assert_eq!(extensions.size, Some(42));
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(split_path("a/b"), Some(("", "a/b")));
+
+        let long = format!("{}/{}", "d".repeat(120), "f".repeat(80));
+        assert_eq!(split_path(&long), Some((&long[..120], &long[121..])));
+
+        assert_eq!(split_path(&"f".repeat(101)), None);
+        assert_eq!(split_path(&format!("{}/f", "d".repeat(156))), None);
+    }
+
+    #[test]
+    fn truncated() {
+        assert_eq!(truncate("été", 4), "ét");
+        assert_eq!(truncate("abc", 4), "abc");
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{Error, Packed, View};
+use std::{any, borrow::Borrow, fmt, hash};
+
+/// a owned [`Packed`] object stored inline
+///
+/// Unlike the [`Packet`], the bytes are kept in the [`Packed::Bytes`]
+/// of `T` and no heap allocation is performed (for the types using
+/// an array as storage).
+///
+/// ```
+/// use packtool::{InlinePacket, Packed};
+///
+/// #[derive(Packed, Debug, PartialEq, Eq)]
+/// pub struct Point {
+///     x: u16,
+///     y: u16,
+/// }
+///
+/// let point = InlinePacket::pack(&Point { x: 1, y: 2 });
+///
+/// assert_eq!(point.as_ref(), &[1, 0, 2, 0]);
+/// assert_eq!(Point::x(point.view()).unpack(), 1);
+/// assert_eq!(point.unpack(), Point { x: 1, y: 2 });
+/// ```
+///
+/// [`Packet`]: crate::Packet
+pub struct InlinePacket<T: Packed> {
+    bytes: T::Bytes,
+}
+
+impl<T> InlinePacket<T>
+where
+    T: Packed,
+{
+    /// pack the object into its [`Packed::Bytes`]
+    #[inline]
+    pub fn pack(packed: &T) -> Self {
+        Self {
+            bytes: packed.to_bytes(),
+        }
+    }
+
+    /// take ownership of the given bytes after checking they hold a
+    /// valid `T`.
+    pub fn try_from_bytes(bytes: T::Bytes) -> Result<Self, Error> {
+        View::<T>::try_from_slice(bytes.as_ref())?;
+        Ok(Self { bytes })
+    }
+
+    /// get a [`View`] of the [`InlinePacket`].
+    #[inline]
+    pub fn view(&self) -> View<'_, T> {
+        View::new(self.bytes.as_ref())
+    }
+
+    /// reconstruct the object `T` from the given [`InlinePacket`]
+    #[inline]
+    pub fn unpack(&self) -> T {
+        self.view().unpack()
+    }
+
+    #[inline]
+    pub fn into_bytes(self) -> T::Bytes {
+        self.bytes
+    }
+}
+
+impl<T: Packed> Borrow<[u8]> for InlinePacket<T> {
+    fn borrow(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+}
+
+impl<T: Packed> AsRef<[u8]> for InlinePacket<T> {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+}
+
+impl<T: Packed> Clone for InlinePacket<T> {
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+        }
+    }
+}
+
+impl<T: Packed> Copy for InlinePacket<T> where T::Bytes: Copy {}
+
+impl<T: Packed, U: Packed> PartialEq<InlinePacket<U>> for InlinePacket<T> {
+    fn eq(&self, other: &InlinePacket<U>) -> bool {
+        self.as_ref().eq(other.as_ref())
+    }
+}
+
+impl<T: Packed> Eq for InlinePacket<T> {}
+
+impl<T: Packed, U: Packed> PartialOrd<InlinePacket<U>> for InlinePacket<T> {
+    fn partial_cmp(&self, other: &InlinePacket<U>) -> Option<std::cmp::Ordering> {
+        self.as_ref().partial_cmp(other.as_ref())
+    }
+}
+
+impl<T: Packed> Ord for InlinePacket<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_ref().cmp(other.as_ref())
+    }
+}
+
+impl<T: Packed> hash::Hash for InlinePacket<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state);
+    }
+}
+
+impl<T: Packed> fmt::Debug for InlinePacket<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ty = any::type_name::<T>();
+        f.debug_struct(&format!("InlinePacket<{}>", ty))
+            .field("bytes", &self.as_ref())
+            .finish()
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{Error, Segment};
+use std::fmt;
+
+/// a field of a packed layout, as decoded by [`View::inspect`]
+///
+/// the node knows its position and size in the inspected slice, the
+/// decoded value of the leaves (the integers, the strings, the variant
+/// of the enumerations...) and the error of the invalid ones.
+///
+/// [`View::inspect`]: crate::View::inspect
+#[derive(Debug)]
+pub struct Node {
+    segment: Option<Segment>,
+    offset: usize,
+    size: usize,
+    value: Option<String>,
+    error: Option<Error>,
+    children: Vec<Node>,
+}
+
+/// build the tree of [`Node`] of a packed layout
+///
+/// see [`Packed::inspect`], it works as the [`Report`] of
+/// [`Packed::check_all`] but records every field.
+///
+/// [`Packed::inspect`]: crate::Packed::inspect
+/// [`Packed::check_all`]: crate::Packed::check_all
+/// [`Report`]: crate::Report
+#[derive(Debug)]
+pub struct Inspector {
+    // the root is the first node, the last one is the node being
+    // inspected
+    stack: Vec<Node>,
+}
+
+impl Node {
+    fn new(segment: Option<Segment>, offset: usize, size: usize) -> Self {
+        Self {
+            segment,
+            offset,
+            size,
+            value: None,
+            error: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// the name of the field, `None` for the root of the layout
+    #[inline]
+    pub fn segment(&self) -> Option<Segment> {
+        self.segment
+    }
+
+    /// offset, in bytes, of the field from the start of the inspected
+    /// slice
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// the bytes of the field in the inspected `slice`
+    pub fn bytes<'a>(&self, slice: &'a [u8]) -> &'a [u8] {
+        let end = (self.offset + self.size).min(slice.len());
+        &slice[self.offset.min(end)..end]
+    }
+
+    /// the decoded value, if any
+    #[inline]
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// the error of the field, if it is not valid
+    #[inline]
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+
+    #[inline]
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    /// `true` if neither the node nor any of its children has an error
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none() && self.children.iter().all(Node::is_valid)
+    }
+}
+
+impl Inspector {
+    /// start the inspection of a layout of `size` bytes
+    pub fn new(size: usize) -> Self {
+        Self {
+            stack: vec![Node::new(None, 0, size)],
+        }
+    }
+
+    fn current(&mut self) -> &mut Node {
+        self.stack
+            .last_mut()
+            .expect("the root of the inspection is never popped")
+    }
+
+    /// set the decoded value of the current field
+    pub fn value(&mut self, value: impl fmt::Display) {
+        self.current().value = Some(value.to_string());
+    }
+
+    /// record the error of the current field
+    pub fn error(&mut self, error: Error) {
+        self.current().error = Some(error);
+    }
+
+    /// walk into the field `segment` of `size` bytes of the current
+    /// layout. `start` is the offset of the field relative to the
+    /// current field.
+    pub fn nested<F>(&mut self, segment: Segment, start: usize, size: usize, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let offset = self.current().offset + start;
+        self.stack.push(Node::new(Some(segment), offset, size));
+
+        f(self);
+
+        let node = self.stack.pop().expect("the node was just pushed");
+        self.current().children.push(node);
+    }
+
+    /// the root of the inspected layout
+    pub fn finish(mut self) -> Node {
+        self.stack.truncate(1);
+        self.stack
+            .pop()
+            .expect("the root of the inspection is never popped")
+    }
+}
@@ -90,6 +90,69 @@ pub enum Color {
 }
 ```
 
+The discriminants can be implicit, like in Rust they follow the
+previous variant (or start at `0`). The packed discriminant may use
+a different type than the `repr(...)` with `#[packed(tag = ...)]`:
+the size of the enum is then the size of the tag.
+
+```
+use packtool::Packed;
+
+#[derive(Packed)]
+#[repr(u8)]
+#[packed(tag = u16)]
+pub enum Kind {
+    Block = 1,
+    Transaction,
+    Deleted = 0xFF,
+}
+
+# assert_eq!(Kind::SIZE, 2);
+let mut bytes = [0; 2];
+Kind::Transaction.unchecked_write_to_slice(&mut bytes);
+assert_eq!(bytes, [2, 0]);
+```
+
+A discriminant that does not fit in the tag is a compile time error.
+
+```compile_fail
+use packtool::Packed;
+
+#[derive(Packed)]
+#[repr(u16)]
+#[packed(tag = u8)]
+pub enum Kind {
+    Block = 1,
+    Deleted = 0x100,
+}
+```
+
+The enumerations also get the conversions from and to the type of
+the tag (`TryFrom` and `From`), the list of their `VARIANTS`, the
+`name()` of the variants (and `from_name()`) and a `Display` that
+writes the name. Skip the ones that are already implemented with
+`#[packed(skip(try_from, into, variants, name, display))]`.
+
+```
+use packtool::Packed;
+use std::convert::TryFrom;
+
+#[derive(Packed, Debug, PartialEq)]
+#[repr(u8)]
+#[packed(skip(display))]
+pub enum Version {
+    V1 = 1,
+    V2,
+}
+
+assert_eq!(Version::try_from(2).unwrap(), Version::V2);
+assert!(Version::try_from(3).is_err());
+assert_eq!(u8::from(Version::V1), 1);
+assert_eq!(Version::VARIANTS, &[Version::V1, Version::V2]);
+assert_eq!(Version::V2.name(), "V2");
+assert_eq!(Version::from_name("V1"), Some(Version::V1));
+```
+
 ## combining packed objects
 
 It is possible to compose packed objects in named or tuple structures.
@@ -228,27 +291,332 @@ pub struct Hash(
 let bytes = Hash::_0(hash);
 ```
 
+## Layout
+
+Specifications often give the exact size of a record and the offset of
+some of its fields. `#[packed(size = ...)]` on the type and
+`#[packed(offset = ...)]` on the fields are checked at compile time
+against the computed layout:
+
+```
+use packtool::Packed;
+
+#[derive(Packed)]
+#[packed(size = 12)]
+pub struct Entry {
+    kind: u32,
+    #[packed(offset = 4)]
+    length: u32,
+    checksum: u32,
+}
+# assert_eq!(Entry::SIZE, 12);
+```
+
+```compile_fail
+use packtool::Packed;
+
+#[derive(Packed)]
+pub struct Entry {
+    kind: u32,
+    #[packed(offset = 8)]
+    length: u32,
+}
+```
+
+With `#[packed(padding)]` the gaps between the fields (and up to the
+declared `size`) are filled with padding bytes instead. The padding
+bytes are not checked when reading and are not written by
+[`Packed::unchecked_write_to_slice`]:
+
+```
+use packtool::Packed;
+
+#[derive(Packed)]
+#[packed(size = 16, padding)]
+pub struct Entry {
+    kind: u32,
+    #[packed(offset = 8)]
+    length: u32,
+}
+# assert_eq!(Entry::SIZE, 16);
+```
+
+## Overlays
+
+With `#[packed(overlay)]` every field of a structure is a different
+interpretation of the same bytes: all the fields start at byte 0 and
+the size is the size of the largest field. Only the first field is
+written when packing, it has to be the largest one. By default every
+interpretation must be valid.
+
+`#[packed(check = "any")]` only requires one of them to be valid: every
+field is then an `Option`, `None` when the interpretation is not valid.
+The accessors check their member and return a `Result`, and the first
+field set is the one written when packing.
+
+```
+use packtool::{Packed, Packet};
+
+#[derive(Packed)]
+#[packed(overlay)]
+pub struct Register {
+    raw: u16,
+    bytes: [u8; 2],
+}
+
+let register = Packet::pack(&Register { raw: 0x0102, bytes: [0; 2] });
+assert_eq!(Register::bytes(register.view()).unpack(), [2, 1]);
+
+#[derive(Packed, Debug, PartialEq)]
+#[repr(u8)]
+pub enum Mode {
+    Off = 0,
+    On = 1,
+}
+
+#[derive(Packed)]
+#[packed(overlay, check = "any")]
+pub struct Control {
+    mode: Option<Mode>,
+    raw: Option<u8>,
+}
+
+let control = Packet::pack(&Control { mode: None, raw: Some(5) });
+assert!(Control::mode(control.view()).is_err());
+assert_eq!(control.unpack().raw, Some(5));
+```
+
+Rust `union`s cannot be packed, use an overlay instead:
+
+```compile_fail
+use packtool::Packed;
+
+#[derive(Packed)]
+pub union Register {
+    raw: u16,
+    bytes: [u8; 2],
+}
+```
+
+The first field of an overlay checking all its members has to be the
+largest one:
+
+```compile_fail
+use packtool::Packed;
+
+#[derive(Packed)]
+#[packed(overlay)]
+pub struct Register {
+    low: u8,
+    raw: u16,
+}
+```
+
+## Strings
+
+Fixed size text fields are packed with [`FixedStr`]: the encoding
+(ASCII, UTF-8 or UTF-16) and the padding (NUL padded, space padded or
+NUL terminated) are part of the type and checked when reading. See the
+[`string`] module.
+
+```
+use packtool::{CStr, Packed, View};
+
+#[derive(Packed)]
+pub struct Header {
+    name: CStr<8>,
+    length: u32,
+}
+
+let header = View::<Header>::try_from_slice(b"readme\0\0\x2a\0\0\0").unwrap();
+assert_eq!(Header::name(header).as_str(), "readme");
+```
+
+## ASCII numbers
+
+Numbers written with ASCII digits, as in tar or cpio, are packed with
+[`Octal`], [`Decimal`] and [`HexAscii`]. The digits and the padding are
+checked when reading, see the [`number`] module.
+
+```
+use packtool::{Octal, Packed, View};
+
+let size = View::<Octal<12>>::try_from_slice(b"00000001750\0").unwrap();
+assert_eq!(size.unpack().value(), 1000);
+```
+
+## Offsets
+
+An [`Offset<I, T>`](Offset) field is an integer pointing to a `T`
+elsewhere in the same [`Buffer`]. The [`Rooted`] views know their
+position in the buffer and resolve the offsets with bounds checking,
+see the [`offset`] module.
+
+## Formats
+
+Some well known formats are implemented with the packed types, each
+behind its own feature: `formats::tar` (feature `tar`) reads and writes
+tar archives.
+
+## Inspecting
+
+[`View::inspect`] decodes a slice field by field, even the invalid ones,
+into a tree of [`Node`] with the offset, the bytes, the decoded value
+and the error of every field. The `packtool` command line of the
+`packtool-cli` crate prints this tree for the records of a binary file.
+
+## Re-exporting packtool
+
+The generated code only uses fully qualified paths to `::packtool`. When
+`packtool` is re-exported by another crate, set the path to use with
+`#[packed(crate = "...")]`:
+
+```ignore
+use my_facade::packtool::Packed;
+
+#[derive(Packed)]
+#[packed(crate = "my_facade::packtool")]
+pub struct Length(u32);
+```
+
+## Versioned records
+
+A record often evolves while keeping a version tag at the same offset.
+`#[derive(PackedVersions)]` ties the versions of a record together: it
+reads the tag at `tag_offset` and returns the `View` of the matching
+version. `#[packed(upgrade(Previous))]` generates the `From<Previous>`
+conversion: the field marked with `#[packed(version(...))]` is set to
+the version tag of the structure, the fields with the same name are
+converted with `Into` and the new fields take their `#[packed(default)]`
+(or `default = "expr"`) value.
+
+```
+use packtool::{Packed, PackedVersions, View};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Version {
+    V1 = 1,
+    V2 = 2,
+}
+
+#[derive(Packed)]
+pub struct HeaderV1 {
+    version: Version,
+    length: u16,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(upgrade(HeaderV1))]
+pub struct HeaderV2 {
+    #[packed(version(Version::V2))]
+    version: Version,
+    length: u32,
+    #[packed(default = "0xff")]
+    flags: u8,
+}
+
+#[derive(PackedVersions)]
+#[packed(tag(Version), upgrade)]
+pub enum AnyHeader<'a> {
+    #[packed(version(Version::V1))]
+    V1(View<'a, HeaderV1>),
+    #[packed(version(Version::V2))]
+    V2(View<'a, HeaderV2>),
+}
+
+let header = AnyHeader::try_from_slice(&[1, 0x2a, 0x00]).unwrap();
+assert_eq!(header.tag(), Version::V1);
+assert_eq!(
+    header.upgrade(),
+    HeaderV2 { version: Version::V2, length: 0x2a, flags: 0xff },
+);
+```
+
 */
 
-#[cfg(test)]
-extern crate quickcheck;
 #[cfg(test)]
 #[macro_use(quickcheck)]
 extern crate quickcheck_macros;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_packed;
 mod array;
+pub mod builder;
+mod byte_array;
+#[cfg(feature = "tokio")]
+pub mod codec;
+mod compatible;
 mod error;
+#[cfg(feature = "tar")]
+pub mod formats;
+mod inline_packet;
+mod inspect;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod number;
+pub mod offset;
+pub mod ordered;
 mod packet;
+mod packet_vec;
 mod primitives;
+mod report;
+pub mod sequence;
+mod shared;
+mod sorted_table;
+pub mod string;
+#[cfg(any(feature = "quickcheck", feature = "proptest"))]
+pub mod testing;
 mod tuple;
 mod view;
 
 pub use self::{
+    byte_array::ByteArray,
+    compatible::Compatible,
     error::{Context, Error},
+    inline_packet::InlinePacket,
+    inspect::{Inspector, Node},
+    number::{Decimal, HexAscii, Octal},
+    offset::{Buffer, Offset, Rooted},
+    ordered::{Ordered, OrderedPacked},
     packet::Packet,
+    packet_vec::PacketVec,
+    report::{Issue, Report, Segment},
+    sequence::{Records, Sequence},
+    shared::{Shared, Sliceable},
+    sorted_table::{SortedTable, SortedTableBuilder},
+    string::{CStr, FixedStr, Utf16Str},
     view::View,
 };
-pub use packtool_macro::Packed;
+pub use packtool_macro::{Packed, PackedVersions};
+
+#[cfg(feature = "arbitrary")]
+pub use self::arbitrary_packed::ArbitraryPacked;
+
+/// re-export of the [`arbitrary`](https://docs.rs/arbitrary) crate used by
+/// the `Arbitrary` implementations generated by `#[derive(Packed)]`
+/// with the `#[packed(arbitrary)]` attribute when the `arbitrary`
+/// feature is enabled.
+#[cfg(feature = "arbitrary")]
+pub use arbitrary;
+
+/// re-export of the [`bytes`](https://docs.rs/bytes) crate, `bytes::Bytes`
+/// can be used as the storage of a [`Packet`] and be projected with
+/// [`Packet::project`] when the `bytes` feature is enabled.
+#[cfg(feature = "bytes")]
+pub use bytes;
+
+/// re-export of the [`quickcheck`](https://docs.rs/quickcheck) crate used
+/// by the `Arbitrary` implementations generated by `#[derive(Packed)]`
+/// when the `quickcheck` feature is enabled.
+#[cfg(feature = "quickcheck")]
+pub use quickcheck;
+
+/// re-export of the [`proptest`](https://docs.rs/proptest) crate used
+/// by the strategies generated by `#[derive(Packed)]` when the
+/// `proptest` feature is enabled.
+#[cfg(feature = "proptest")]
+pub use proptest;
 
 /// trait to define how a fixed size Packed object is serialized
 /// into a byte slice representation.
@@ -261,6 +629,14 @@ pub trait Packed: Sized {
     /// but the size it takes to have this object on a slice of memory.
     const SIZE: usize;
 
+    /// the storage of the packed object: `[u8; Self::SIZE]`
+    ///
+    /// it allows encoding the object without heap allocation, see
+    /// [`Packed::to_bytes`] and [`InlinePacket`]. `#[derive(Packed)]`
+    /// always uses an array. The tuples use a `Box<[u8]>` instead as
+    /// their size cannot be used as an array length in generic code.
+    type Bytes: ByteArray;
+
     /// assuming the given slice if valid, perform a conversion
     /// from the slice to the object.
     fn unchecked_read_from_slice(slice: &[u8]) -> Self;
@@ -274,6 +650,33 @@ pub trait Packed: Sized {
     /// method so no need to do that again in here.
     fn check(slice: &[u8]) -> Result<(), Error>;
 
+    /// check the validity of the given slice, recording every
+    /// failure in the [`Report`] instead of stopping at the first one.
+    ///
+    /// the default implementation records the error returned by
+    /// [`Packed::check`], if any. Composite types walk into each of
+    /// their fields so all the issues are reported.
+    #[inline]
+    fn check_all(slice: &[u8], report: &mut Report) {
+        if let Err(error) = Self::check(slice) {
+            report.push(error);
+        }
+    }
+
+    /// decode the given slice field by field, recording the value and
+    /// the validity of each of them in the [`Inspector`].
+    ///
+    /// the default implementation records the error returned by
+    /// [`Packed::check`], if any. The leaves (integers, strings,
+    /// enumerations...) set their decoded value and the composite types
+    /// walk into each of their fields. See [`View::inspect`].
+    #[inline]
+    fn inspect(slice: &[u8], inspector: &mut Inspector) {
+        if let Err(error) = Self::check(slice) {
+            inspector.error(error);
+        }
+    }
+
     /// assuming the given slice if valid, perform a conversion
     /// from the slice to the object.
     ///
@@ -284,4 +687,18 @@ pub trait Packed: Sized {
     fn read(view: View<'_, Self>) -> Self {
         Self::unchecked_read_from_slice(view.as_ref())
     }
+
+    /// encode the object into its [`Packed::Bytes`]
+    #[inline]
+    fn to_bytes(&self) -> Self::Bytes {
+        let mut bytes = Self::Bytes::zeroed(Self::SIZE);
+        self.unchecked_write_to_slice(bytes.as_mut());
+        bytes
+    }
+
+    /// check the given bytes and decode the object
+    #[inline]
+    fn from_bytes(bytes: &Self::Bytes) -> Result<Self, Error> {
+        View::try_from_slice(bytes.as_ref()).map(View::unpack)
+    }
 }
@@ -0,0 +1,220 @@
+/*!
+memory mapped files of packed records
+
+the [`View`]s handed out by [`MappedFile`] and [`MappedFileMut`] are
+borrowing the mapping so the file cannot be unmapped while they are
+still in use.
+*/
+
+use crate::{Error, Packed, Records, Sequence, View};
+use memmap2::{Mmap, MmapMut};
+use std::{fs::OpenOptions, io, path::Path};
+
+/// a read only memory mapped file
+///
+/// ```no_run
+/// use packtool::{mmap::MappedFile, Packed};
+///
+/// #[derive(Packed)]
+/// pub struct Entry {
+///     key: [u8; 32],
+///     offset: u64,
+/// }
+///
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// // SAFETY: the file is not modified while it is mapped
+/// let file = unsafe { MappedFile::open("index.bin")? };
+///
+/// for entry in file.records::<Entry>()? {
+///     println!("{}", Entry::offset(entry).unpack());
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct MappedFile {
+    map: Mmap,
+}
+
+/// a writable memory mapped file
+///
+/// the records can be patched in place and the modifications are
+/// written back to the file with [`MappedFileMut::flush`].
+#[derive(Debug)]
+pub struct MappedFileMut {
+    map: MmapMut,
+}
+
+/// check there is a `T` at `offset` in the `slice`
+fn view_at<T: Packed>(slice: &[u8], offset: usize) -> Result<View<'_, T>, Error> {
+    let remaining = slice.get(offset..).unwrap_or_default();
+    match remaining.get(..T::SIZE) {
+        Some(slice) => View::try_from_slice(slice),
+        None => Err(Error::invalid_size::<T>(remaining.len(), T::SIZE)),
+    }
+}
+
+impl MappedFile {
+    /// map the file at the given path in memory
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified (by this or another process)
+    /// while it is mapped: the checked [`View`]s could otherwise
+    /// become invalid. See [`memmap2::Mmap::map`].
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).open(path)?;
+        let map = Mmap::map(&file)?;
+        Ok(Self { map })
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.map
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// get a checked [`View`] of the `T` starting at `offset`
+    #[inline]
+    pub fn view<T: Packed>(&self, offset: usize) -> Result<View<'_, T>, Error> {
+        view_at(self.as_slice(), offset)
+    }
+
+    /// check up front that the whole file is a sequence of valid `T`
+    ///
+    /// returns [`Error::Truncated`] if the file does not end on a
+    /// record boundary.
+    #[inline]
+    pub fn records<T: Packed>(&self) -> Result<Sequence<'_, T>, Error> {
+        Sequence::try_from_slice(self.as_slice())
+    }
+
+    /// iterate through the records of the file, checking them
+    /// only when they are reached.
+    #[inline]
+    pub fn lazy_records<T: Packed>(&self) -> Records<'_, T> {
+        Records::new(self.as_slice())
+    }
+}
+
+impl MappedFileMut {
+    /// map the file at the given path in memory for reading and writing
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified by another process while it is
+    /// mapped. See [`memmap2::MmapMut::map_mut`].
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        let map = MmapMut::map_mut(&file)?;
+        Ok(Self { map })
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.map
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// get a checked [`View`] of the `T` starting at `offset`
+    #[inline]
+    pub fn view<T: Packed>(&self, offset: usize) -> Result<View<'_, T>, Error> {
+        view_at(self.as_slice(), offset)
+    }
+
+    /// see [`MappedFile::records`]
+    #[inline]
+    pub fn records<T: Packed>(&self) -> Result<Sequence<'_, T>, Error> {
+        Sequence::try_from_slice(self.as_slice())
+    }
+
+    /// see [`MappedFile::lazy_records`]
+    #[inline]
+    pub fn lazy_records<T: Packed>(&self) -> Records<'_, T> {
+        Records::new(self.as_slice())
+    }
+
+    /// overwrite the `T` at `offset` with the given value
+    pub fn write<T: Packed>(&mut self, offset: usize, value: &T) -> Result<(), Error> {
+        let len = self.len();
+        let slice = self
+            .map
+            .get_mut(offset..)
+            .and_then(|slice| slice.get_mut(..T::SIZE))
+            .ok_or_else(|| Error::invalid_size::<T>(len.saturating_sub(offset), T::SIZE))?;
+
+        value.unchecked_write_to_slice(slice);
+        Ok(())
+    }
+
+    /// overwrite one field of the record `T` at `offset`
+    ///
+    /// the record is checked first and the field is selected with
+    /// one of the accessors of `T`:
+    ///
+    /// ```no_run
+    /// use packtool::{mmap::MappedFileMut, Packed};
+    ///
+    /// #[derive(Packed)]
+    /// pub struct Entry {
+    ///     key: [u8; 32],
+    ///     offset: u64,
+    /// }
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = unsafe { MappedFileMut::open("index.bin")? };
+    ///
+    /// file.patch(40, Entry::offset, &1024)?;
+    /// file.flush()?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// if the [`View`] returned by `field` is not part of the record.
+    pub fn patch<T, U, F>(&mut self, offset: usize, field: F, value: &U) -> Result<(), Error>
+    where
+        T: Packed,
+        U: Packed,
+        F: for<'a> FnOnce(View<'a, T>) -> View<'a, U>,
+    {
+        let record = self.view::<T>(offset)?;
+        let range = field(record)
+            .range_in(record.as_slice())
+            .unwrap_or_else(|| {
+                panic!(
+                    "the field View<{}> is not part of the record {}",
+                    std::any::type_name::<U>(),
+                    std::any::type_name::<T>(),
+                )
+            });
+
+        let start = offset + range.start;
+        let end = offset + range.end;
+        value.unchecked_write_to_slice(&mut self.map[start..end]);
+        Ok(())
+    }
+
+    /// write the modifications back to the file
+    #[inline]
+    pub fn flush(&self) -> io::Result<()> {
+        self.map.flush()
+    }
+}
@@ -0,0 +1,484 @@
+/*!
+numbers written with ASCII digits
+
+tar, cpio, ar and many other legacy formats store their numbers as a
+fixed number of ASCII digits. [`Octal<N>`], [`Decimal<N>`] and
+[`HexAscii<N>`] are packed numbers of `N` bytes:
+
+* reading accepts leading spaces, then the digits, then any padding
+  made of `NUL`s and spaces. A field without any digit is `0`;
+* [`Packed::check`] rejects the invalid digits, the digits after the
+  padding and the values larger than [`Number::MAX`], which could not
+  be written back with the terminator;
+* writing pads the digits with zeros and ends with the terminator:
+  a `NUL` for [`Octal`] (as in tar) and none for [`Decimal`] and
+  [`HexAscii`] (as in cpio). It can be changed with the second type
+  parameter: [`Nul`], [`Space`] or [`Unterminated`].
+
+```
+use packtool::{Decimal, HexAscii, Octal, Packed, Packet, View};
+
+#[derive(Packed)]
+pub struct Entry {
+    mode: Octal<8>,
+    size: Decimal<6>,
+    inode: HexAscii<4>,
+}
+
+let entry = View::<Entry>::try_from_slice(b" 000644 12    00fF").unwrap();
+assert_eq!(Entry::mode(entry).unpack().value(), 0o644);
+assert_eq!(Entry::size(entry).unpack().value(), 12);
+assert_eq!(Entry::inode(entry).unpack().value(), 0xff);
+
+let entry = Packet::pack(&entry.unpack());
+assert_eq!(entry.as_ref(), b"0000644\000001200FF");
+
+// 9 is not an octal digit
+assert!(View::<Octal<4>>::try_from_slice(b"0009").is_err());
+// only padding after the digits
+assert!(View::<Decimal<4>>::try_from_slice(b"1 2 ").is_err());
+// too large for the 3 digits and the terminator of `Octal<4>`
+assert!(Octal::<4>::new(0o1000).is_err());
+```
+*/
+
+use crate::{
+    builder::{BuilderField, Unset},
+    Error, Inspector, Packed,
+};
+use std::{borrow::Cow, cmp::Ordering, convert::TryFrom, fmt, hash, marker::PhantomData};
+
+/// a number of `N` bytes written with octal digits, `NUL` terminated
+pub type Octal<const N: usize, T = Nul> = Number<N, Base8, T>;
+
+/// a number of `N` bytes written with decimal digits
+pub type Decimal<const N: usize, T = Unterminated> = Number<N, Base10, T>;
+
+/// a number of `N` bytes written with hexadecimal digits, upper or
+/// lower case
+pub type HexAscii<const N: usize, T = Unterminated> = Number<N, Base16, T>;
+
+/// the base of the digits of a [`Number`]
+pub trait Radix {
+    /// the base: `8`, `10` or `16`
+    const RADIX: u32;
+
+    /// the name of the digits, used in the error messages
+    const NAME: &'static str;
+}
+
+/// the bytes written after the digits of a [`Number`]
+pub trait Terminator {
+    /// the terminator, it is not counted in the digits
+    const BYTES: &'static [u8];
+}
+
+/// octal digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Base8;
+
+/// decimal digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Base10;
+
+/// hexadecimal digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Base16;
+
+/// the digits are followed by a `NUL`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Nul;
+
+/// the digits are followed by a space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Space;
+
+/// the digits take all the bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Unterminated;
+
+impl Radix for Base8 {
+    const RADIX: u32 = 8;
+    const NAME: &'static str = "octal";
+}
+
+impl Radix for Base10 {
+    const RADIX: u32 = 10;
+    const NAME: &'static str = "decimal";
+}
+
+impl Radix for Base16 {
+    const RADIX: u32 = 16;
+    const NAME: &'static str = "hexadecimal";
+}
+
+impl Terminator for Nul {
+    const BYTES: &'static [u8] = b"\0";
+}
+
+impl Terminator for Space {
+    const BYTES: &'static [u8] = b" ";
+}
+
+impl Terminator for Unterminated {
+    const BYTES: &'static [u8] = b"";
+}
+
+/// a number of `N` bytes written with the digits of `R` and ended
+/// with `T`, see the [module documentation](self)
+pub struct Number<const N: usize, R, T> {
+    value: u64,
+    _marker: PhantomData<fn() -> (R, T)>,
+}
+
+/// the largest value written with `digits` digits in `radix`
+const fn max_value(radix: u32, digits: usize) -> u64 {
+    let mut max: u64 = 0;
+    let mut digit = 0;
+    while digit < digits {
+        max = match max.checked_mul(radix as u64) {
+            Some(max) => max + (radix as u64 - 1),
+            None => return u64::MAX,
+        };
+        digit += 1;
+    }
+    max
+}
+
+fn is_padding(byte: u8) -> bool {
+    byte == 0 || byte == b' '
+}
+
+impl<const N: usize, R: Radix, T: Terminator> Number<N, R, T> {
+    /// the number of digits written
+    pub const DIGITS: usize = N - T::BYTES.len();
+
+    /// the largest value that can be written
+    pub const MAX: u64 = max_value(R::RADIX, Self::DIGITS);
+
+    /// fails if the `value` needs more than [`Self::DIGITS`] digits
+    pub fn new(value: u64) -> Result<Self, Error> {
+        if value > Self::MAX {
+            Err(Error::number_too_large::<Self>(value, Self::MAX))
+        } else {
+            Ok(Self {
+                value,
+                _marker: PhantomData,
+            })
+        }
+    }
+
+    /// the value of the number
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    fn parse(slice: &[u8]) -> Result<u64, Cow<'static, str>> {
+        let start = slice.iter().take_while(|byte| **byte == b' ').count();
+        let digits = slice[start..]
+            .iter()
+            .take_while(|byte| !is_padding(**byte))
+            .count();
+        let end = start + digits;
+
+        let mut value: u64 = 0;
+        for (index, byte) in slice.iter().enumerate().take(end).skip(start) {
+            let digit = char::from(*byte).to_digit(R::RADIX).ok_or_else(|| {
+                Cow::Owned(format!(
+                    "invalid {} digit 0x{:02x} at byte {}",
+                    R::NAME,
+                    byte,
+                    index
+                ))
+            })?;
+            value = value
+                .checked_mul(R::RADIX as u64)
+                .and_then(|value| value.checked_add(digit as u64))
+                .ok_or(Cow::Borrowed("the value does not fit in an u64"))?;
+        }
+
+        if let Some(index) = slice[end..].iter().position(|byte| !is_padding(*byte)) {
+            return Err(Cow::Owned(format!(
+                "expecting NUL or space padding after the digits but found 0x{:02x} at byte {}",
+                slice[end + index],
+                end + index
+            )));
+        }
+
+        Ok(value)
+    }
+
+    /// parse the `slice`, the value must fit in [`Self::DIGITS`] digits
+    /// so it is written back as it was read
+    fn read(slice: &[u8]) -> Result<u64, Error> {
+        let value = Self::parse(slice).map_err(Error::invalid_number::<Self>)?;
+        if value > Self::MAX {
+            return Err(Error::number_too_large::<Self>(value, Self::MAX));
+        }
+        Ok(value)
+    }
+}
+
+impl<const N: usize, R: Radix, T: Terminator> Packed for Number<N, R, T> {
+    const SIZE: usize = {
+        assert!(
+            T::BYTES.len() < N,
+            "the number needs at least one digit and the terminator"
+        );
+        N
+    };
+    type Bytes = [u8; N];
+
+    #[inline]
+    fn unchecked_read_from_slice(slice: &[u8]) -> Self {
+        Self {
+            value: Self::parse(slice).unwrap_or_default(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
+        let (digits, terminator) = slice.split_at_mut(Self::DIGITS);
+
+        let mut value = self.value;
+        for byte in digits.iter_mut().rev() {
+            let digit = (value % R::RADIX as u64) as u32;
+            *byte = std::char::from_digit(digit, R::RADIX)
+                .map(|digit| digit.to_ascii_uppercase() as u8)
+                .unwrap_or(b'0');
+            value /= R::RADIX as u64;
+        }
+        terminator.copy_from_slice(T::BYTES);
+    }
+
+    fn check(slice: &[u8]) -> Result<(), Error> {
+        Self::read(slice).map(|_| ())
+    }
+
+    fn inspect(slice: &[u8], inspector: &mut Inspector) {
+        match Self::read(slice) {
+            Ok(value) if R::RADIX == 8 => {
+                inspector.value(format_args!("{} (0o{:o})", value, value))
+            }
+            Ok(value) if R::RADIX == 16 => {
+                inspector.value(format_args!("{} (0x{:X})", value, value))
+            }
+            Ok(value) => inspector.value(value),
+            Err(error) => inspector.error(error),
+        }
+    }
+}
+
+impl<const N: usize, R: Radix, T: Terminator> BuilderField for Number<N, R, T> {
+    type Initial = Unset;
+}
+
+impl<const N: usize, R: Radix, T: Terminator> TryFrom<u64> for Number<N, R, T> {
+    type Error = Error;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl<const N: usize, R, T> From<Number<N, R, T>> for u64 {
+    fn from(number: Number<N, R, T>) -> Self {
+        number.value
+    }
+}
+
+impl<const N: usize, R, T> Clone for Number<N, R, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const N: usize, R, T> Copy for Number<N, R, T> {}
+
+impl<const N: usize, R, T> PartialEq for Number<N, R, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<const N: usize, R, T> Eq for Number<N, R, T> {}
+
+impl<const N: usize, R, T> PartialOrd for Number<N, R, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize, R, T> Ord for Number<N, R, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<const N: usize, R, T> hash::Hash for Number<N, R, T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<const N: usize, R, T> Default for Number<N, R, T> {
+    fn default() -> Self {
+        Self {
+            value: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<const N: usize, R: Radix, T> fmt::Debug for Number<N, R, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match R::RADIX {
+            8 => write!(f, "{:#o}", self.value),
+            16 => write!(f, "{:#x}", self.value),
+            _ => write!(f, "{}", self.value),
+        }
+    }
+}
+
+impl<const N: usize, R, T> fmt::Display for Number<N, R, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, const N: usize, R: Radix, T: Terminator> arbitrary::Arbitrary<'a> for Number<N, R, T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            value: u.int_in_range(0..=Self::MAX)?,
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<const N: usize, R: Radix, T: Terminator> crate::ArbitraryPacked for Number<N, R, T> {
+    fn arbitrary_write(
+        u: &mut arbitrary::Unstructured<'_>,
+        slice: &mut [u8],
+    ) -> arbitrary::Result<()> {
+        let number: Self = arbitrary::Arbitrary::arbitrary(u)?;
+        number.unchecked_write_to_slice(slice);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<const N: usize, R, T> quickcheck::Arbitrary for Number<N, R, T>
+where
+    R: Radix + 'static,
+    T: Terminator + 'static,
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let value = u64::arbitrary(g);
+        Self {
+            // `MAX + 1` overflows when the field holds any `u64`
+            value: Self::MAX
+                .checked_add(1)
+                .map_or(value, |modulus| value % modulus),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl<const N: usize, R, T> proptest::arbitrary::Arbitrary for Number<N, R, T>
+where
+    R: Radix + 'static,
+    T: Terminator + 'static,
+{
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<std::ops::RangeInclusive<u64>, fn(u64) -> Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy as _;
+
+        (0..=Self::MAX).prop_map(|value| Self {
+            value,
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Packet, View};
+
+    fn read<T: Packed>(bytes: &[u8]) -> Result<T, Error> {
+        View::<T>::try_from_slice(bytes).map(View::unpack)
+    }
+
+    #[quickcheck]
+    fn octal_round_trip(value: u64) -> bool {
+        let value = value % (Octal::<12>::MAX + 1);
+        let number = Octal::<12>::new(value).unwrap();
+        let packet = Packet::pack(&number);
+        read::<Octal<12>>(packet.as_ref()).unwrap().value() == value
+            && packet.as_ref() == format!("{:011o}\0", value).as_bytes()
+    }
+
+    #[quickcheck]
+    fn hex_round_trip(value: u64) -> bool {
+        let number = HexAscii::<16>::new(value).unwrap();
+        let packet = Packet::pack(&number);
+        read::<HexAscii<16>>(packet.as_ref()).unwrap() == number
+            && packet.as_ref() == format!("{:016X}", value).as_bytes()
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[quickcheck]
+    fn arbitrary_numbers_are_valid(hex: HexAscii<16>, octal: Octal<12>) -> bool {
+        read::<HexAscii<16>>(Packet::pack(&hex).as_ref()).unwrap() == hex
+            && read::<Octal<12>>(Packet::pack(&octal).as_ref()).unwrap() == octal
+    }
+
+    #[test]
+    fn tar() {
+        assert_eq!(read::<Octal<12>>(b"00000001750\0").unwrap().value(), 1000);
+        assert_eq!(read::<Octal<8>>(b"000644 \0").unwrap().value(), 0o644);
+        assert_eq!(read::<Octal<8>>(b"  1750  ").unwrap().value(), 1000);
+        assert_eq!(read::<Octal<8>>(b"\0\0\0\0\0\0\0\0").unwrap().value(), 0);
+        assert_eq!(read::<Octal<4>>(b"777\0").unwrap().value(), 0o777);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(read::<Octal<4>>(b"0128").is_err());
+        assert!(read::<Octal<4>>(b"-12\0").is_err());
+        assert!(read::<Decimal<4>>(b"12\0a").is_err());
+        assert!(read::<HexAscii<4>>(b"0xff").is_err());
+        assert!(read::<Decimal<24>>(b"999999999999999999999999").is_err());
+    }
+
+    #[test]
+    fn too_large_to_be_written_back() {
+        // the terminator would be lost when writing the value back
+        assert_eq!(
+            read::<Octal<4>>(b"7777").unwrap_err().to_string(),
+            "Number too large for packtool::number::Number<4, packtool::number::Base8, packtool::number::Nul>: 4095 is greater than 511"
+        );
+        assert!(read::<Decimal<3, Space>>(b"100").is_err());
+        assert!(read::<HexAscii<2>>(b"ff").is_ok());
+    }
+
+    #[test]
+    fn too_large() {
+        assert_eq!(Decimal::<2>::MAX, 99);
+        assert_eq!(Octal::<3, Space>::MAX, 0o77);
+        assert_eq!(HexAscii::<24>::MAX, u64::MAX);
+        assert_eq!(
+            Decimal::<2>::new(100).unwrap_err().to_string(),
+            "Number too large for packtool::number::Number<2, packtool::number::Base10, packtool::number::Unterminated>: 100 is greater than 99"
+        );
+        assert_eq!(
+            Packet::pack(&Octal::<3, Space>::new(0o7).unwrap()).as_ref(),
+            b"07 "
+        );
+    }
+}
@@ -0,0 +1,417 @@
+/*!
+offsets to other records of the same buffer
+
+ELF, GPT, fonts and many container formats store integers pointing
+elsewhere in the file. [`Offset<I, T, M>`] is such an integer `I`
+pointing to a `T`, the addressing mode `M` says where the offset
+starts from:
+
+* [`Absolute`] (the default): from the start of the [`Buffer`];
+* [`FromField`]: from the position of the offset itself;
+* [`FromStruct`]: from the position of the structure holding the
+  offset.
+
+a [`View`] only knows its own bytes. A [`Rooted`] view also knows the
+whole [`Buffer`] and its position in it, [`Rooted::project`] follows
+the accessors of the structures and [`Rooted::follow`] resolves an
+offset to the `Rooted` view of its target. The target must be within
+the buffer and it is checked like [`View::try_from_slice`] does.
+
+```
+use packtool::{Buffer, Offset, Packed};
+use packtool::offset::FromField;
+
+#[derive(Packed)]
+pub struct Section {
+    kind: u16,
+    length: u16,
+}
+
+#[derive(Packed)]
+pub struct Header {
+    magic: [u8; 2],
+    section: Offset<u32, Section>,
+    next: Offset<i8, Section, FromField>,
+}
+
+let bytes = [b'P', b'K', 8, 0, 0, 0, 6, 0, 1, 0, 42, 0, 2, 0, 7, 0];
+let buffer = Buffer::new(&bytes);
+let header = buffer.root::<Header>().unwrap();
+
+// absolute: the section is at byte 8
+let section = header.project(Header::section).follow().unwrap();
+assert_eq!(section.position(), 8);
+assert_eq!(Section::length(section.view()).unpack(), 42);
+
+// relative: 6 + 6 = 12
+let next = header.project(Header::next).follow().unwrap();
+assert_eq!(next.position(), 12);
+assert_eq!(next.project(Section::kind).unpack(), 2);
+
+// out of the buffer
+let header = Buffer::new(&bytes[..10]).root::<Header>().unwrap();
+assert!(header.project(Header::section).follow().is_err());
+```
+
+[`View`]: crate::View
+[`View::try_from_slice`]: crate::View::try_from_slice
+*/
+
+use crate::{
+    builder::{BuilderField, Unset},
+    Error, Inspector, Packed, View,
+};
+use std::{cmp::Ordering, convert::TryFrom, fmt, hash, marker::PhantomData};
+
+/// where an [`Offset`] starts from
+pub trait Addressing {
+    /// the position the offset is added to, given the position of the
+    /// offset field and of the structure holding it
+    fn origin(field: usize, structure: usize) -> usize;
+}
+
+/// the offset starts from the start of the [`Buffer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Absolute;
+
+/// the offset starts from the position of the offset itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FromField;
+
+/// the offset starts from the position of the structure holding it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FromStruct;
+
+impl Addressing for Absolute {
+    fn origin(_field: usize, _structure: usize) -> usize {
+        0
+    }
+}
+
+impl Addressing for FromField {
+    fn origin(field: usize, _structure: usize) -> usize {
+        field
+    }
+}
+
+impl Addressing for FromStruct {
+    fn origin(_field: usize, structure: usize) -> usize {
+        structure
+    }
+}
+
+/// an integer `I` pointing to a `T` in the same buffer, see the
+/// [module documentation](self)
+pub struct Offset<I, T, M = Absolute> {
+    offset: I,
+    _marker: PhantomData<fn() -> (T, M)>,
+}
+
+impl<I, T, M> Offset<I, T, M> {
+    pub fn new(offset: I) -> Self {
+        Self {
+            offset,
+            _marker: PhantomData,
+        }
+    }
+
+    /// the value of the offset
+    pub fn get(self) -> I {
+        self.offset
+    }
+}
+
+impl<I, T, M> Packed for Offset<I, T, M>
+where
+    I: Packed,
+{
+    const SIZE: usize = I::SIZE;
+    type Bytes = I::Bytes;
+
+    #[inline]
+    fn unchecked_read_from_slice(slice: &[u8]) -> Self {
+        Self::new(I::unchecked_read_from_slice(slice))
+    }
+
+    #[inline]
+    fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
+        self.offset.unchecked_write_to_slice(slice)
+    }
+
+    #[inline]
+    fn check(slice: &[u8]) -> Result<(), Error> {
+        I::check(slice)
+    }
+
+    #[inline]
+    fn inspect(slice: &[u8], inspector: &mut Inspector) {
+        I::inspect(slice, inspector)
+    }
+}
+
+impl<I, T, M> BuilderField for Offset<I, T, M>
+where
+    I: Packed,
+{
+    type Initial = Unset;
+}
+
+/// the bytes the [`Offset`]s point into
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Buffer<'a> {
+    slice: &'a [u8],
+}
+
+/// a [`View`] that knows the [`Buffer`] it belongs to and its position
+/// in it
+pub struct Rooted<'a, T> {
+    buffer: Buffer<'a>,
+    view: View<'a, T>,
+    position: usize,
+    structure: usize,
+}
+
+impl<'a> Buffer<'a> {
+    pub fn new(slice: &'a [u8]) -> Self {
+        Self { slice }
+    }
+
+    /// check the `T` at the start of the buffer
+    pub fn root<T: Packed>(self) -> Result<Rooted<'a, T>, Error> {
+        self.view_at(0)
+    }
+
+    /// check the `T` at the given `position` of the buffer
+    pub fn view_at<T: Packed>(self, position: usize) -> Result<Rooted<'a, T>, Error> {
+        let slice = position
+            .checked_add(T::SIZE)
+            .and_then(|end| self.slice.get(position..end))
+            .ok_or_else(|| {
+                Error::out_of_bounds::<T>(position as i128, T::SIZE, self.slice.len())
+            })?;
+
+        Ok(Rooted {
+            buffer: self,
+            view: View::try_from_slice(slice)?,
+            position,
+            structure: position,
+        })
+    }
+
+    #[inline]
+    pub fn as_slice(self) -> &'a [u8] {
+        self.slice
+    }
+
+    #[inline]
+    pub fn len(self) -> usize {
+        self.slice.len()
+    }
+
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.slice.is_empty()
+    }
+}
+
+impl<'a, T> Rooted<'a, T>
+where
+    T: Packed,
+{
+    #[inline]
+    pub fn view(self) -> View<'a, T> {
+        self.view
+    }
+
+    #[inline]
+    #[must_use = "this will clone data from the slice, it is often expensive"]
+    pub fn unpack(self) -> T {
+        self.view.unpack()
+    }
+
+    #[inline]
+    pub fn buffer(self) -> Buffer<'a> {
+        self.buffer
+    }
+
+    /// the position of the view in the buffer
+    #[inline]
+    pub fn position(self) -> usize {
+        self.position
+    }
+
+    /// follow an accessor of `T`: `header.project(Header::section)`
+    ///
+    /// the structure of the returned view, used by [`FromStruct`]
+    /// offsets, is `self`.
+    ///
+    /// # Panics
+    ///
+    /// panics if the accessor returns a view that is not within the
+    /// view of `T`
+    pub fn project<U>(self, accessor: impl FnOnce(View<'a, T>) -> View<'a, U>) -> Rooted<'a, U>
+    where
+        U: Packed,
+    {
+        let view = accessor(self.view);
+        let range = view
+            .range_in(self.view.as_slice())
+            .expect("the accessor must return a view within the structure");
+
+        Rooted {
+            buffer: self.buffer,
+            view,
+            position: self.position + range.start,
+            structure: self.position,
+        }
+    }
+}
+
+impl<'a, I, T, M> Rooted<'a, Offset<I, T, M>>
+where
+    I: Packed + Into<i128>,
+    T: Packed,
+    M: Addressing,
+{
+    /// resolve the offset to the `T` it points to
+    ///
+    /// fails if the target is not within the [`Buffer`] or is not a
+    /// valid `T`
+    pub fn follow(self) -> Result<Rooted<'a, T>, Error> {
+        let offset: i128 = self.unpack().get().into();
+        let origin = M::origin(self.position, self.structure) as i128;
+        let target = origin + offset;
+
+        let position = usize::try_from(target)
+            .map_err(|_| Error::out_of_bounds::<T>(target, T::SIZE, self.buffer.len()))?;
+        self.buffer.view_at(position)
+    }
+}
+
+impl<'a, T> Clone for Rooted<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for Rooted<'a, T> {}
+
+impl<'a, T> fmt::Debug for Rooted<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rooted")
+            .field("position", &self.position)
+            .field("view", &self.view)
+            .finish()
+    }
+}
+
+impl<'a> fmt::Debug for Buffer<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Buffer")
+            .field("len", &self.slice.len())
+            .finish()
+    }
+}
+
+impl<I: Clone, T, M> Clone for Offset<I, T, M> {
+    fn clone(&self) -> Self {
+        Self::new(self.offset.clone())
+    }
+}
+
+impl<I: Copy, T, M> Copy for Offset<I, T, M> {}
+
+impl<I: PartialEq, T, M> PartialEq for Offset<I, T, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset
+    }
+}
+
+impl<I: Eq, T, M> Eq for Offset<I, T, M> {}
+
+impl<I: PartialOrd, T, M> PartialOrd for Offset<I, T, M> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.offset.partial_cmp(&other.offset)
+    }
+}
+
+impl<I: Ord, T, M> Ord for Offset<I, T, M> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.offset.cmp(&other.offset)
+    }
+}
+
+impl<I: hash::Hash, T, M> hash::Hash for Offset<I, T, M> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.offset.hash(state)
+    }
+}
+
+impl<I: Default, T, M> Default for Offset<I, T, M> {
+    fn default() -> Self {
+        Self::new(I::default())
+    }
+}
+
+impl<I: fmt::Debug, T, M> fmt::Debug for Offset<I, T, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Offset").field(&self.offset).finish()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, I, T, M> arbitrary::Arbitrary<'a> for Offset<I, T, M>
+where
+    I: arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        I::arbitrary(u).map(Self::new)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<I, T, M> crate::ArbitraryPacked for Offset<I, T, M>
+where
+    I: crate::ArbitraryPacked,
+{
+    #[inline]
+    fn arbitrary_write(
+        u: &mut arbitrary::Unstructured<'_>,
+        slice: &mut [u8],
+    ) -> arbitrary::Result<()> {
+        I::arbitrary_write(u, slice)
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<I, T, M> quickcheck::Arbitrary for Offset<I, T, M>
+where
+    I: quickcheck::Arbitrary,
+    T: 'static,
+    M: 'static,
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Self::new(I::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.offset.shrink().map(Self::new))
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl<I, T, M> proptest::arbitrary::Arbitrary for Offset<I, T, M>
+where
+    I: proptest::arbitrary::Arbitrary,
+    T: 'static,
+    M: 'static,
+{
+    type Parameters = I::Parameters;
+    type Strategy = proptest::strategy::Map<I::Strategy, fn(I) -> Self>;
+
+    fn arbitrary_with(parameters: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy as _;
+
+        I::arbitrary_with(parameters).prop_map(Self::new)
+    }
+}
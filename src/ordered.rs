@@ -0,0 +1,271 @@
+/*!
+order preserving encoding of the packed types
+
+the default encoding of the integers is little endian (and two's
+complement for the signed integers), so comparing the bytes of two
+packed values does not compare the values. The types deriving
+`Packed` with `#[packed(ordered)]` use the encoding of the
+[`OrderedPacked`] trait instead:
+
+* the integers are big endian and the sign bit of the signed integers
+  is flipped;
+* the discriminants of the enumerations are encoded like integers and
+  the variants have to be declared by increasing discriminant;
+* the byte arrays and the units are left as they are;
+* the structures and tuples are the concatenation of their fields.
+
+This way comparing the bytes of two values (for example with the `Ord`
+implementation of [`View`] or [`Packet`], or in a sorted key value store)
+gives the same result as comparing the values field by field.
+
+```
+use packtool::{Ordered, Packed, Packet};
+
+#[derive(Packed, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[packed(ordered)]
+pub struct Key {
+    height: u32,
+    delta: i16,
+}
+
+let a = Packet::pack(&Key { height: 1, delta: 2 });
+let b = Packet::pack(&Key { height: 256, delta: -2 });
+
+assert!(a < b);
+assert_eq!(a.as_ref(), &[0, 0, 0, 1, 0x80, 2]);
+
+let height: Ordered<u32> = Key::height(b.view()).unpack();
+assert_eq!(height.into_inner(), 256);
+```
+
+The variants of an ordered enumeration must be declared by increasing
+discriminant, so the derived `Ord` matches the order of the bytes:
+
+```compile_fail
+use packtool::Packed;
+
+#[derive(Packed)]
+#[packed(ordered)]
+#[repr(u8)]
+pub enum Kind {
+    Block = 2,
+    Transaction = 1,
+}
+```
+
+[`View`]: crate::View
+[`Packet`]: crate::Packet
+*/
+
+use crate::{
+    builder::{BuilderField, Unset},
+    Error, Packed,
+};
+use std::convert::TryInto as _;
+
+/// order preserving encoding of a packed type, see the [module
+/// documentation](self).
+///
+/// the encoding always takes [`Packed::SIZE`] bytes.
+pub trait OrderedPacked: Packed {
+    /// the packed type with the order preserving encoding of `Self`.
+    ///
+    /// This is the type of the [`View`](crate::View) returned by the
+    /// accessors of the `#[packed(ordered)]` types. It is `Self` for the
+    /// types already encoded in order (derived types, byte arrays) and
+    /// [`Ordered<Self>`] otherwise.
+    type Ordered: Packed;
+
+    /// assuming the given slice is valid, read the order preserving
+    /// encoding of the object
+    fn unchecked_read_ordered(slice: &[u8]) -> Self;
+
+    /// write the order preserving encoding of the object
+    fn unchecked_write_ordered(&self, slice: &mut [u8]);
+
+    /// check the validity of the order preserving encoding in `slice`
+    fn check_ordered(slice: &[u8]) -> Result<(), Error>;
+}
+
+/// a value of `T` packed with its order preserving encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Ordered<T>(pub T);
+
+impl<T> Ordered<T> {
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: OrderedPacked> Packed for Ordered<T> {
+    const SIZE: usize = T::SIZE;
+    type Bytes = T::Bytes;
+
+    #[inline]
+    fn unchecked_read_from_slice(slice: &[u8]) -> Self {
+        Self(T::unchecked_read_ordered(slice))
+    }
+
+    #[inline]
+    fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
+        self.0.unchecked_write_ordered(slice)
+    }
+
+    #[inline]
+    fn check(slice: &[u8]) -> Result<(), Error> {
+        T::check_ordered(slice)
+    }
+}
+
+impl<T: OrderedPacked> BuilderField for Ordered<T> {
+    type Initial = Unset;
+}
+
+#[cfg(feature = "arbitrary")]
+impl<T> crate::ArbitraryPacked for Ordered<T>
+where
+    T: OrderedPacked + crate::ArbitraryPacked,
+{
+    #[inline]
+    fn arbitrary_write(
+        u: &mut arbitrary::Unstructured<'_>,
+        slice: &mut [u8],
+    ) -> arbitrary::Result<()> {
+        T::arbitrary_write_ordered(u, slice)
+    }
+}
+
+macro_rules! unsigned_ordered {
+    ($t:ty) => {
+        impl OrderedPacked for $t {
+            type Ordered = Ordered<$t>;
+
+            #[inline]
+            fn unchecked_read_ordered(slice: &[u8]) -> Self {
+                <$t>::from_be_bytes(slice.try_into().expect("slice of the appropriate size"))
+            }
+
+            #[inline]
+            fn unchecked_write_ordered(&self, slice: &mut [u8]) {
+                slice.copy_from_slice(&self.to_be_bytes())
+            }
+
+            #[inline]
+            fn check_ordered(_slice: &[u8]) -> Result<(), Error> {
+                Ok(())
+            }
+        }
+    };
+}
+
+macro_rules! signed_ordered {
+    ($t:ty, $u:ty) => {
+        impl OrderedPacked for $t {
+            type Ordered = Ordered<$t>;
+
+            #[inline]
+            fn unchecked_read_ordered(slice: &[u8]) -> Self {
+                let flipped = <$u>::unchecked_read_ordered(slice);
+                (flipped ^ (1 << (<$u>::BITS - 1))) as $t
+            }
+
+            #[inline]
+            fn unchecked_write_ordered(&self, slice: &mut [u8]) {
+                let flipped = (*self as $u) ^ (1 << (<$u>::BITS - 1));
+                flipped.unchecked_write_ordered(slice)
+            }
+
+            #[inline]
+            fn check_ordered(_slice: &[u8]) -> Result<(), Error> {
+                Ok(())
+            }
+        }
+    };
+}
+
+unsigned_ordered!(u8);
+unsigned_ordered!(u16);
+unsigned_ordered!(u32);
+unsigned_ordered!(u64);
+unsigned_ordered!(u128);
+unsigned_ordered!(usize);
+signed_ordered!(i8, u8);
+signed_ordered!(i16, u16);
+signed_ordered!(i32, u32);
+signed_ordered!(i64, u64);
+signed_ordered!(i128, u128);
+signed_ordered!(isize, usize);
+
+impl<const SIZE: usize> OrderedPacked for [u8; SIZE] {
+    type Ordered = Self;
+
+    #[inline]
+    fn unchecked_read_ordered(slice: &[u8]) -> Self {
+        Self::unchecked_read_from_slice(slice)
+    }
+
+    #[inline]
+    fn unchecked_write_ordered(&self, slice: &mut [u8]) {
+        self.unchecked_write_to_slice(slice)
+    }
+
+    #[inline]
+    fn check_ordered(slice: &[u8]) -> Result<(), Error> {
+        Self::check(slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ordered_bytes<T: OrderedPacked>(value: T) -> Vec<u8> {
+        let mut bytes = vec![0; T::SIZE];
+        value.unchecked_write_ordered(&mut bytes);
+        bytes
+    }
+
+    macro_rules! mk_ordered_test {
+        ($f:ident, $Type:ty) => {
+            #[quickcheck]
+            fn $f(a: $Type, b: $Type) -> bool {
+                let bytes_a = ordered_bytes(a);
+                let bytes_b = ordered_bytes(b);
+
+                <$Type>::unchecked_read_ordered(&bytes_a) == a && a.cmp(&b) == bytes_a.cmp(&bytes_b)
+            }
+        };
+    }
+
+    mk_ordered_test!(u8, u8);
+    mk_ordered_test!(u16, u16);
+    mk_ordered_test!(u32, u32);
+    mk_ordered_test!(u64, u64);
+    mk_ordered_test!(u128, u128);
+    mk_ordered_test!(usize, usize);
+
+    mk_ordered_test!(i8, i8);
+    mk_ordered_test!(i16, i16);
+    mk_ordered_test!(i32, i32);
+    mk_ordered_test!(i64, i64);
+    mk_ordered_test!(i128, i128);
+    mk_ordered_test!(isize, isize);
+
+    #[quickcheck]
+    fn tuple(a: (u16, i8, u32), b: (u16, i8, u32)) -> bool {
+        let bytes_a = ordered_bytes(a);
+        let bytes_b = ordered_bytes(b);
+
+        <(u16, i8, u32)>::unchecked_read_ordered(&bytes_a) == a
+            && a.cmp(&b) == bytes_a.cmp(&bytes_b)
+    }
+
+    #[test]
+    fn signed_bounds() {
+        assert_eq!(ordered_bytes(i16::MIN), vec![0x00, 0x00]);
+        assert_eq!(ordered_bytes(-1i16), vec![0x7f, 0xff]);
+        assert_eq!(ordered_bytes(0i16), vec![0x80, 0x00]);
+        assert_eq!(ordered_bytes(i16::MAX), vec![0xff, 0xff]);
+    }
+}
@@ -1,31 +1,169 @@
-use crate::{Packed, View};
-use std::{any, borrow::Borrow, fmt, hash, marker};
+use crate::{Error, Packed, Shared, Sliceable, View};
+use std::{any, borrow::Borrow, convert::TryFrom, fmt, hash, marker, sync::Arc};
 
 /// a owned slice of memory containing the [`Packed`]
-pub struct Packet<T> {
-    boxed: Box<[u8]>,
+///
+/// the memory is held in the storage `B`: a `Box<[u8]>` by default
+/// but it can be any type giving access to the bytes with [`AsRef<[u8]>`]
+/// (`Vec<u8>`, `Arc<[u8]>`, `bytes::Bytes`...). This allows to take
+/// ownership of a buffer that is already allocated without copying it.
+///
+/// ```
+/// use packtool::{Packed, Packet};
+/// use std::{convert::TryFrom, sync::Arc};
+///
+/// #[derive(Packed)]
+/// pub struct Point {
+///     x: u16,
+///     y: u16,
+/// }
+///
+/// let received: Vec<u8> = vec![1, 0, 2, 0];
+///
+/// let point = Packet::<Point, Vec<u8>>::try_from(received).unwrap();
+/// assert_eq!(Point::y(point.view()).unpack(), 2);
+///
+/// let shared: Arc<[u8]> = point.into_inner().into();
+/// let point = Packet::<Point, _>::try_from_storage(shared).unwrap();
+/// assert_eq!(Point::x(point.view()).unpack(), 1);
+/// ```
+pub struct Packet<T, B = Box<[u8]>> {
+    storage: B,
     marker: marker::PhantomData<T>,
 }
 
-impl<T> Packet<T>
+impl<T, B> Packet<T, B>
 where
     T: Packed,
+    B: AsRef<[u8]>,
 {
     #[inline]
-    pub(crate) fn new(boxed: Box<[u8]>) -> Self {
+    pub(crate) fn new(storage: B) -> Self {
         Self {
-            boxed,
+            storage,
             marker: marker::PhantomData,
         }
     }
 
+    /// take ownership of the given storage after checking it holds
+    /// a valid `T`. No copy of the bytes is performed.
+    ///
+    /// this function will perform the same checks as [`View::try_from_slice`].
+    pub fn try_from_storage(storage: B) -> Result<Self, Error> {
+        View::<T>::try_from_slice(storage.as_ref())?;
+        Ok(Self::new(storage))
+    }
+
+    /// take ownership of the given storage without checking its content.
+    ///
+    /// Only use this if you know the storage has already been checked
+    /// (for example because it comes from another [`Packet`] of the same
+    /// type). See [`View::unchecked_from_slice`].
+    #[inline]
+    pub fn unchecked_from_storage(storage: B) -> Self {
+        Self::new(storage)
+    }
+
     /// get a [`View`] of the [`Packet`].
     ///
     #[inline]
     pub fn view(&self) -> View<'_, T> {
-        View::new(self.boxed.as_ref())
+        View::new(self.storage.as_ref())
+    }
+
+    /// reconstruct the object `T` from the given [`Packet`]
+    ///
+    /// this function will involve some hoops and loops and may
+    /// involve some heap allocation.
+    #[inline]
+    #[must_use = "this will clone data from the slice, it is often expensive"]
+    pub fn unpack(&self) -> T {
+        self.view().unpack()
     }
 
+    /// access the underlying storage
+    #[inline]
+    pub fn storage(&self) -> &B {
+        &self.storage
+    }
+
+    /// take back the underlying storage
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.storage
+    }
+
+    /// convert the storage into a [`Shared`] one so the [`Packet`]
+    /// can be cheaply cloned and projected (see [`Packet::project`]).
+    #[inline]
+    pub fn into_shared(self) -> Packet<T, Shared>
+    where
+        B: Into<Shared>,
+    {
+        Packet::new(self.storage.into())
+    }
+}
+
+impl<T, B> Packet<T, B>
+where
+    T: Packed,
+    B: Sliceable,
+{
+    /// create an owned [`Packet`] of one of the sub element of `T`
+    /// pointing in the same memory as `self`.
+    ///
+    /// The function `f` usually is one of the accessor generated
+    /// by `#[derive(Packed)]`. The content is not checked again
+    /// as the returned [`View`] is guaranteed to be valid.
+    ///
+    /// ```
+    /// use packtool::{Packed, Packet, Shared};
+    ///
+    /// #[derive(Packed)]
+    /// pub struct Header {
+    ///     version: u8,
+    ///     height: u32,
+    /// }
+    ///
+    /// #[derive(Packed)]
+    /// pub struct Block {
+    ///     header: Header,
+    ///     content: [u8; 16],
+    /// }
+    ///
+    /// # let bytes = vec![1, 42, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    /// let block = Packet::<Block, Shared>::try_from_storage(bytes.into()).unwrap();
+    /// let header: Packet<Header, Shared> = block.project(Block::header);
+    ///
+    /// assert_eq!(Header::height(header.view()).unpack(), 42);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// if the returned [`View`] is not pointing in the memory of `self`.
+    pub fn project<U, F>(&self, f: F) -> Packet<U, B>
+    where
+        U: Packed,
+        F: for<'a> FnOnce(View<'a, T>) -> View<'a, U>,
+    {
+        let range = f(self.view())
+            .range_in(self.storage.as_ref())
+            .unwrap_or_else(|| {
+                panic!(
+                    "the projected View<{}> is not part of the Packet<{}>",
+                    any::type_name::<U>(),
+                    any::type_name::<T>(),
+                )
+            });
+
+        Packet::new(self.storage.slice(range))
+    }
+}
+
+impl<T> Packet<T>
+where
+    T: Packed,
+{
     /// pack any object that implements [`Packed`] into an owned
     /// slice of memory: [`Packet`].
     pub fn pack(packed: &T) -> Self {
@@ -36,70 +174,136 @@ where
         Self::new(boxed.into_boxed_slice())
     }
 
-    /// reconstruct the object `T` from the given [`Packet`]
-    ///
-    /// this function will involve some hoops and loops and may
-    /// involve some heap allocation.
+    /// take ownership of the given boxed slice after checking
+    /// it holds a valid `T`.
     #[inline]
-    #[must_use = "this will clone data from the slice, it is often expensive"]
-    pub fn unpack(&self) -> T {
-        self.view().unpack()
+    pub fn from_boxed(boxed: Box<[u8]>) -> Result<Self, Error> {
+        Self::try_from_storage(boxed)
+    }
+
+    /// take back the bytes as a [`Vec`], without copying them.
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.storage.into_vec()
+    }
+}
+
+impl<T: Packed> TryFrom<Box<[u8]>> for Packet<T> {
+    type Error = Error;
+
+    fn try_from(boxed: Box<[u8]>) -> Result<Self, Self::Error> {
+        Self::from_boxed(boxed)
+    }
+}
+
+/// the vector is converted into a boxed slice, this will reallocate
+/// the memory if the vector has more capacity than its length.
+impl<T: Packed> TryFrom<Vec<u8>> for Packet<T> {
+    type Error = Error;
+
+    fn try_from(vec: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::from_boxed(vec.into_boxed_slice())
+    }
+}
+
+impl<T: Packed> TryFrom<Vec<u8>> for Packet<T, Vec<u8>> {
+    type Error = Error;
+
+    fn try_from(vec: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::try_from_storage(vec)
     }
 }
 
-impl<T> Borrow<[u8]> for Packet<T> {
+impl<T: Packed> TryFrom<Arc<[u8]>> for Packet<T, Arc<[u8]>> {
+    type Error = Error;
+
+    fn try_from(shared: Arc<[u8]>) -> Result<Self, Self::Error> {
+        Self::try_from_storage(shared)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<T> Packet<T>
+where
+    T: crate::ArbitraryPacked,
+{
+    /// generate a random but valid [`Packet`] from the unstructured data.
+    ///
+    /// The bytes are written directly by [`ArbitraryPacked`], no `T` is
+    /// built, and always pass the [`Packed::check`] of `T` (or an error
+    /// is returned, see [`ArbitraryPacked`] for the overlays). Useful for
+    /// fuzz targets that should not waste time on invalid
+    /// discriminants or constant values.
+    ///
+    /// [`ArbitraryPacked`]: crate::ArbitraryPacked
+    pub fn arbitrary_valid(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<Self> {
+        let mut bytes = vec![0; T::SIZE].into_boxed_slice();
+        T::arbitrary_write(u, &mut bytes)?;
+        Ok(Self::new(bytes))
+    }
+}
+
+impl<T, B: AsRef<[u8]>> Borrow<[u8]> for Packet<T, B> {
     fn borrow(&self) -> &[u8] {
-        self.boxed.borrow()
+        self.storage.as_ref()
     }
 }
 
-impl<T> Clone for Packet<T> {
+impl<T, B: Clone> Clone for Packet<T, B> {
     fn clone(&self) -> Self {
         Self {
-            boxed: self.boxed.clone(),
+            storage: self.storage.clone(),
             marker: self.marker,
         }
     }
 }
 
-impl<T> AsRef<[u8]> for Packet<T> {
+impl<T, B: AsRef<[u8]>> AsRef<[u8]> for Packet<T, B> {
     fn as_ref(&self) -> &[u8] {
-        self.boxed.as_ref()
+        self.storage.as_ref()
     }
 }
 
-impl<T, U> PartialEq<Packet<U>> for Packet<T> {
-    fn eq(&self, other: &Packet<U>) -> bool {
-        self.boxed.eq(&other.boxed)
+impl<T, U, B, C> PartialEq<Packet<U, C>> for Packet<T, B>
+where
+    B: AsRef<[u8]>,
+    C: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Packet<U, C>) -> bool {
+        self.as_ref().eq(other.as_ref())
     }
 }
 
-impl<T> Eq for Packet<T> {}
+impl<T, B: AsRef<[u8]>> Eq for Packet<T, B> {}
 
-impl<T, U> PartialOrd<Packet<U>> for Packet<T> {
-    fn partial_cmp(&self, other: &Packet<U>) -> Option<std::cmp::Ordering> {
-        self.boxed.partial_cmp(&other.boxed)
+impl<T, U, B, C> PartialOrd<Packet<U, C>> for Packet<T, B>
+where
+    B: AsRef<[u8]>,
+    C: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Packet<U, C>) -> Option<std::cmp::Ordering> {
+        self.as_ref().partial_cmp(other.as_ref())
     }
 }
 
-impl<T> Ord for Packet<T> {
+impl<T, B: AsRef<[u8]>> Ord for Packet<T, B> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.boxed.cmp(&other.boxed)
+        self.as_ref().cmp(other.as_ref())
     }
 }
 
-impl<T> hash::Hash for Packet<T> {
+impl<T, B: AsRef<[u8]>> hash::Hash for Packet<T, B> {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
-        self.boxed.hash(state);
+        self.as_ref().hash(state);
         self.marker.hash(state);
     }
 }
 
-impl<T> fmt::Debug for Packet<T> {
+impl<T, B: AsRef<[u8]>> fmt::Debug for Packet<T, B> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let ty = any::type_name::<T>();
         f.debug_struct(&format!("Packet<{}>", ty))
-            .field("slice", &self.boxed)
+            .field("slice", &self.as_ref())
             .field("marker", &self.marker)
             .finish()
     }
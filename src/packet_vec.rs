@@ -0,0 +1,285 @@
+use crate::{sequence::Iter, Error, Packed, Packet, Sequence, View};
+use std::{any, fmt, iter::FromIterator, marker};
+
+/// a growable and contiguous buffer of [`Packed`] records
+///
+/// Unlike a `Vec<Packet<T>>`, all the records are stored one after the
+/// other in a single allocation. The records are checked when they are
+/// inserted so accessing them is free.
+///
+/// ```
+/// use packtool::{Packed, PacketVec};
+///
+/// #[derive(Packed)]
+/// pub struct Entry {
+///     key: u8,
+///     value: u16,
+/// }
+///
+/// let mut entries = PacketVec::new();
+/// entries.push(&Entry { key: 2, value: 0x2a });
+/// entries.push(&Entry { key: 1, value: 0x2b });
+///
+/// entries.sort_by_key(|entry| Entry::key(entry).unpack());
+///
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(Entry::value(entries.get(0).unwrap()).unpack(), 0x2b);
+/// assert_eq!(entries.as_bytes(), &[1, 0x2b, 0, 2, 0x2a, 0]);
+/// ```
+pub struct PacketVec<T> {
+    bytes: Vec<u8>,
+    marker: marker::PhantomData<fn() -> T>,
+}
+
+impl<T> PacketVec<T>
+where
+    T: Packed,
+{
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// create an empty [`PacketVec`] with enough memory for `capacity`
+    /// records.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            bytes: Vec::with_capacity(capacity * T::SIZE),
+            marker: marker::PhantomData,
+        }
+    }
+
+    /// take ownership of the given bytes after checking every one
+    /// of the records.
+    ///
+    /// returns [`Error::Truncated`] if the bytes do not end on
+    /// a record boundary.
+    pub fn try_from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+        Sequence::<T>::try_from_slice(&bytes)?;
+        Ok(Self {
+            bytes,
+            marker: marker::PhantomData,
+        })
+    }
+
+    /// number of records
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.as_sequence().len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// append the record at the end of the buffer
+    pub fn push(&mut self, record: &T) {
+        let start = self.bytes.len();
+        self.bytes.resize(start + T::SIZE, 0);
+        record.unchecked_write_to_slice(&mut self.bytes[start..]);
+    }
+
+    /// append the already checked record at the end of the buffer
+    #[inline]
+    pub fn push_view(&mut self, record: View<'_, T>) {
+        self.bytes.extend_from_slice(record.as_slice());
+    }
+
+    /// check the given slice holds a valid `T` and append it
+    /// at the end of the buffer
+    #[inline]
+    pub fn try_push_slice(&mut self, slice: &[u8]) -> Result<(), Error> {
+        let record = View::<T>::try_from_slice(slice)?;
+        self.push_view(record);
+        Ok(())
+    }
+
+    /// get the record at the given `index`
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<View<'_, T>> {
+        self.as_sequence().get(index)
+    }
+
+    /// get the last record
+    #[inline]
+    pub fn last(&self) -> Option<View<'_, T>> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// iterate through the records
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(&self.bytes)
+    }
+
+    /// shortens the buffer, keeping the first `len` records
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.bytes.truncate(len.saturating_mul(T::SIZE))
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bytes.clear()
+    }
+
+    /// remove the last record and return it
+    pub fn pop(&mut self) -> Option<Packet<T>> {
+        let start = self.bytes.len().checked_sub(T::SIZE)?;
+        let record = self.bytes.split_off(start);
+        Some(Packet::new(record.into_boxed_slice()))
+    }
+
+    /// remove the record at `index` and return it
+    ///
+    /// the last record is moved in its place, this does not preserve
+    /// the order of the records but it is O(1).
+    ///
+    /// # Panics
+    ///
+    /// if `index` is out of bounds
+    pub fn swap_remove(&mut self, index: usize) -> Packet<T> {
+        let len = self.len();
+        assert!(
+            index < len,
+            "swap_remove index (is {}) should be < len (is {})",
+            index,
+            len
+        );
+
+        let start = index * T::SIZE;
+        let last = (len - 1) * T::SIZE;
+        if start != last {
+            let (head, tail) = self.bytes.split_at_mut(last);
+            head[start..start + T::SIZE].swap_with_slice(tail);
+        }
+
+        self.pop().expect("the buffer is not empty")
+    }
+
+    /// sort the records with the key extracted from their [`View`]
+    ///
+    /// the sort is stable.
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(View<'_, T>) -> K,
+    {
+        self.sort_by(|a, b| f(*a).cmp(&f(*b)))
+    }
+
+    /// sort the records with the given comparison function
+    ///
+    /// the sort is stable.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&View<'_, T>, &View<'_, T>) -> std::cmp::Ordering,
+    {
+        let mut records = self.iter().collect::<Vec<_>>();
+        records.sort_by(|a, b| compare(a, b));
+
+        let mut bytes = Vec::with_capacity(self.bytes.capacity());
+        for record in records {
+            bytes.extend_from_slice(record.as_slice());
+        }
+        self.bytes = bytes;
+    }
+
+    /// the records as a [`Sequence`]
+    #[inline]
+    pub fn as_sequence(&self) -> Sequence<'_, T> {
+        Sequence::unchecked_from_slice(&self.bytes)
+    }
+
+    /// all the records, one after the other
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl<'a, T: Packed> Extend<&'a T> for PacketVec<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        for record in iter {
+            self.push(record);
+        }
+    }
+}
+
+impl<'a, T: Packed> Extend<View<'a, T>> for PacketVec<T> {
+    fn extend<I: IntoIterator<Item = View<'a, T>>>(&mut self, iter: I) {
+        for record in iter {
+            self.push_view(record);
+        }
+    }
+}
+
+impl<'a, T: Packed> FromIterator<&'a T> for PacketVec<T> {
+    fn from_iter<I: IntoIterator<Item = &'a T>>(iter: I) -> Self {
+        let mut records = Self::new();
+        records.extend(iter);
+        records
+    }
+}
+
+impl<'a, T: Packed> FromIterator<View<'a, T>> for PacketVec<T> {
+    fn from_iter<I: IntoIterator<Item = View<'a, T>>>(iter: I) -> Self {
+        let mut records = Self::new();
+        records.extend(iter);
+        records
+    }
+}
+
+impl<'a, T: Packed> IntoIterator for &'a PacketVec<T> {
+    type Item = View<'a, T>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Packed> Default for PacketVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> AsRef<[u8]> for PacketVec<T> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<T> Clone for PacketVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+            marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for PacketVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes.eq(&other.bytes)
+    }
+}
+
+impl<T> Eq for PacketVec<T> {}
+
+impl<T> fmt::Debug for PacketVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ty = any::type_name::<T>();
+        f.debug_struct(&format!("PacketVec<{}>", ty))
+            .field("bytes", &self.bytes)
+            .finish()
+    }
+}
@@ -1,8 +1,13 @@
-use crate::{Error, Packed};
+use crate::{
+    builder::{BuilderField, Unset},
+    Error, Inspector, Packed,
+};
 use std::convert::TryInto;
 
 impl Packed for i8 {
     const SIZE: usize = 1;
+    type Bytes = [u8; 1];
+
     #[inline]
     fn unchecked_read_from_slice(slice: &[u8]) -> Self {
         slice[0] as i8
@@ -19,9 +24,19 @@ impl Packed for i8 {
         // by the [`View::try_from_slice`]
         Ok(())
     }
+
+    #[inline]
+    fn inspect(slice: &[u8], inspector: &mut Inspector) {
+        inspector.value(Self::unchecked_read_from_slice(slice))
+    }
+}
+impl BuilderField for i8 {
+    type Initial = Unset;
 }
 impl Packed for u8 {
     const SIZE: usize = 1;
+    type Bytes = [u8; 1];
+
     #[inline]
     fn unchecked_read_from_slice(slice: &[u8]) -> Self {
         slice[0]
@@ -38,12 +53,22 @@ impl Packed for u8 {
         // by the [`View::try_from_slice`]
         Ok(())
     }
+
+    #[inline]
+    fn inspect(slice: &[u8], inspector: &mut Inspector) {
+        inspector.value(Self::unchecked_read_from_slice(slice))
+    }
+}
+
+impl BuilderField for u8 {
+    type Initial = Unset;
 }
 
 macro_rules! primitive_pack {
     ($t:ty) => {
         impl Packed for $t {
             const SIZE: usize = ::std::mem::size_of::<$t>();
+            type Bytes = [u8; ::std::mem::size_of::<$t>()];
 
             #[inline]
             fn check(_slice: &[u8]) -> Result<(), Error> {
@@ -52,6 +77,11 @@ macro_rules! primitive_pack {
                 Ok(())
             }
 
+            #[inline]
+            fn inspect(slice: &[u8], inspector: &mut Inspector) {
+                inspector.value(Self::unchecked_read_from_slice(slice))
+            }
+
             #[inline]
             fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
                 slice.copy_from_slice(&self.to_le_bytes())
@@ -82,6 +112,10 @@ macro_rules! primitive_pack {
                 }
             }
         }
+
+        impl BuilderField for $t {
+            type Initial = Unset;
+        }
     };
 }
 
@@ -96,6 +130,27 @@ primitive_pack!(i64);
 primitive_pack!(i128);
 primitive_pack!(isize);
 
+/// every bit pattern is a valid integer
+#[cfg(feature = "arbitrary")]
+macro_rules! primitive_arbitrary {
+    ($($t:ty),+) => {
+        $(
+        impl crate::ArbitraryPacked for $t {
+            #[inline]
+            fn arbitrary_write(
+                u: &mut arbitrary::Unstructured<'_>,
+                slice: &mut [u8],
+            ) -> arbitrary::Result<()> {
+                u.fill_buffer(slice)
+            }
+        }
+        )+
+    };
+}
+
+#[cfg(feature = "arbitrary")]
+primitive_arbitrary!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,157 @@
+use crate::Error;
+use std::fmt;
+
+/// one step in the path leading to a field of a packed layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// a named field of a structure
+    Field(&'static str),
+    /// an entry of a tuple or of an unnamed structure
+    Index(usize),
+}
+
+/// a single validation failure found while walking a packed layout
+#[derive(Debug)]
+pub struct Issue {
+    offset: usize,
+    path: Vec<Segment>,
+    error: Error,
+}
+
+/// every validation failure found in a slice
+///
+/// Unlike [`Packed::check`], which stops at the first error, the
+/// [`Report`] keeps on walking the whole layout and record every
+/// invalid field, discriminant or magic constant with its offset.
+///
+/// see [`View::validate_all`].
+///
+/// [`Packed::check`]: crate::Packed::check
+/// [`View::validate_all`]: crate::View::validate_all
+#[derive(Debug)]
+pub struct Report {
+    ty: &'static str,
+    issues: Vec<Issue>,
+    offset: usize,
+    path: Vec<Segment>,
+}
+
+impl Issue {
+    /// offset, in bytes, of the invalid element from the start of the
+    /// validated slice
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// path of the fields leading to the invalid element
+    ///
+    /// the path is empty if the error concerns the top level type itself
+    #[inline]
+    pub fn path(&self) -> &[Segment] {
+        &self.path
+    }
+
+    /// the error returned by the failing check
+    #[inline]
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    #[inline]
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+}
+
+impl Report {
+    pub(crate) fn new<T: ?Sized>() -> Self {
+        Self {
+            ty: std::any::type_name::<T>(),
+            issues: Vec::new(),
+            offset: 0,
+            path: Vec::new(),
+        }
+    }
+
+    /// `true` if no issue was found
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// all the issues found, in the order they appear in the layout
+    #[inline]
+    pub fn issues(&self) -> &[Issue] {
+        &self.issues
+    }
+
+    #[inline]
+    pub fn into_issues(self) -> Vec<Issue> {
+        self.issues
+    }
+
+    /// record the given error at the current position in the layout
+    pub fn push(&mut self, error: Error) {
+        self.issues.push(Issue {
+            offset: self.offset,
+            path: self.path.clone(),
+            error,
+        });
+    }
+
+    /// walk into the element `segment` of the current layout. `start`
+    /// is the offset of the element relative to the current position.
+    ///
+    /// every issue pushed by `f` will be recorded with the appropriate
+    /// path and offset.
+    pub fn nested<F>(&mut self, segment: Segment, start: usize, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.offset += start;
+        self.path.push(segment);
+
+        f(self);
+
+        self.path.pop();
+        self.offset -= start;
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Field(field) => field.fmt(f),
+            Self::Index(index) => index.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at byte {}", self.offset)?;
+        if let Some((first, rest)) = self.path.split_first() {
+            write!(f, " ({}", first)?;
+            for segment in rest {
+                write!(f, ".{}", segment)?;
+            }
+            write!(f, ")")?;
+        }
+        write!(f, ": {}", self.error)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return write!(f, "{} is valid", self.ty);
+        }
+
+        write!(f, "{} issue(s) found in {}", self.issues.len(), self.ty)?;
+        for issue in self.issues.iter() {
+            write!(f, "\n  {}", issue)?;
+        }
+        Ok(())
+    }
+}
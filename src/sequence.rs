@@ -0,0 +1,250 @@
+use crate::{Error, Packed, View};
+use std::{fmt, iter::FusedIterator, marker};
+
+/// view of a slice in memory as a sequence of consecutive records of
+/// type `T`
+///
+/// all the records have been checked when the [`Sequence`] was created
+/// so accessing them is free. See [`Records`] to validate the records
+/// lazily instead.
+///
+/// ```
+/// use packtool::{Packed, Sequence};
+///
+/// #[derive(Packed)]
+/// pub struct Entry {
+///     key: u8,
+///     value: u16,
+/// }
+///
+/// const SLICE: &[u8] = &[1, 0x2a, 0, 2, 0x2b, 0];
+/// let entries = Sequence::<Entry>::try_from_slice(SLICE).unwrap();
+///
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(Entry::value(entries.get(1).unwrap()).unpack(), 0x2b);
+/// ```
+pub struct Sequence<'a, T> {
+    slice: &'a [u8],
+    marker: marker::PhantomData<fn() -> T>,
+}
+
+/// lazily checked iterator over the records of type `T` of a slice
+///
+/// every record is checked when it is reached. If the slice does not
+/// end on a record boundary the last item is an [`Error::Truncated`].
+pub struct Records<'a, T> {
+    slice: &'a [u8],
+    index: usize,
+    marker: marker::PhantomData<fn() -> T>,
+}
+
+/// iterator over the [`View`]s of a [`Sequence`]
+pub struct Iter<'a, T> {
+    slice: &'a [u8],
+    marker: marker::PhantomData<fn() -> T>,
+}
+
+/// count the number of complete records in a slice of length `len`
+/// and the number of remaining bytes
+fn split<T: Packed>(len: usize) -> (usize, usize) {
+    match len.checked_div(T::SIZE) {
+        Some(records) => (records, len % T::SIZE),
+        None => (0, len),
+    }
+}
+
+impl<'a, T> Sequence<'a, T>
+where
+    T: Packed,
+{
+    /// create the [`Sequence`] checking every one of the records
+    ///
+    /// returns [`Error::Truncated`] if the slice does not end on a
+    /// record boundary.
+    pub fn try_from_slice(slice: &'a [u8]) -> Result<Self, Error> {
+        for record in Records::<T>::new(slice) {
+            record?;
+        }
+
+        Ok(Self::unchecked_from_slice(slice))
+    }
+
+    /// create the [`Sequence`] without performing any checks
+    ///
+    /// better not use this function if you are not sure this is
+    /// actually a valid slice.
+    #[inline]
+    pub fn unchecked_from_slice(slice: &'a [u8]) -> Self {
+        Self {
+            slice,
+            marker: marker::PhantomData,
+        }
+    }
+
+    /// number of records in the sequence
+    #[inline]
+    pub fn len(&self) -> usize {
+        split::<T>(self.slice.len()).0
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// get the record at the given `index`
+    pub fn get(&self, index: usize) -> Option<View<'a, T>> {
+        if index < self.len() {
+            let start = index * T::SIZE;
+            Some(View::new(&self.slice[start..start + T::SIZE]))
+        } else {
+            None
+        }
+    }
+
+    /// iterate through the records of the sequence
+    #[inline]
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter::new(&self.slice[..self.len() * T::SIZE])
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.slice
+    }
+}
+
+impl<'a, T> Records<'a, T>
+where
+    T: Packed,
+{
+    /// iterate through the records of the slice, checking each
+    /// one of them only when it is reached.
+    #[inline]
+    pub fn new(slice: &'a [u8]) -> Self {
+        Self {
+            slice,
+            index: 0,
+            marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// the slice must be a sequence of valid records of `T`
+    #[inline]
+    pub(crate) fn new(slice: &'a [u8]) -> Self {
+        Self {
+            slice,
+            marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Packed> Iterator for Records<'a, T> {
+    type Item = Result<View<'a, T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.is_empty() {
+            return None;
+        }
+
+        if self.slice.len() < T::SIZE || T::SIZE == 0 {
+            let error = Error::truncated::<T>(self.index, self.slice.len());
+            self.slice = &[];
+            return Some(Err(error));
+        }
+
+        let (record, remaining) = self.slice.split_at(T::SIZE);
+        self.slice = remaining;
+        self.index += 1;
+        Some(View::try_from_slice(record))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (records, remaining) = split::<T>(self.slice.len());
+        let len = records + usize::from(remaining != 0);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: Packed> ExactSizeIterator for Records<'a, T> {}
+impl<'a, T: Packed> FusedIterator for Records<'a, T> {}
+
+impl<'a, T: Packed> Iterator for Iter<'a, T> {
+    type Item = View<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.is_empty() || T::SIZE == 0 {
+            return None;
+        }
+
+        let (record, remaining) = self.slice.split_at(T::SIZE);
+        self.slice = remaining;
+        Some(View::new(record))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = split::<T>(self.slice.len()).0;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: Packed> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.is_empty() || T::SIZE == 0 {
+            return None;
+        }
+
+        let (remaining, record) = self.slice.split_at(self.slice.len() - T::SIZE);
+        self.slice = remaining;
+        Some(View::new(record))
+    }
+}
+
+impl<'a, T: Packed> ExactSizeIterator for Iter<'a, T> {}
+impl<'a, T: Packed> FusedIterator for Iter<'a, T> {}
+
+impl<'a, T: Packed> IntoIterator for Sequence<'a, T> {
+    type Item = View<'a, T>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Packed> IntoIterator for &Sequence<'a, T> {
+    type Item = View<'a, T>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Clone for Sequence<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for Sequence<'a, T> {}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            slice: self.slice,
+            marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Sequence<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ty = std::any::type_name::<T>();
+        f.debug_struct(&format!("Sequence<'_, {}>", ty))
+            .field("slice", &self.slice)
+            .finish()
+    }
+}
@@ -0,0 +1,97 @@
+use std::{fmt, ops::Range, sync::Arc};
+
+/// a storage that can be narrowed down to a sub range of itself
+/// without copying the bytes.
+///
+/// This is what allows [`Packet::project`] to create owned sub packets
+/// pointing in the same allocation as the parent packet. It is
+/// implemented for [`Shared`] and for `bytes::Bytes` (with the `bytes`
+/// feature).
+///
+/// [`Packet::project`]: crate::Packet::project
+pub trait Sliceable: AsRef<[u8]> {
+    /// create a new handle to the `range` of the storage
+    ///
+    /// `range` is always within `0..self.as_ref().len()`.
+    fn slice(&self, range: Range<usize>) -> Self;
+}
+
+/// a reference counted slice of memory
+///
+/// Cloning or slicing the [`Shared`] only increments the reference
+/// counter of the underlying allocation.
+#[derive(Clone)]
+pub struct Shared {
+    buffer: Arc<[u8]>,
+    range: Range<usize>,
+}
+
+impl Shared {
+    /// the whole allocation this slice is part of
+    #[inline]
+    pub fn buffer(&self) -> &Arc<[u8]> {
+        &self.buffer
+    }
+
+    /// the range of the allocation covered by this slice
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+impl Sliceable for Shared {
+    fn slice(&self, range: Range<usize>) -> Self {
+        let start = self.range.start + range.start;
+        let end = self.range.start + range.end;
+        assert!(start <= end && end <= self.range.end);
+
+        Self {
+            buffer: Arc::clone(&self.buffer),
+            range: start..end,
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Sliceable for bytes::Bytes {
+    #[inline]
+    fn slice(&self, range: Range<usize>) -> Self {
+        bytes::Bytes::slice(self, range)
+    }
+}
+
+impl AsRef<[u8]> for Shared {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.buffer[self.range.clone()]
+    }
+}
+
+impl From<Arc<[u8]>> for Shared {
+    fn from(buffer: Arc<[u8]>) -> Self {
+        let range = 0..buffer.len();
+        Self { buffer, range }
+    }
+}
+
+impl From<Box<[u8]>> for Shared {
+    fn from(boxed: Box<[u8]>) -> Self {
+        Self::from(Arc::<[u8]>::from(boxed))
+    }
+}
+
+impl From<Vec<u8>> for Shared {
+    fn from(vec: Vec<u8>) -> Self {
+        Self::from(Arc::<[u8]>::from(vec))
+    }
+}
+
+impl fmt::Debug for Shared {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shared")
+            .field("range", &self.range)
+            .field("slice", &self.as_ref())
+            .finish()
+    }
+}
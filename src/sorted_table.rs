@@ -0,0 +1,231 @@
+use crate::{sequence::Iter, Error, Packed, PacketVec, Sequence, View};
+use std::{
+    any, fmt,
+    ops::{Bound, RangeBounds},
+};
+
+/// a read only [`Sequence`] of records sorted by a key
+///
+/// the key is extracted from the [`View`] of the records, usually with
+/// one of the accessors of `T`. Lookups are performed with a binary
+/// search so the comparisons are done on the decoded keys and not
+/// on the raw (little endian) bytes.
+///
+/// ```
+/// use packtool::{Packed, SortedTable, SortedTableBuilder};
+///
+/// #[derive(Packed)]
+/// pub struct Header {
+///     block_number: u32,
+///     hash: [u8; 4],
+/// }
+///
+/// fn block_number(header: packtool::View<'_, Header>) -> u32 {
+///     Header::block_number(header).unpack()
+/// }
+///
+/// let mut builder = SortedTableBuilder::new(block_number);
+/// for number in [256, 1, 42] {
+///     builder.push(&Header { block_number: number, hash: [0; 4] });
+/// }
+/// let records = builder.finish();
+///
+/// // the bytes can be written to disk and loaded again
+/// let table = SortedTable::try_from_slice(records.as_bytes(), block_number).unwrap();
+///
+/// assert!(table.get(&42).is_some());
+/// assert!(table.get(&43).is_none());
+/// assert_eq!(table.lower_bound(&2), 1);
+/// assert_eq!(table.range(2..300).len(), 2);
+/// ```
+pub struct SortedTable<'a, T, K> {
+    records: Sequence<'a, T>,
+    key: fn(View<'_, T>) -> K,
+}
+
+/// sort records by key in order to create a [`SortedTable`]
+pub struct SortedTableBuilder<T, K> {
+    records: PacketVec<T>,
+    key: fn(View<'_, T>) -> K,
+}
+
+impl<'a, T, K> SortedTable<'a, T, K>
+where
+    T: Packed,
+    K: Ord,
+{
+    /// check every one of the records of the slice and that they
+    /// are sorted by the given `key`.
+    pub fn try_from_slice(slice: &'a [u8], key: fn(View<'_, T>) -> K) -> Result<Self, Error> {
+        Self::try_from_sequence(Sequence::try_from_slice(slice)?, key)
+    }
+
+    /// check the records of the [`Sequence`] are sorted by the given `key`
+    pub fn try_from_sequence(
+        records: Sequence<'a, T>,
+        key: fn(View<'_, T>) -> K,
+    ) -> Result<Self, Error> {
+        let mut previous = None;
+        for (index, record) in records.iter().enumerate() {
+            let current = key(record);
+            if matches!(&previous, Some(previous) if previous > &current) {
+                return Err(Error::not_sorted::<T>(index));
+            }
+            previous = Some(current);
+        }
+
+        Ok(Self { records, key })
+    }
+
+    /// number of records in the table
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// index of the first record for which `predicate` is `false`
+    ///
+    /// the records must be partitioned by `predicate`.
+    fn partition_point<P>(&self, mut predicate: P) -> usize
+    where
+        P: FnMut(&K) -> bool,
+    {
+        let mut low = 0;
+        let mut high = self.len();
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let record = self.records.get(middle).expect("index within the table");
+            if predicate(&(self.key)(record)) {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+
+    /// index of the first record with a key greater or equal to `key`
+    #[inline]
+    pub fn lower_bound(&self, key: &K) -> usize {
+        self.partition_point(|k| k < key)
+    }
+
+    /// index of the first record with a key strictly greater than `key`
+    #[inline]
+    pub fn upper_bound(&self, key: &K) -> usize {
+        self.partition_point(|k| k <= key)
+    }
+
+    /// get the first record with the given `key`
+    pub fn get(&self, key: &K) -> Option<View<'a, T>> {
+        let record = self.records.get(self.lower_bound(key))?;
+        if &(self.key)(record) == key {
+            Some(record)
+        } else {
+            None
+        }
+    }
+
+    /// get the record at the given `index`
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<View<'a, T>> {
+        self.records.get(index)
+    }
+
+    /// all the records with a key within the given `range`
+    pub fn range<R>(&self, range: R) -> Sequence<'a, T>
+    where
+        R: RangeBounds<K>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(key) => self.lower_bound(key),
+            Bound::Excluded(key) => self.upper_bound(key),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => self.upper_bound(key),
+            Bound::Excluded(key) => self.lower_bound(key),
+            Bound::Unbounded => self.len(),
+        }
+        .max(start);
+
+        let slice = self.records.as_slice();
+        Sequence::unchecked_from_slice(&slice[start * T::SIZE..end * T::SIZE])
+    }
+
+    /// iterate through the records, in order
+    #[inline]
+    pub fn iter(&self) -> Iter<'a, T> {
+        self.records.iter()
+    }
+
+    #[inline]
+    pub fn as_sequence(&self) -> Sequence<'a, T> {
+        self.records
+    }
+}
+
+impl<T, K> SortedTableBuilder<T, K>
+where
+    T: Packed,
+    K: Ord,
+{
+    #[inline]
+    pub fn new(key: fn(View<'_, T>) -> K) -> Self {
+        Self {
+            records: PacketVec::new(),
+            key,
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, record: &T) {
+        self.records.push(record)
+    }
+
+    #[inline]
+    pub fn push_view(&mut self, record: View<'_, T>) {
+        self.records.push_view(record)
+    }
+
+    /// sort the records by key, the returned records can be
+    /// loaded with [`SortedTable::try_from_slice`].
+    pub fn finish(self) -> PacketVec<T> {
+        let Self { mut records, key } = self;
+        records.sort_by_key(key);
+        records
+    }
+}
+
+impl<'a, T, K> Clone for SortedTable<'a, T, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T, K> Copy for SortedTable<'a, T, K> {}
+
+impl<'a, T, K> fmt::Debug for SortedTable<'a, T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ty = any::type_name::<T>();
+        let key = any::type_name::<K>();
+        f.debug_struct(&format!("SortedTable<'_, {}, {}>", ty, key))
+            .field("records", &self.records)
+            .finish()
+    }
+}
+
+impl<T, K> fmt::Debug for SortedTableBuilder<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ty = any::type_name::<T>();
+        let key = any::type_name::<K>();
+        f.debug_struct(&format!("SortedTableBuilder<{}, {}>", ty, key))
+            .field("records", &self.records)
+            .finish()
+    }
+}
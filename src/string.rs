@@ -0,0 +1,717 @@
+/*!
+fixed capacity strings
+
+a string field of a packed structure takes a fixed number of bytes: the
+encoded string followed by some padding. [`FixedStr<N, E, P>`] is a
+packed string of `N` bytes with the encoding `E` and the padding `P`:
+
+* encodings: [`Ascii`], [`Utf8`] (the default), [`Utf8Lossy`],
+  [`Utf16Le`] and [`Utf16Be`];
+* paddings: [`NulPadded`] (the default) where every byte after the
+  string is `0`, [`SpacePadded`] where the string is followed by
+  spaces and [`NulTerminated`] where the string ends at the first
+  `NUL` and anything may follow.
+
+[`CStr<N>`] is a NUL terminated string and [`Utf16Str<N>`] a UTF-16
+string, `N` is always the size in bytes.
+
+[`Packed::check`] validates the encoding and the padding, so the
+[`View`] of a string in `Ascii` or `Utf8` borrows it without any
+allocation or copy:
+
+```
+use packtool::{CStr, FixedStr, Packed, View};
+use packtool::string::{Ascii, SpacePadded};
+
+#[derive(Packed)]
+pub struct Entry {
+    name: CStr<8>,
+    kind: FixedStr<4, Ascii, SpacePadded>,
+}
+
+let bytes = b"file\0\xFF\xFF\xFFTXT ";
+let entry = View::<Entry>::try_from_slice(bytes).unwrap();
+
+assert_eq!(Entry::name(entry).as_str(), "file");
+assert_eq!(Entry::kind(entry).as_str(), "TXT");
+
+// only ascii strings, without NUL characters
+assert!(View::<Entry>::try_from_slice(b"file\0\0\0\0T\xC3\xA9 ").is_err());
+assert!(View::<Entry>::try_from_slice(b"file\0\0\0\0T\0T ").is_err());
+```
+
+the strings are created with a checked constructor:
+
+```
+use packtool::{FixedStr, Utf16Str, Packed};
+
+let name = FixedStr::<8>::new("packtool").unwrap();
+assert_eq!(name.as_str(), "packtool");
+assert!(FixedStr::<8>::new("packtool!").is_err());
+
+let name = Utf16Str::<8>::new("été").unwrap();
+assert_eq!(name.to_string(), "été");
+assert_eq!(name.as_bytes(), &[0xE9, 0, 0x74, 0, 0xE9, 0, 0, 0]);
+```
+
+[`View`]: crate::View
+*/
+
+use crate::{
+    builder::{BuilderField, Unset},
+    Error, Inspector, Packed, View,
+};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+/// a NUL terminated string of `N` bytes
+///
+/// the string is followed by a `NUL` unless it takes all the `N`
+/// bytes, the bytes after the `NUL` are ignored.
+pub type CStr<const N: usize, E = Utf8> = FixedStr<N, E, NulTerminated>;
+
+/// a UTF-16 string of `N` bytes (`N / 2` code units)
+pub type Utf16Str<const N: usize, E = Utf16Le, P = NulPadded> = FixedStr<N, E, P>;
+
+/// the encoding of the characters of a [`FixedStr`]
+pub trait Encoding {
+    /// the size in bytes of a code unit
+    const UNIT: usize;
+
+    /// the encoding of the space character, used by [`SpacePadded`]
+    const SPACE: &'static [u8];
+
+    /// the number of bytes needed to encode the `string`, `None` if
+    /// some characters cannot be encoded
+    fn encoded_len(string: &str) -> Option<usize>;
+
+    /// encode the `string` in `bytes`, `bytes` is exactly
+    /// [`Encoding::encoded_len`] long
+    fn encode(string: &str, bytes: &mut [u8]);
+
+    /// check the `bytes` are a valid encoded string
+    fn validate(bytes: &[u8]) -> Result<(), Cow<'static, str>>;
+
+    /// decode the validated `bytes`
+    fn decode(bytes: &[u8]) -> Cow<'_, str>;
+}
+
+/// the encodings that are valid UTF-8: the string can be borrowed
+/// from the bytes
+pub trait StrEncoding: Encoding {}
+
+/// how the bytes after the string are filled in a [`FixedStr`]
+pub trait Padding {
+    /// the length in bytes of the string at the start of `bytes`,
+    /// without the padding
+    fn content_len<E: Encoding>(bytes: &[u8]) -> usize;
+
+    /// check the bytes after the string
+    fn check_padding<E: Encoding>(padding: &[u8]) -> Result<(), Cow<'static, str>>;
+
+    /// fill the bytes after the string
+    fn pad<E: Encoding>(padding: &mut [u8]);
+
+    /// check a string can be stored with this padding and read back
+    fn validate(string: &str) -> Result<(), Cow<'static, str>> {
+        if string.contains('\0') {
+            Err(Cow::Borrowed("the string contains a NUL character"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// ASCII strings, every byte is lower than `0x80`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Ascii;
+
+/// UTF-8 strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Utf8;
+
+/// UTF-8 strings that are not checked: any byte is accepted and the
+/// invalid sequences are replaced by `U+FFFD` when decoding
+///
+/// for the formats storing the names as raw bytes (like the tar
+/// archives), the exact bytes are still available with `content`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Utf8Lossy;
+
+/// UTF-16 strings, little endian code units
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Utf16Le;
+
+/// UTF-16 strings, big endian code units
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Utf16Be;
+
+/// the string is followed by `NUL` code units only
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct NulPadded;
+
+/// the string is followed by spaces, the trailing spaces are not part
+/// of the string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SpacePadded;
+
+/// the string ends at the first `NUL` code unit, or at the end of the
+/// bytes. What follows the `NUL` is not checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct NulTerminated;
+
+impl Encoding for Ascii {
+    const UNIT: usize = 1;
+    const SPACE: &'static [u8] = b" ";
+
+    fn encoded_len(string: &str) -> Option<usize> {
+        if string.is_ascii() {
+            Some(string.len())
+        } else {
+            None
+        }
+    }
+
+    fn encode(string: &str, bytes: &mut [u8]) {
+        bytes.copy_from_slice(string.as_bytes())
+    }
+
+    fn validate(bytes: &[u8]) -> Result<(), Cow<'static, str>> {
+        match bytes.iter().position(|byte| !byte.is_ascii()) {
+            None => Ok(()),
+            Some(index) => Err(Cow::Owned(format!(
+                "invalid ascii character 0x{:02x} at byte {}",
+                bytes[index], index
+            ))),
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Cow<'_, str> {
+        Utf8::decode(bytes)
+    }
+}
+impl StrEncoding for Ascii {}
+
+impl Encoding for Utf8 {
+    const UNIT: usize = 1;
+    const SPACE: &'static [u8] = b" ";
+
+    fn encoded_len(string: &str) -> Option<usize> {
+        Some(string.len())
+    }
+
+    fn encode(string: &str, bytes: &mut [u8]) {
+        bytes.copy_from_slice(string.as_bytes())
+    }
+
+    fn validate(bytes: &[u8]) -> Result<(), Cow<'static, str>> {
+        std::str::from_utf8(bytes)
+            .map(|_| ())
+            .map_err(|error| Cow::Owned(error.to_string()))
+    }
+
+    fn decode(bytes: &[u8]) -> Cow<'_, str> {
+        String::from_utf8_lossy(bytes)
+    }
+}
+impl StrEncoding for Utf8 {}
+
+impl Encoding for Utf8Lossy {
+    const UNIT: usize = 1;
+    const SPACE: &'static [u8] = b" ";
+
+    fn encoded_len(string: &str) -> Option<usize> {
+        Some(string.len())
+    }
+
+    fn encode(string: &str, bytes: &mut [u8]) {
+        bytes.copy_from_slice(string.as_bytes())
+    }
+
+    fn validate(_bytes: &[u8]) -> Result<(), Cow<'static, str>> {
+        Ok(())
+    }
+
+    fn decode(bytes: &[u8]) -> Cow<'_, str> {
+        String::from_utf8_lossy(bytes)
+    }
+}
+
+macro_rules! utf16 {
+    ($Encoding:ty, $to_bytes:ident, $from_bytes:ident, $space:expr) => {
+        impl $Encoding {
+            fn units(bytes: &[u8]) -> impl Iterator<Item = u16> + '_ {
+                bytes
+                    .chunks_exact(2)
+                    .map(|unit| u16::$from_bytes([unit[0], unit[1]]))
+            }
+        }
+
+        impl Encoding for $Encoding {
+            const UNIT: usize = 2;
+            const SPACE: &'static [u8] = $space;
+
+            fn encoded_len(string: &str) -> Option<usize> {
+                Some(string.encode_utf16().count() * 2)
+            }
+
+            fn encode(string: &str, bytes: &mut [u8]) {
+                for (unit, bytes) in string.encode_utf16().zip(bytes.chunks_exact_mut(2)) {
+                    bytes.copy_from_slice(&unit.$to_bytes());
+                }
+            }
+
+            fn validate(bytes: &[u8]) -> Result<(), Cow<'static, str>> {
+                match std::char::decode_utf16(Self::units(bytes)).find_map(Result::err) {
+                    None => Ok(()),
+                    Some(error) => Err(Cow::Owned(format!(
+                        "invalid UTF-16 unpaired surrogate 0x{:04x}",
+                        error.unpaired_surrogate()
+                    ))),
+                }
+            }
+
+            fn decode(bytes: &[u8]) -> Cow<'_, str> {
+                Cow::Owned(
+                    std::char::decode_utf16(Self::units(bytes))
+                        .map(|c| c.unwrap_or(std::char::REPLACEMENT_CHARACTER))
+                        .collect(),
+                )
+            }
+        }
+    };
+}
+
+utf16!(Utf16Le, to_le_bytes, from_le_bytes, &[b' ', 0]);
+utf16!(Utf16Be, to_be_bytes, from_be_bytes, &[0, b' ']);
+
+/// the length in bytes before the first `NUL` code unit
+fn until_nul<E: Encoding>(bytes: &[u8]) -> usize {
+    bytes
+        .chunks(E::UNIT)
+        .position(|unit| unit.iter().all(|byte| *byte == 0))
+        .map(|units| units * E::UNIT)
+        .unwrap_or(bytes.len())
+}
+
+impl Padding for NulPadded {
+    fn content_len<E: Encoding>(bytes: &[u8]) -> usize {
+        until_nul::<E>(bytes)
+    }
+
+    fn check_padding<E: Encoding>(padding: &[u8]) -> Result<(), Cow<'static, str>> {
+        match padding.iter().position(|byte| *byte != 0) {
+            None => Ok(()),
+            Some(index) => Err(Cow::Owned(format!(
+                "expecting NUL padding but found 0x{:02x}",
+                padding[index]
+            ))),
+        }
+    }
+
+    fn pad<E: Encoding>(padding: &mut [u8]) {
+        padding.iter_mut().for_each(|byte| *byte = 0)
+    }
+}
+
+impl Padding for SpacePadded {
+    fn content_len<E: Encoding>(bytes: &[u8]) -> usize {
+        let spaces = bytes
+            .chunks(E::UNIT)
+            .rev()
+            .take_while(|unit| *unit == E::SPACE)
+            .count();
+        bytes.len() - spaces * E::UNIT
+    }
+
+    fn check_padding<E: Encoding>(_padding: &[u8]) -> Result<(), Cow<'static, str>> {
+        // the padding is every trailing space
+        Ok(())
+    }
+
+    fn pad<E: Encoding>(padding: &mut [u8]) {
+        padding
+            .chunks_mut(E::UNIT)
+            .for_each(|unit| unit.copy_from_slice(E::SPACE))
+    }
+
+    fn validate(string: &str) -> Result<(), Cow<'static, str>> {
+        if string.ends_with(' ') {
+            Err(Cow::Borrowed(
+                "the string ends with a space, it would be read as padding",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Padding for NulTerminated {
+    fn content_len<E: Encoding>(bytes: &[u8]) -> usize {
+        until_nul::<E>(bytes)
+    }
+
+    fn check_padding<E: Encoding>(_padding: &[u8]) -> Result<(), Cow<'static, str>> {
+        // anything is allowed after the terminator
+        Ok(())
+    }
+
+    fn pad<E: Encoding>(padding: &mut [u8]) {
+        padding.iter_mut().for_each(|byte| *byte = 0)
+    }
+}
+
+/// a string of `N` bytes with the encoding `E` and the padding `P`,
+/// see the [module documentation](self)
+pub struct FixedStr<const N: usize, E = Utf8, P = NulPadded> {
+    bytes: [u8; N],
+    _marker: PhantomData<fn() -> (E, P)>,
+}
+
+impl<const N: usize, E: Encoding, P: Padding> FixedStr<N, E, P> {
+    /// encode the `string`, fails if it does not fit in `N` bytes or
+    /// cannot be encoded with `E`
+    pub fn new(string: &str) -> Result<Self, Error> {
+        let len = E::encoded_len(string)
+            .ok_or_else(|| Error::invalid_string::<Self>("the string cannot be encoded"))?;
+        if len > N {
+            return Err(Error::string_too_long::<Self>(len, N));
+        }
+        P::validate(string).map_err(Error::invalid_string::<Self>)?;
+
+        let mut bytes = [0; N];
+        E::encode(string, &mut bytes[..len]);
+        P::pad::<E>(&mut bytes[len..]);
+
+        Ok(Self {
+            bytes,
+            _marker: PhantomData,
+        })
+    }
+
+    /// the encoded string, without the padding
+    pub fn content(&self) -> &[u8] {
+        &self.bytes[..P::content_len::<E>(&self.bytes)]
+    }
+
+    /// the `N` bytes, padding included
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.bytes
+    }
+
+    /// decode the string
+    pub fn decode(&self) -> Cow<'_, str> {
+        E::decode(self.content())
+    }
+}
+
+impl<const N: usize, E: StrEncoding, P: Padding> FixedStr<N, E, P> {
+    /// the string, borrowed from the bytes
+    pub fn as_str(&self) -> &str {
+        // the encoding was checked when reading or creating the string
+        std::str::from_utf8(self.content()).unwrap_or_default()
+    }
+}
+
+impl<'a, const N: usize, E: Encoding, P: Padding> View<'a, FixedStr<N, E, P>> {
+    /// the encoded string, without the padding
+    pub fn content(self) -> &'a [u8] {
+        let bytes = self.as_slice();
+        &bytes[..P::content_len::<E>(bytes)]
+    }
+
+    /// decode the string, only allocates for the encodings that are
+    /// not UTF-8 compatible
+    pub fn decode(self) -> Cow<'a, str> {
+        E::decode(self.content())
+    }
+}
+
+impl<'a, const N: usize, E: StrEncoding, P: Padding> View<'a, FixedStr<N, E, P>> {
+    /// the string, borrowed from the viewed slice
+    pub fn as_str(self) -> &'a str {
+        // the encoding was checked when creating the view
+        std::str::from_utf8(self.content()).unwrap_or_default()
+    }
+}
+
+impl<const N: usize, E: Encoding, P: Padding> Packed for FixedStr<N, E, P> {
+    const SIZE: usize = {
+        assert!(
+            N.is_multiple_of(E::UNIT),
+            "the size of the string is not a multiple of the code unit"
+        );
+        N
+    };
+    type Bytes = [u8; N];
+
+    #[inline]
+    fn unchecked_read_from_slice(slice: &[u8]) -> Self {
+        let mut bytes = <[u8; N]>::unchecked_read_from_slice(slice);
+        // ignore what follows the end of a NUL terminated string
+        let len = P::content_len::<E>(&bytes);
+        P::pad::<E>(&mut bytes[len..]);
+
+        Self {
+            bytes,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
+        slice.copy_from_slice(&self.bytes)
+    }
+
+    fn check(slice: &[u8]) -> Result<(), Error> {
+        let len = P::content_len::<E>(slice);
+        if until_nul::<E>(&slice[..len]) != len {
+            return Err(Error::invalid_string::<Self>(
+                "the string contains a NUL character",
+            ));
+        }
+        E::validate(&slice[..len]).map_err(Error::invalid_string::<Self>)?;
+        P::check_padding::<E>(&slice[len..]).map_err(Error::invalid_string::<Self>)
+    }
+
+    fn inspect(slice: &[u8], inspector: &mut Inspector) {
+        match Self::check(slice) {
+            Ok(()) => {
+                let len = P::content_len::<E>(slice);
+                inspector.value(format_args!("{:?}", E::decode(&slice[..len])))
+            }
+            Err(error) => inspector.error(error),
+        }
+    }
+}
+
+impl<const N: usize, E: Encoding, P: Padding> BuilderField for FixedStr<N, E, P> {
+    type Initial = Unset;
+}
+
+/// the characters of the generated strings: they are valid in every
+/// encoding and with every padding
+#[cfg(any(feature = "arbitrary", feature = "quickcheck", feature = "proptest"))]
+const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+#[cfg(any(feature = "arbitrary", feature = "quickcheck", feature = "proptest"))]
+impl<const N: usize, E: Encoding, P: Padding> FixedStr<N, E, P> {
+    /// the maximum number of characters of a generated string
+    const CAPACITY: usize = N / E::UNIT;
+
+    fn generate(characters: impl IntoIterator<Item = u8>) -> Self {
+        let string = characters
+            .into_iter()
+            .map(char::from)
+            .take(Self::CAPACITY)
+            .collect::<String>();
+        // the characters of the alphabet always fit
+        Self::new(&string).unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, const N: usize, E: Encoding, P: Padding> arbitrary::Arbitrary<'a> for FixedStr<N, E, P> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.int_in_range(0..=Self::CAPACITY)?;
+        let characters = (0..len)
+            .map(|_| u.choose(ALPHABET).copied())
+            .collect::<arbitrary::Result<Vec<u8>>>()?;
+        Ok(Self::generate(characters))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<const N: usize, E: Encoding, P: Padding> crate::ArbitraryPacked for FixedStr<N, E, P> {
+    fn arbitrary_write(
+        u: &mut arbitrary::Unstructured<'_>,
+        slice: &mut [u8],
+    ) -> arbitrary::Result<()> {
+        let string: Self = arbitrary::Arbitrary::arbitrary(u)?;
+        string.unchecked_write_to_slice(slice);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<const N: usize, E, P> quickcheck::Arbitrary for FixedStr<N, E, P>
+where
+    E: Encoding + 'static,
+    P: Padding + 'static,
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let len = usize::arbitrary(g) % (Self::CAPACITY + 1);
+        Self::generate((0..len).map(|_| *g.choose(ALPHABET).unwrap_or(&b'a')))
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl<const N: usize, E, P> proptest::arbitrary::Arbitrary for FixedStr<N, E, P>
+where
+    E: Encoding + 'static,
+    P: Padding + 'static,
+{
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<
+        proptest::collection::VecStrategy<proptest::sample::Select<u8>>,
+        fn(Vec<u8>) -> Self,
+    >;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy as _;
+
+        proptest::collection::vec(proptest::sample::select(ALPHABET), 0..=Self::CAPACITY)
+            .prop_map(Self::generate)
+    }
+}
+
+impl<const N: usize, E, P> Clone for FixedStr<N, E, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const N: usize, E, P> Copy for FixedStr<N, E, P> {}
+
+impl<const N: usize, E, P> PartialEq for FixedStr<N, E, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl<const N: usize, E, P> Eq for FixedStr<N, E, P> {}
+
+impl<const N: usize, E, P> PartialOrd for FixedStr<N, E, P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize, E, P> Ord for FixedStr<N, E, P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes.cmp(&other.bytes)
+    }
+}
+
+impl<const N: usize, E, P> Hash for FixedStr<N, E, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state)
+    }
+}
+
+impl<const N: usize, E: Encoding, P: Padding> Default for FixedStr<N, E, P> {
+    fn default() -> Self {
+        let mut bytes = [0; N];
+        P::pad::<E>(&mut bytes);
+
+        Self {
+            bytes,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<const N: usize, E: Encoding, P: Padding> fmt::Debug for FixedStr<N, E, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.decode(), f)
+    }
+}
+
+impl<const N: usize, E: Encoding, P: Padding> fmt::Display for FixedStr<N, E, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.decode())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view<T: Packed>(bytes: &[u8]) -> Result<T, Error> {
+        View::<T>::try_from_slice(bytes).map(View::unpack)
+    }
+
+    #[quickcheck]
+    fn utf8_round_trip(string: String) -> bool {
+        match FixedStr::<32>::new(&string) {
+            Ok(fixed) => view::<FixedStr<32>>(fixed.as_bytes()).unwrap().as_str() == string,
+            Err(_) => string.len() > 32 || string.contains('\0'),
+        }
+    }
+
+    #[quickcheck]
+    fn utf16_round_trip(string: String) -> bool {
+        let be = Utf16Str::<64, Utf16Be>::new(&string);
+        let le = Utf16Str::<64>::new(&string);
+        match (le, be) {
+            (Ok(le), Ok(be)) => {
+                view::<Utf16Str<64>>(le.as_bytes()).unwrap().to_string() == string
+                    && view::<Utf16Str<64, Utf16Be>>(be.as_bytes())
+                        .unwrap()
+                        .to_string()
+                        == string
+            }
+            (Err(_), Err(_)) => string.encode_utf16().count() > 32 || string.contains('\0'),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn nul_padded() {
+        assert_eq!(view::<FixedStr<4>>(b"ab\0\0").unwrap().as_str(), "ab");
+        assert_eq!(view::<FixedStr<4>>(b"abcd").unwrap().as_str(), "abcd");
+        assert!(view::<FixedStr<4>>(b"ab\0c").is_err());
+        assert!(view::<FixedStr<4>>(b"a\xFF\0\0").is_err());
+    }
+
+    #[test]
+    fn nul_terminated() {
+        let string = view::<CStr<4>>(b"ab\0c").unwrap();
+        assert_eq!(string.as_str(), "ab");
+        assert_eq!(string.as_bytes(), b"ab\0\0");
+        assert_eq!(view::<CStr<4>>(b"\0\xFF\xFF\xFF").unwrap().as_str(), "");
+        assert!(view::<CStr<4>>(b"\xFF\0\0\0").is_err());
+    }
+
+    #[test]
+    fn space_padded() {
+        type Name = FixedStr<4, Ascii, SpacePadded>;
+
+        assert_eq!(view::<Name>(b"a b ").unwrap().as_str(), "a b");
+        assert_eq!(view::<Name>(b"    ").unwrap().as_str(), "");
+        assert!(view::<Name>(b"\xC3\xA9  ").is_err());
+        assert!(view::<Name>(b"a\0b ").is_err());
+        assert_eq!(Name::new("ab").unwrap().as_bytes(), b"ab  ");
+        assert!(Name::new("ab ").is_err());
+        assert!(Name::new("é").is_err());
+    }
+
+    #[test]
+    fn utf16() {
+        let string = Utf16Str::<6, Utf16Be, SpacePadded>::new("a").unwrap();
+        assert_eq!(string.as_bytes(), &[0, b'a', 0, b' ', 0, b' ']);
+
+        // unpaired surrogate
+        assert!(view::<Utf16Str<4>>(&[0x00, 0xD8, 0, 0]).is_err());
+        // emoji: surrogate pair
+        assert_eq!(
+            view::<Utf16Str<4>>(&[0x3D, 0xD8, 0x00, 0xDE])
+                .unwrap()
+                .to_string(),
+            "😀"
+        );
+    }
+
+    #[test]
+    fn too_long() {
+        assert_eq!(
+            CStr::<4>::new("abcde").unwrap_err().to_string(),
+            "String too long for packtool::string::FixedStr<4, packtool::string::Utf8, packtool::string::NulTerminated>: 5 bytes but the capacity is 4 bytes"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+/*!
+property testing helpers for the [`Packed`] types.
+
+Available with the `quickcheck` or `proptest` features. With these
+features, the `#[packed(quickcheck)]` and `#[packed(proptest)]` attributes
+make `#[derive(Packed)]` implement the `Arbitrary` traits of the given
+crates. The generated values are always valid packed values: only
+the declared enum variants are generated (and shrunk toward) and unit
+types always hold their `value`.
+
+The fields of the type need to implement the `Arbitrary` traits too.
+`quickcheck` also requires the type to implement `Clone` and `proptest`
+requires `Debug`.
+
+```
+# #[cfg(feature = "quickcheck")] {
+use packtool::Packed;
+
+#[derive(Packed, Debug, Clone, PartialEq)]
+#[packed(quickcheck)]
+pub struct BlockNumber(u32, u32);
+
+packtool::testing::roundtrip::<BlockNumber>();
+# }
+```
+*/
+
+use crate::{Packed, Packet, View};
+use std::fmt;
+
+/// pack the given value, check the packed bytes are valid and that
+/// unpacking them returns the same value.
+///
+/// returns a description of the failure if any.
+pub fn check_roundtrip<T>(value: &T) -> Result<(), String>
+where
+    T: Packed + PartialEq + fmt::Debug,
+{
+    let packet = Packet::pack(value);
+
+    let report = View::<T>::validate_all(packet.as_ref());
+    if !report.is_valid() {
+        return Err(format!(
+            "{:?} was packed into invalid bytes: {}",
+            value, report
+        ));
+    }
+
+    let unpacked = packet.unpack();
+    if &unpacked != value {
+        return Err(format!("{:?} was unpacked into {:?}", value, unpacked));
+    }
+
+    Ok(())
+}
+
+/// run the [`check_roundtrip`] property with [`quickcheck`] over
+/// randomly generated values of type `T`.
+///
+/// # Panics
+///
+/// panics with the (shrunk) failing value if the property does not hold
+#[cfg(feature = "quickcheck")]
+pub fn roundtrip<T>()
+where
+    T: Packed + quickcheck::Arbitrary + PartialEq + fmt::Debug,
+{
+    fn property<T>(value: T) -> quickcheck::TestResult
+    where
+        T: Packed + PartialEq + fmt::Debug,
+    {
+        match check_roundtrip(&value) {
+            Ok(()) => quickcheck::TestResult::passed(),
+            Err(error) => quickcheck::TestResult::error(error),
+        }
+    }
+
+    quickcheck::QuickCheck::new().quickcheck(property::<T> as fn(T) -> quickcheck::TestResult)
+}
+
+/// run the [`check_roundtrip`] property with [`proptest`] over
+/// randomly generated values of type `T`.
+///
+/// # Panics
+///
+/// panics with the (shrunk) failing value if the property does not hold
+#[cfg(feature = "proptest")]
+pub fn roundtrip_proptest<T>()
+where
+    T: Packed + proptest::arbitrary::Arbitrary + PartialEq,
+{
+    use proptest::test_runner::{TestCaseError, TestRunner};
+
+    let mut runner = TestRunner::default();
+    let result = runner.run(&proptest::arbitrary::any::<T>(), |value| {
+        check_roundtrip(&value).map_err(TestCaseError::fail)
+    });
+
+    if let Err(error) = result {
+        panic!("{}\n{}", error, runner);
+    }
+}
@@ -3,13 +3,16 @@ Module only to define the default packed implementation for all
 the kind of tuples we will want to support
 */
 
-use crate::{Error, Packed};
+use crate::{
+    builder::{BuilderField, Unset},
+    Error, Inspector, Ordered, OrderedPacked, Packed, Report, Segment,
+};
 
 macro_rules! range {
     ($($Pred:ident)* , $Type:ident) => {
-        ( $(<$Pred as Packed>::SIZE + )* 0)
+        $(<$Pred as Packed>::SIZE + )* 0
         ..
-        ( $(<$Pred as Packed>::SIZE + )* <$Type as Packed>::SIZE)
+        $(<$Pred as Packed>::SIZE + )* <$Type as Packed>::SIZE
     };
 }
 
@@ -25,6 +28,7 @@ macro_rules! tuple_impls {
             const SIZE: usize = 0 $(
                 + <$T as Packed>::SIZE
             )+;
+            type Bytes = Box<[u8]>;
 
             fn check(slice: &[u8]) -> Result<(), Error> {
                 $(
@@ -38,6 +42,24 @@ macro_rules! tuple_impls {
                 Ok(())
             }
 
+            fn check_all(slice: &[u8], report: &mut Report) {
+                $(
+                    let range = range!($($Pred)* , $T);
+                    report.nested(Segment::Index($idx), range.start, |report| {
+                        <$T as Packed>::check_all(&slice[range], report)
+                    });
+                )+
+            }
+
+            fn inspect(slice: &[u8], inspector: &mut Inspector) {
+                $(
+                    let range = range!($($Pred)* , $T);
+                    inspector.nested(Segment::Index($idx), range.start, <$T as Packed>::SIZE, |inspector| {
+                        <$T as Packed>::inspect(&slice[range], inspector)
+                    });
+                )+
+            }
+
             #[inline]
             fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
                 $(
@@ -62,7 +84,62 @@ macro_rules! tuple_impls {
                     )+
                 )
             }
-        })+
+        }
+
+        impl<$($T:Packed),+> BuilderField for ($($T,)+) {
+            type Initial = Unset;
+        }
+
+        #[cfg(feature = "arbitrary")]
+        impl<$($T:crate::ArbitraryPacked),+> crate::ArbitraryPacked for ($($T,)+) {
+            fn arbitrary_write(
+                u: &mut arbitrary::Unstructured<'_>,
+                slice: &mut [u8],
+            ) -> arbitrary::Result<()> {
+                $(
+                    <$T as crate::ArbitraryPacked>::arbitrary_write(
+                        u,
+                        &mut slice[range!($($Pred)* , $T)]
+                    )?;
+                )+
+
+                Ok(())
+            }
+        }
+
+        impl<$($T:OrderedPacked),+> OrderedPacked for ($($T,)+) {
+            type Ordered = Ordered<Self>;
+
+            fn unchecked_read_ordered(slice: &[u8]) -> Self {
+                (
+                    $(
+                        <$T as OrderedPacked>::unchecked_read_ordered(
+                            &slice[range!($($Pred)* , $T)]
+                        ),
+                    )+
+                )
+            }
+
+            fn unchecked_write_ordered(&self, slice: &mut [u8]) {
+                $(
+                    <$T as OrderedPacked>::unchecked_write_ordered(
+                        &self.$idx,
+                        &mut slice[range!($($Pred)* , $T)]
+                    );
+                )+
+            }
+
+            fn check_ordered(slice: &[u8]) -> Result<(), Error> {
+                $(
+                    <$T as OrderedPacked>::check_ordered(
+                        &slice[range!($($Pred)* , $T)]
+                    )?;
+                )+
+
+                Ok(())
+            }
+        }
+        )+
     };
 }
 
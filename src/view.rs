@@ -1,5 +1,5 @@
-use crate::{Error, Packed, Packet};
-use std::{any, fmt, hash, marker};
+use crate::{compatible::SameSize, Compatible, Error, Inspector, Node, Packed, Packet, Report};
+use std::{any, fmt, hash, marker, ops::Range};
 
 /// view of a slice in memory as a packed structure of type `T`
 pub struct View<'a, T> {
@@ -67,18 +67,153 @@ where
         Ok(View::new(slice))
     }
 
+    /// walk the whole layout of `T` over the given slice and collect
+    /// every invalid field, discriminant or constant value with its
+    /// offset.
+    ///
+    /// Where [`View::try_from_slice`] stops at the first error, this
+    /// function is meant for diagnostic tools.
+    pub fn validate_all(slice: &[u8]) -> Report {
+        let mut report = Report::new::<T>();
+
+        if T::SIZE != slice.len() {
+            report.push(Error::invalid_size::<T>(slice.len(), T::SIZE));
+        }
+
+        if let Some(slice) = slice.get(..T::SIZE) {
+            T::check_all(slice, &mut report);
+        }
+
+        report
+    }
+
+    /// decode the whole layout of `T` over the given slice, field by
+    /// field, with the offset, the decoded value and the error of each
+    /// of them.
+    ///
+    /// Unlike [`View::validate_all`] the invalid fields do not stop the
+    /// walk and the valid ones are decoded too, this is what the
+    /// `packtool` command line inspector prints.
+    ///
+    /// ```
+    /// use packtool::{Packed, Segment, View};
+    ///
+    /// #[derive(Packed)]
+    /// #[repr(u8)]
+    /// pub enum Kind {
+    ///     File = 1,
+    ///     Directory = 2,
+    /// }
+    ///
+    /// #[derive(Packed)]
+    /// pub struct Entry {
+    ///     kind: Kind,
+    ///     length: u16,
+    /// }
+    ///
+    /// let node = View::<Entry>::inspect(&[3, 0x2a, 0x00]);
+    /// assert!(!node.is_valid());
+    ///
+    /// let [kind, length] = [&node.children()[0], &node.children()[1]];
+    /// assert_eq!(kind.segment(), Some(Segment::Field("kind")));
+    /// assert!(kind.error().is_some());
+    /// assert_eq!(length.offset(), 1);
+    /// assert_eq!(length.value(), Some("42"));
+    /// ```
+    pub fn inspect(slice: &[u8]) -> Node {
+        let mut inspector = Inspector::new(T::SIZE);
+
+        match slice.get(..T::SIZE) {
+            Some(prefix) if prefix.len() == slice.len() => T::inspect(slice, &mut inspector),
+            Some(prefix) => {
+                inspector.error(Error::invalid_size::<T>(slice.len(), T::SIZE));
+                T::inspect(prefix, &mut inspector)
+            }
+            None => inspector.error(Error::invalid_size::<T>(slice.len(), T::SIZE)),
+        }
+
+        inspector.finish()
+    }
+
+    /// reinterpret the view as a view of `U`, checking the bytes
+    /// against the layout of `U`.
+    ///
+    /// `U` must have the same size as `T`, this is verified at compile
+    /// time. See [`View::cast`] to skip the checks for the types known
+    /// to be compatible.
+    ///
+    /// ```
+    /// use packtool::{Packed, Packet};
+    ///
+    /// #[derive(Packed, Debug, PartialEq, Eq)]
+    /// #[repr(u8)]
+    /// pub enum Kind {
+    ///     A = 1,
+    ///     B = 2,
+    /// }
+    ///
+    /// let valid = Packet::pack(&1u8);
+    /// assert_eq!(valid.view().try_cast::<Kind>().unwrap().unpack(), Kind::A);
+    ///
+    /// let invalid = Packet::pack(&3u8);
+    /// assert!(invalid.view().try_cast::<Kind>().is_err());
+    /// ```
+    ///
+    /// the sizes are checked when the function is instantiated:
+    ///
+    /// ```compile_fail
+    /// # use packtool::Packet;
+    /// let packet = Packet::pack(&1u8);
+    /// let _ = packet.view().try_cast::<u16>();
+    /// ```
+    pub fn try_cast<U>(self) -> Result<View<'a, U>, Error>
+    where
+        U: Packed,
+    {
+        #[allow(clippy::let_unit_value)]
+        let () = SameSize::<T, U>::ASSERT;
+
+        U::check(self.slice)?;
+        Ok(View::new(self.slice))
+    }
+
+    /// reinterpret the view as a view of `U` without checking the bytes
+    /// again: `T` is declared [`Compatible`] with `U`.
+    #[inline]
+    pub fn cast<U>(self) -> View<'a, U>
+    where
+        U: Packed,
+        T: Compatible<U>,
+    {
+        #[allow(clippy::let_unit_value)]
+        let () = SameSize::<T, U>::ASSERT;
+
+        View::new(self.slice)
+    }
+
     #[inline]
     pub fn as_slice(self) -> &'a [u8] {
         self.slice
     }
+
+    /// find the position of the view in the given `slice`
+    ///
+    /// returns `None` if the view is not pointing within `slice`
+    pub(crate) fn range_in(self, slice: &[u8]) -> Option<Range<usize>> {
+        let start = (self.slice.as_ptr() as usize).checked_sub(slice.as_ptr() as usize)?;
+        let end = start.checked_add(self.slice.len())?;
+
+        if end <= slice.len() {
+            Some(start..end)
+        } else {
+            None
+        }
+    }
 }
 
 impl<'a, T> Clone for View<'a, T> {
     fn clone(&self) -> Self {
-        Self {
-            slice: self.slice,
-            marker: self.marker,
-        }
+        *self
     }
 }
 
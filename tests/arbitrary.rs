@@ -0,0 +1,160 @@
+#![cfg(feature = "arbitrary")]
+
+use packtool::{
+    arbitrary::{Arbitrary, Unstructured},
+    Packed, Packet, View,
+};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary, value = b"fuzz")]
+struct Tag;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary, value = 0xcafeu16)]
+struct Magic();
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary)]
+#[repr(u16)]
+enum Version {
+    V1 = 0x0100,
+    V2 = 0x0200,
+    V3 = 0x0F00,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary)]
+struct BlockNumber(u32, i64);
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary)]
+struct Header {
+    tag: Tag,
+    version: Version,
+    magic: Magic,
+    block_number: BlockNumber,
+    pair: (u8, [u8; 3]),
+}
+
+/// a small deterministic generator so the test does not need extra
+/// dependencies to generate the unstructured data
+fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (state >> 33) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn arbitrary_values_are_valid() {
+    for seed in 0..256 {
+        let data = random_bytes(seed, 64);
+        let mut u = Unstructured::new(&data);
+
+        let header = Header::arbitrary(&mut u).unwrap();
+        let packet = Packet::pack(&header);
+
+        let view = View::<Header>::try_from_slice(packet.as_ref()).unwrap();
+        assert_eq!(view.unpack(), header);
+    }
+}
+
+#[test]
+fn arbitrary_valid_packet() {
+    for seed in 0..256 {
+        let data = random_bytes(seed, 64);
+        let mut u = Unstructured::new(&data);
+
+        let packet = Packet::<Header>::arbitrary_valid(&mut u).unwrap();
+
+        assert!(View::<Header>::validate_all(packet.as_ref()).is_valid());
+    }
+}
+
+#[test]
+fn every_variant_is_generated() {
+    let mut seen = [false; 3];
+    for seed in 0..256 {
+        let data = random_bytes(seed, 8);
+        let mut u = Unstructured::new(&data);
+
+        match Version::arbitrary(&mut u).unwrap() {
+            Version::V1 => seen[0] = true,
+            Version::V2 => seen[1] = true,
+            Version::V3 => seen[2] = true,
+        }
+    }
+
+    assert_eq!(seen, [true; 3]);
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary, ordered)]
+#[repr(u16)]
+enum Priority {
+    Low = 0x0001,
+    High = 0x0100,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary, ordered)]
+struct Key(Priority, i32, [u8; 2]);
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary, size = 12, padding)]
+struct Padded {
+    version: Version,
+    #[packed(offset = 4)]
+    value: u32,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary, overlay)]
+struct Raw {
+    header: Padded,
+    bytes: [u8; 6],
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary)]
+#[repr(u8)]
+enum Kind {
+    Read = 1,
+    Write = 2,
+}
+
+/// the bytes of `raw` are not all valid for `kind`
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(arbitrary, overlay)]
+struct Reg {
+    raw: [u8; 2],
+    kind: Kind,
+}
+
+#[test]
+fn arbitrary_valid_layouts() {
+    for seed in 0..256 {
+        let data = random_bytes(seed, 64);
+        let mut u = Unstructured::new(&data);
+
+        let key = Packet::<Key>::arbitrary_valid(&mut u).unwrap();
+        assert!(View::<Key>::validate_all(key.as_ref()).is_valid());
+
+        let padded = Packet::<Padded>::arbitrary_valid(&mut u).unwrap();
+        assert!(View::<Padded>::validate_all(padded.as_ref()).is_valid());
+        assert_eq!(&padded.as_ref()[2..4], &[0, 0]);
+        assert_eq!(&padded.as_ref()[8..], &[0; 4]);
+
+        let raw = Packet::<Raw>::arbitrary_valid(&mut u).unwrap();
+        assert!(View::<Raw>::validate_all(raw.as_ref()).is_valid());
+
+        let reg = Packet::<Reg>::arbitrary_valid(&mut u).unwrap();
+        assert!(View::<Reg>::validate_all(reg.as_ref()).is_valid());
+
+        let reg = Reg::arbitrary(&mut u).unwrap();
+        assert_eq!(Packet::pack(&reg).unpack(), reg);
+    }
+}
@@ -0,0 +1,145 @@
+use packtool::{builder::Draft, Error, Packed, Packet};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = b"tag")]
+struct Tag;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Version {
+    V1 = 1,
+    V2 = 2,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(builder)]
+struct BlockNumber(
+    #[packed(accessor = "epoch")] u32,
+    #[packed(accessor = "slot")] u32,
+);
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(builder)]
+struct Header {
+    tag: Tag,
+    version: Version,
+    block_number: BlockNumber,
+    pair: (u8, u16),
+}
+
+/// a packed type written by hand, without `BuilderField`
+#[derive(Debug, PartialEq, Eq)]
+struct Celsius(i16);
+
+impl Packed for Celsius {
+    const SIZE: usize = 2;
+    type Bytes = [u8; 2];
+
+    fn unchecked_read_from_slice(slice: &[u8]) -> Self {
+        Self(i16::unchecked_read_from_slice(slice))
+    }
+
+    fn unchecked_write_to_slice(&self, slice: &mut [u8]) {
+        self.0.unchecked_write_to_slice(slice)
+    }
+
+    fn check(_slice: &[u8]) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Reading {
+    sensor: u8,
+    temperature: Celsius,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(builder)]
+struct Sample {
+    tag: Tag,
+    reading: Reading,
+}
+
+#[test]
+fn build_tuple() {
+    let block_number = BlockNumber::builder().slot(2).epoch(1).build();
+
+    assert_eq!(block_number, Packet::pack(&BlockNumber(1, 2)));
+}
+
+#[test]
+fn build_struct() {
+    let header = Header::builder()
+        .version(Version::V2)
+        .block_number(BlockNumber(1, 2))
+        .pair((3, 4))
+        .build();
+
+    let expected = Header {
+        tag: Tag,
+        version: Version::V2,
+        block_number: BlockNumber(1, 2),
+        pair: (3, 4),
+    };
+
+    assert_eq!(header, Packet::pack(&expected));
+    assert_eq!(header.unpack(), expected);
+}
+
+#[test]
+fn build_from_views() {
+    let block_number = BlockNumber::builder().epoch(1).slot(2).build();
+    let previous = Header::builder()
+        .version(Version::V1)
+        .block_number(block_number.view())
+        .pair((3, 4))
+        .build();
+
+    let header = Header::builder()
+        .version(Version::V2)
+        .block_number(Header::block_number(previous.view()))
+        .pair(Header::pair(previous.view()))
+        .build();
+
+    assert_eq!(Header::version(header.view()).unpack(), Version::V2);
+    assert_eq!(
+        Header::block_number(header.view()).unpack(),
+        BlockNumber(1, 2)
+    );
+    assert_eq!(Header::pair(header.view()).unpack(), (3, 4));
+}
+
+#[test]
+fn setting_a_field_twice() {
+    let header = Header::builder()
+        .version(Version::V1)
+        .block_number(BlockNumber(1, 2))
+        .pair((3, 4))
+        .version(Version::V2)
+        .build();
+
+    assert_eq!(Header::version(header.view()).unpack(), Version::V2);
+}
+
+#[test]
+fn build_with_fields_without_builder() {
+    let reading = Reading {
+        sensor: 1,
+        temperature: Celsius(-4),
+    };
+    let sample = Sample::builder().reading(reading).build();
+
+    assert_eq!(
+        Sample::reading(sample.view()).unpack().temperature,
+        Celsius(-4)
+    );
+}
+
+#[test]
+fn unfinished_draft() {
+    // the version of the header is not set
+    assert!(Draft::<Header>::new().finish().is_err());
+
+    assert!(Draft::<BlockNumber>::new().finish().is_ok());
+}
@@ -0,0 +1,72 @@
+use packtool::{Compatible, Packed, Packet, View};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Hash([u8; 32]);
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = b"rec")]
+struct Tag;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Generic {
+    tag: [u8; 3],
+    value: u32,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Specialised {
+    tag: Tag,
+    value: u32,
+}
+
+impl Compatible<[u8; 32]> for Hash {}
+impl Compatible<Hash> for [u8; 32] {}
+impl Compatible<Generic> for Specialised {}
+
+#[test]
+fn cast_between_compatible_types() {
+    let bytes = Packet::pack(&[7u8; 32]);
+    let hash: View<'_, Hash> = bytes.view().cast();
+    assert_eq!(hash.unpack(), Hash([7; 32]));
+    assert_eq!(hash.as_slice(), bytes.view().as_slice());
+
+    let back: View<'_, [u8; 32]> = hash.cast();
+    assert_eq!(back.unpack(), [7; 32]);
+
+    // every type is compatible with itself
+    let same: View<'_, Hash> = hash.cast();
+    assert_eq!(same, hash);
+}
+
+#[test]
+fn specialised_to_generic() {
+    let record = Packet::pack(&Specialised {
+        tag: Tag,
+        value: 42,
+    });
+
+    let generic = record.view().cast::<Generic>();
+    assert_eq!(
+        generic.unpack(),
+        Generic {
+            tag: *b"rec",
+            value: 42
+        }
+    );
+}
+
+#[test]
+fn try_cast_checks_the_target_layout() {
+    let valid = Packet::pack(&Generic {
+        tag: *b"rec",
+        value: 42,
+    });
+    let specialised = valid.view().try_cast::<Specialised>().unwrap();
+    assert_eq!(Specialised::value(specialised).unpack(), 42);
+
+    let invalid = Packet::pack(&Generic {
+        tag: *b"REC",
+        value: 42,
+    });
+    assert!(invalid.view().try_cast::<Specialised>().is_err());
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "tokio")]
+
+use futures::{SinkExt as _, StreamExt as _};
+use packtool::{
+    codec::{InvalidFrame, PackedCodec},
+    Error, Packed, Packet,
+};
+use tokio::io::AsyncWriteExt as _;
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = 0xfeu8)]
+struct Magic;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Command {
+    Get = 1,
+    Set = 2,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Frame {
+    magic: Magic,
+    command: Command,
+    key: u16,
+}
+
+fn frame(command: Command, key: u16) -> Frame {
+    Frame {
+        magic: Magic,
+        command,
+        key,
+    }
+}
+
+async fn decode_all(bytes: &[u8], on_invalid: InvalidFrame) -> Vec<Result<Frame, Error>> {
+    let (mut client, server) = tokio::io::duplex(3);
+    let codec = PackedCodec::<Frame>::new().on_invalid(on_invalid);
+    let reader = FramedRead::new(server, codec);
+
+    let bytes = bytes.to_vec();
+    let writer = tokio::spawn(async move {
+        client.write_all(&bytes).await.unwrap();
+    });
+
+    let frames = reader
+        .map(|frame| frame.map(|frame| frame.unpack()))
+        .collect()
+        .await;
+    writer.await.unwrap();
+    frames
+}
+
+#[tokio::test]
+async fn end_to_end() {
+    let (client, server) = tokio::io::duplex(5);
+    let mut writer = FramedWrite::new(client, PackedCodec::<Frame>::new());
+    let reader = FramedRead::new(server, PackedCodec::<Frame>::new());
+
+    let sender = tokio::spawn(async move {
+        writer.send(&frame(Command::Get, 1)).await.unwrap();
+
+        let packet = Packet::pack(&frame(Command::Set, 2));
+        writer.send(packet.view()).await.unwrap();
+    });
+
+    let frames = reader
+        .map(|frame| frame.unwrap().unpack())
+        .collect::<Vec<_>>()
+        .await;
+    sender.await.unwrap();
+
+    assert_eq!(frames, vec![frame(Command::Get, 1), frame(Command::Set, 2)]);
+}
+
+#[tokio::test]
+async fn invalid_frame_error() {
+    let frames = decode_all(b"\xfe\x01\x01\x00\xfe\x03\x02\x00", InvalidFrame::Error).await;
+
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].as_ref().unwrap(), &frame(Command::Get, 1));
+    assert!(frames[1]
+        .as_ref()
+        .unwrap_err()
+        .to_string()
+        .ends_with("Field command of codec::Command is not valid"));
+}
+
+#[tokio::test]
+async fn invalid_frame_skip() {
+    let frames = decode_all(b"\xfe\x03\x01\x00\xfe\x02\x02\x00", InvalidFrame::Skip).await;
+
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].as_ref().unwrap(), &frame(Command::Set, 2));
+}
+
+#[tokio::test]
+async fn invalid_frame_resync() {
+    let frames = decode_all(
+        b"\x00\x00\xfe\x01\x01\x00\x42\xfe\x02\x02\x00",
+        InvalidFrame::Resync,
+    )
+    .await;
+
+    let frames = frames.into_iter().map(Result::unwrap).collect::<Vec<_>>();
+    assert_eq!(frames, vec![frame(Command::Get, 1), frame(Command::Set, 2)]);
+}
+
+#[tokio::test]
+async fn truncated_stream() {
+    let frames = decode_all(b"\xfe\x01\x01\x00\xfe\x01", InvalidFrame::Error).await;
+
+    assert_eq!(frames.len(), 2);
+    assert!(matches!(
+        frames[1],
+        Err(Error::Truncated { remaining: 2, .. })
+    ));
+}
+
+#[test]
+fn discarded() {
+    use bytes::BytesMut;
+    use tokio_util::codec::Decoder as _;
+
+    let mut codec = PackedCodec::<Frame>::new().on_invalid(InvalidFrame::Resync);
+    let mut buffer = BytesMut::from(&b"\x01\x02\xfe\x01\x2a\x00"[..]);
+
+    let frame = codec.decode(&mut buffer).unwrap().unwrap();
+    assert_eq!(Frame::key(frame.view()).unpack(), 0x2a);
+    assert_eq!(codec.discarded(), 2);
+    assert!(buffer.is_empty());
+}
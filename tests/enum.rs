@@ -1,4 +1,5 @@
 use packtool::{Packed, View};
+use std::convert::TryFrom;
 
 #[derive(Packed, Debug, PartialEq, Eq)]
 #[repr(u8)]
@@ -13,6 +14,53 @@ enum TwoU8 {
     Two = 0x00FF00FF,
 }
 
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Implicit {
+    Zero,
+    One,
+    Ten = 10,
+    Eleven,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[packed(tag = u16)]
+enum Wide {
+    One = 1,
+    Two,
+    Max = 0xFF,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(tag = i8)]
+enum Signed {
+    Minus = -1,
+    Zero,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[packed(skip(display, try_from))]
+enum Skipped {
+    Some,
+    Other,
+}
+
+impl std::fmt::Display for Skipped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("skipped")
+    }
+}
+
+impl TryFrom<u8> for Skipped {
+    type Error = ();
+
+    fn try_from(_: u8) -> Result<Self, Self::Error> {
+        Err(())
+    }
+}
+
 /*
 #[derive(Packed, Debug, PartialEq, Eq)]
 enum ThisOrThat {
@@ -56,3 +104,75 @@ fn two_u8() {
     internal_mk_test!(TwoU8 => ( "Invalid discriminant for enum::TwoU8, received 0 while expecting one of: [ 42, 16711935, ]" &[0, 0, 0, 0 ] ) );
     internal_mk_test!(TwoU8 => ( "Invalid size for enum::TwoU8: expected 4 bytes but received 6 bytes" &[0, 0, 0, 0, 0, 0 ] ) );
 }
+
+#[test]
+fn implicit() {
+    internal_mk_test!(Implicit => (Implicit::Zero, &[0]));
+    internal_mk_test!(Implicit => (Implicit::One, &[1]));
+    internal_mk_test!(Implicit => (Implicit::Ten, &[10]));
+    internal_mk_test!(Implicit => (Implicit::Eleven, &[11]));
+
+    internal_mk_test!(Implicit => ( "Invalid discriminant for enum::Implicit, received 2 while expecting one of: [ 0, 1, 10, 11, ]" &[2] ) );
+}
+
+#[test]
+fn tag() {
+    assert_eq!(Wide::SIZE, 2);
+    assert_eq!(core::mem::size_of::<Wide>(), 1);
+
+    internal_mk_test!(Wide => (Wide::One, &[1, 0]));
+    internal_mk_test!(Wide => (Wide::Two, &[2, 0]));
+    internal_mk_test!(Wide => (Wide::Max, &[0xFF, 0]));
+
+    internal_mk_test!(Wide => ( "Invalid discriminant for enum::Wide, received 511 while expecting one of: [ 1, 2, 255, ]" &[0xFF, 1] ) );
+}
+
+#[test]
+fn tag_without_repr() {
+    assert_eq!(Signed::SIZE, 1);
+
+    internal_mk_test!(Signed => (Signed::Minus, &[0xFF]));
+    internal_mk_test!(Signed => (Signed::Zero, &[0]));
+
+    internal_mk_test!(Signed => ( "Invalid discriminant for enum::Signed, received 1 while expecting one of: [ -1, 0, ]" &[1] ) );
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(Implicit::try_from(10u8).unwrap(), Implicit::Ten);
+    assert_eq!(
+        Implicit::try_from(2u8).unwrap_err().to_string(),
+        "Invalid discriminant for enum::Implicit, received 2 while expecting one of: [ 0, 1, 10, 11, ]"
+    );
+    assert_eq!(u8::from(Implicit::Eleven), 11);
+
+    // the conversions use the type of the tag, not the repr
+    assert_eq!(Wide::try_from(0xFFu16).unwrap(), Wide::Max);
+    assert_eq!(u16::from(Wide::Two), 2);
+    assert_eq!(i8::from(Signed::Minus), -1);
+}
+
+#[test]
+fn metadata() {
+    assert_eq!(
+        Implicit::VARIANTS,
+        &[
+            Implicit::Zero,
+            Implicit::One,
+            Implicit::Ten,
+            Implicit::Eleven
+        ]
+    );
+    assert_eq!(Implicit::Ten.name(), "Ten");
+    assert_eq!(Implicit::from_name("Eleven"), Some(Implicit::Eleven));
+    assert_eq!(Implicit::from_name("eleven"), None);
+    assert_eq!(Implicit::One.to_string(), "One");
+}
+
+#[test]
+fn skip() {
+    assert_eq!(Skipped::Some.to_string(), "skipped");
+    assert_eq!(Skipped::try_from(0u8), Err(()));
+    assert_eq!(Skipped::Other.name(), "Other");
+    assert_eq!(u8::from(Skipped::Other), 1);
+}
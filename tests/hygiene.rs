@@ -0,0 +1,126 @@
+//! the generated code only uses fully qualified paths: it compiles
+//! without the prelude and without any `packtool` item in scope.
+#![no_implicit_prelude]
+
+#[derive(::packtool::Packed)]
+#[cfg_attr(feature = "arbitrary", packed(arbitrary))]
+#[packed(value = b"tag")]
+pub struct Tag;
+
+#[derive(::packtool::Packed, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", packed(arbitrary))]
+#[cfg_attr(feature = "quickcheck", packed(quickcheck))]
+#[cfg_attr(feature = "proptest", packed(proptest))]
+#[repr(u8)]
+pub enum Version {
+    V1 = 1,
+    V2 = 2,
+}
+
+#[derive(::packtool::Packed, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(u16)]
+#[packed(ordered)]
+#[cfg_attr(feature = "arbitrary", packed(arbitrary))]
+pub enum Priority {
+    Low = 1,
+    High = 2,
+}
+
+#[derive(::packtool::Packed)]
+#[packed(builder)]
+pub struct HeaderV1 {
+    tag: Tag,
+    version: Version,
+    length: u32,
+}
+
+#[derive(::packtool::Packed)]
+#[packed(size = 16, padding)]
+#[packed(upgrade(HeaderV1))]
+pub struct HeaderV2 {
+    tag: Tag,
+    #[packed(version(Version::V2))]
+    version: Version,
+    length: u32,
+    #[packed(offset = 10, default)]
+    priority: u16,
+}
+
+#[derive(::packtool::Packed)]
+#[packed(ordered)]
+pub struct Key(u32, [u8; 4], Priority);
+
+#[derive(::packtool::Packed)]
+#[packed(value = 'p')]
+pub struct Prefix;
+
+#[derive(::packtool::Packed)]
+#[packed(overlay, check = "any")]
+pub struct Either(
+    ::core::option::Option<Version>,
+    #[allow(dead_code)] ::core::option::Option<u8>,
+);
+
+#[derive(::packtool::Packed, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "quickcheck", packed(quickcheck))]
+#[cfg_attr(feature = "proptest", packed(proptest))]
+pub struct Tested {
+    version: Version,
+    value: u16,
+}
+
+#[derive(::packtool::PackedVersions)]
+#[packed(tag(Version), tag_offset = 3, upgrade)]
+pub enum AnyHeader<'a> {
+    #[packed(version(Version::V1))]
+    V1(::packtool::View<'a, HeaderV1>),
+    #[packed(version(Version::V2))]
+    V2(::packtool::View<'a, HeaderV2>),
+}
+
+/// items shadowing the prelude and the names used by the generated code
+#[allow(dead_code, non_camel_case_types)]
+mod shadowing {
+    pub struct Packed;
+    pub struct Result;
+    pub struct Option;
+    pub struct Ok;
+    pub struct Err;
+    pub struct Some;
+    pub struct None;
+    pub struct Vec;
+    pub struct Box;
+    pub struct String;
+    pub struct str;
+    pub struct core;
+    pub struct std;
+
+    #[derive(::packtool::Packed)]
+    #[repr(u8)]
+    pub enum Kind {
+        A = 1,
+    }
+
+    #[derive(::packtool::Packed)]
+    pub struct Record {
+        kind: Kind,
+        value: u64,
+    }
+}
+
+#[test]
+fn generated_code_is_hygienic() {
+    ::std::assert_eq!(<HeaderV1 as ::packtool::Packed>::SIZE, 8);
+    ::std::assert_eq!(<HeaderV2 as ::packtool::Packed>::SIZE, 16);
+    ::std::assert_eq!(<Key as ::packtool::Packed>::SIZE, 10);
+    ::std::assert_eq!(<Either as ::packtool::Packed>::SIZE, 1);
+    ::std::assert_eq!(<shadowing::Record as ::packtool::Packed>::SIZE, 9);
+
+    let header = HeaderV1::builder()
+        .version(Version::V1)
+        .length(42u32)
+        .build();
+    let any = AnyHeader::try_from_slice(header.view().as_slice()).unwrap();
+    ::std::assert_eq!(any.tag(), Version::V1);
+    ::std::assert_eq!(any.upgrade().length, 42);
+}
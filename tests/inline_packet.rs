@@ -0,0 +1,69 @@
+use packtool::{InlinePacket, Packed, Packet};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = b"blk")]
+struct Magic;
+
+#[derive(Packed, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(u8)]
+enum Kind {
+    Data = 1,
+    Index = 2,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Block {
+    magic: Magic,
+    kind: Kind,
+    length: u32,
+    hash: [u8; 32],
+}
+
+const BLOCK: Block = Block {
+    magic: Magic,
+    kind: Kind::Index,
+    length: 0x2a,
+    hash: [7; 32],
+};
+
+#[test]
+fn to_bytes_is_an_array() {
+    let bytes: [u8; 40] = BLOCK.to_bytes();
+
+    assert_eq!(&bytes[..], Packet::pack(&BLOCK).as_ref());
+    assert_eq!(Block::from_bytes(&bytes).unwrap(), BLOCK);
+}
+
+#[test]
+fn from_bytes_checks() {
+    let mut bytes = BLOCK.to_bytes();
+    bytes[3] = 3;
+
+    assert!(Block::from_bytes(&bytes).is_err());
+    assert!(InlinePacket::<Block>::try_from_bytes(bytes).is_err());
+}
+
+#[test]
+fn inline_packet() {
+    let packet = InlinePacket::pack(&BLOCK);
+
+    assert_eq!(std::mem::size_of_val(&packet), Block::SIZE);
+    assert_eq!(Block::kind(packet.view()).unpack(), Kind::Index);
+    assert_eq!(packet.unpack(), BLOCK);
+
+    let copy = packet;
+    assert_eq!(copy, packet);
+    assert_eq!(
+        InlinePacket::<Block>::try_from_bytes(packet.into_bytes()).unwrap(),
+        packet
+    );
+}
+
+#[test]
+fn tuples() {
+    let value = (Kind::Data, 0xcafeu16);
+    let bytes = value.to_bytes();
+
+    assert_eq!(bytes.as_ref(), &[1, 0xfe, 0xca]);
+    assert_eq!(<(Kind, u16)>::from_bytes(&bytes).unwrap(), value);
+}
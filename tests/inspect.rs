@@ -0,0 +1,104 @@
+use packtool::{CStr, Node, Octal, Packed, Segment, View};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = b"tag")]
+struct Tag;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Version {
+    V1 = 1,
+    V2 = 2,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Versions(Version, Version);
+
+#[derive(Packed)]
+struct Header {
+    tag: Tag,
+    version: Version,
+    value: u32,
+    versions: Versions,
+    name: CStr<4>,
+    mode: Octal<4>,
+    pair: (u8, i8),
+}
+
+const VALID: &[u8] = b"tag\x01\x2a\x00\x00\x00\x01\x02ab\x00\x00755\x00\x07\xff";
+const INVALID: &[u8] = b"taG\x03\x2a\x00\x00\x00\x01\x04ab\x00\x00785\x00\x07\xff";
+
+fn field<'a>(node: &'a Node, name: &'static str) -> &'a Node {
+    node.children()
+        .iter()
+        .find(|child| child.segment() == Some(Segment::Field(name)))
+        .unwrap()
+}
+
+#[test]
+fn valid() {
+    let node = View::<Header>::inspect(VALID);
+
+    assert!(node.is_valid());
+    assert_eq!(node.segment(), None);
+    assert_eq!(node.size(), Header::SIZE);
+    assert_eq!(node.children().len(), 7);
+
+    assert_eq!(field(&node, "tag").value(), None);
+    assert_eq!(field(&node, "version").value(), Some("V1"));
+    assert_eq!(field(&node, "value").value(), Some("42"));
+    assert_eq!(field(&node, "name").value(), Some("\"ab\""));
+    assert_eq!(field(&node, "mode").value(), Some("493 (0o755)"));
+
+    let versions = field(&node, "versions");
+    assert_eq!(versions.offset(), 8);
+    assert_eq!(versions.value(), None);
+    assert_eq!(versions.children()[1].segment(), Some(Segment::Index(1)));
+    assert_eq!(versions.children()[1].offset(), 9);
+    assert_eq!(versions.children()[1].value(), Some("V2"));
+
+    let pair = field(&node, "pair");
+    assert_eq!(pair.offset(), 18);
+    assert_eq!(pair.children()[0].value(), Some("7"));
+    assert_eq!(pair.children()[1].value(), Some("-1"));
+    assert_eq!(pair.children()[1].bytes(VALID), b"\xff");
+}
+
+#[test]
+fn invalid_fields() {
+    let node = View::<Header>::inspect(INVALID);
+
+    assert!(!node.is_valid());
+    assert!(node.error().is_none());
+
+    assert!(field(&node, "tag").error().is_some());
+    assert_eq!(
+        field(&node, "version").error().unwrap().to_string(),
+        "Invalid discriminant for inspect::Version, received 3 while expecting one of: [ 1, 2, ]"
+    );
+    // the valid fields are still decoded
+    assert_eq!(field(&node, "value").value(), Some("42"));
+    assert_eq!(field(&node, "name").value(), Some("\"ab\""));
+
+    let versions = field(&node, "versions");
+    assert!(!versions.is_valid());
+    assert_eq!(versions.children()[0].value(), Some("V1"));
+    assert!(versions.children()[1].error().is_some());
+
+    let mode = field(&node, "mode");
+    assert_eq!(mode.value(), None);
+    assert!(mode.error().is_some());
+}
+
+#[test]
+fn invalid_size() {
+    let node = View::<Header>::inspect(&VALID[..4]);
+    assert!(node.error().is_some());
+    assert!(node.children().is_empty());
+
+    let mut longer = VALID.to_vec();
+    longer.push(0);
+    let node = View::<Header>::inspect(&longer);
+    assert!(node.error().is_some());
+    assert_eq!(node.children().len(), 7);
+}
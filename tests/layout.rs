@@ -0,0 +1,88 @@
+use packtool::{Packed, Packet, View};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = b"MZ")]
+struct Magic;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(size = 10)]
+struct Exact {
+    magic: Magic,
+    #[packed(offset = 2)]
+    length: u32,
+    #[packed(offset = 6)]
+    checksum: u32,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(size = 16, padding)]
+struct Padded {
+    magic: Magic,
+    #[packed(offset = 4)]
+    length: u32,
+    #[packed(offset = 12)]
+    checksum: u16,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(padding)]
+struct Gaps(u8, #[packed(offset = 4)] u8);
+
+#[test]
+fn exact_layout() {
+    assert_eq!(Exact::SIZE, 10);
+
+    let packet = Packet::pack(&Exact {
+        magic: Magic,
+        length: 1,
+        checksum: 2,
+    });
+    assert_eq!(
+        packet.view().as_slice(),
+        b"MZ\x01\x00\x00\x00\x02\x00\x00\x00"
+    );
+}
+
+#[test]
+fn padded_layout() {
+    assert_eq!(Padded::SIZE, 16);
+    assert_eq!(Gaps::SIZE, 5);
+
+    let value = Padded {
+        magic: Magic,
+        length: 0x2a,
+        checksum: 0xffff,
+    };
+    let packet = Packet::pack(&value);
+    assert_eq!(
+        packet.view().as_slice(),
+        b"MZ\x00\x00\x2a\x00\x00\x00\x00\x00\x00\x00\xff\xff\x00\x00"
+    );
+    assert_eq!(Padded::length(packet.view()).unpack(), 0x2a);
+    assert_eq!(Padded::checksum(packet.view()).unpack(), 0xffff);
+    assert_eq!(packet.unpack(), value);
+
+    let gaps = Packet::pack(&Gaps(1, 2));
+    assert_eq!(gaps.view().as_slice(), b"\x01\x00\x00\x00\x02");
+}
+
+#[test]
+fn padding_is_not_checked() {
+    let slice = b"MZ\xaa\xbb\x2a\x00\x00\x00\xcc\xdd\xee\xff\x01\x00\x99\x99";
+
+    let view = View::<Padded>::try_from_slice(slice).unwrap();
+    assert_eq!(
+        view.unpack(),
+        Padded {
+            magic: Magic,
+            length: 0x2a,
+            checksum: 1,
+        }
+    );
+
+    // the fields are still checked
+    assert!(View::<Padded>::try_from_slice(
+        b"mz\x00\x00\x2a\x00\x00\x00\x00\x00\x00\x00\xff\xff\x00\x00"
+    )
+    .is_err());
+}
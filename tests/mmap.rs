@@ -0,0 +1,127 @@
+#![cfg(feature = "mmap")]
+
+use packtool::{
+    mmap::{MappedFile, MappedFileMut},
+    Error, Packed, Packet,
+};
+use std::{fs, path::PathBuf};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = b"rec")]
+struct Magic;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Record {
+    magic: Magic,
+    height: u32,
+    hash: [u8; 4],
+}
+
+/// a file in the temporary directory, removed when dropped
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, content: &[u8]) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("packtool-{}-{}.bin", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        Self(path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn records(count: u32) -> Vec<u8> {
+    (0..count)
+        .flat_map(|height| {
+            Packet::pack(&Record {
+                magic: Magic,
+                height,
+                hash: height.to_be_bytes(),
+            })
+            .into_vec()
+        })
+        .collect()
+}
+
+#[test]
+fn read_records() {
+    let file = TempFile::new("read", &records(16));
+    let mapped = unsafe { MappedFile::open(&file.0) }.unwrap();
+
+    assert_eq!(mapped.len(), 16 * Record::SIZE);
+
+    let records = mapped.records::<Record>().unwrap();
+    assert_eq!(records.len(), 16);
+    for (height, record) in records.iter().enumerate() {
+        assert_eq!(Record::height(record).unpack(), height as u32);
+    }
+
+    let record = mapped.view::<Record>(5 * Record::SIZE).unwrap();
+    assert_eq!(Record::hash(record).unpack(), [0, 0, 0, 5]);
+
+    assert!(mapped.view::<Record>(16 * Record::SIZE - 1).is_err());
+    assert!(mapped.view::<Record>(usize::MAX).is_err());
+}
+
+#[test]
+fn truncated() {
+    let mut content = records(3);
+    content.extend_from_slice(b"re");
+    let file = TempFile::new("truncated", &content);
+    let mapped = unsafe { MappedFile::open(&file.0) }.unwrap();
+
+    assert!(matches!(
+        mapped.records::<Record>(),
+        Err(Error::Truncated {
+            records: 3,
+            remaining: 2,
+            ..
+        })
+    ));
+
+    let lazy = mapped.lazy_records::<Record>().collect::<Vec<_>>();
+    assert_eq!(lazy.len(), 4);
+    assert!(lazy[..3].iter().all(Result::is_ok));
+    assert!(lazy[3].is_err());
+}
+
+#[test]
+fn patch_and_flush() {
+    let file = TempFile::new("patch", &records(4));
+
+    {
+        let mut mapped = unsafe { MappedFileMut::open(&file.0) }.unwrap();
+
+        mapped
+            .patch(2 * Record::SIZE, Record::height, &0xcafe)
+            .unwrap();
+        mapped
+            .write(
+                3 * Record::SIZE,
+                &Record {
+                    magic: Magic,
+                    height: 42,
+                    hash: *b"beef",
+                },
+            )
+            .unwrap();
+
+        assert!(mapped.patch(4 * Record::SIZE, Record::height, &0).is_err());
+        mapped.flush().unwrap();
+    }
+
+    let content = fs::read(&file.0).unwrap();
+    let mapped = unsafe { MappedFile::open(&file.0) }.unwrap();
+    assert_eq!(mapped.as_slice(), content.as_slice());
+
+    let records = mapped.records::<Record>().unwrap();
+    let record = records.get(2).unwrap().unpack();
+    assert_eq!(record.height, 0xcafe);
+    assert_eq!(record.hash, [0, 0, 0, 2]);
+    assert_eq!(Record::hash(records.get(3).unwrap()).unpack(), *b"beef");
+}
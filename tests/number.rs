@@ -0,0 +1,73 @@
+use packtool::{
+    number::{Space, Unterminated},
+    Decimal, Error, HexAscii, Octal, Packed, Packet, View,
+};
+use std::convert::TryFrom;
+
+/// the header of an `ar` member
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Member {
+    timestamp: Decimal<12, Space>,
+    owner: Decimal<6, Space>,
+    group: Decimal<6, Space>,
+    mode: Octal<8, Unterminated>,
+    size: Decimal<10>,
+}
+
+/// the header of a `newc` cpio entry
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Entry {
+    inode: HexAscii<8>,
+    mode: HexAscii<8>,
+}
+
+#[test]
+fn ar() {
+    let member =
+        View::<Member>::try_from_slice(b"1620000000  1000  1000  100644  1234      ").unwrap();
+    assert_eq!(Member::timestamp(member).unpack().value(), 1_620_000_000);
+    assert_eq!(Member::owner(member).unpack().value(), 1000);
+    assert_eq!(Member::mode(member).unpack().value(), 0o100644);
+    assert_eq!(u64::from(Member::size(member).unpack()), 1234);
+
+    assert_eq!(
+        Packet::pack(&member.unpack()).as_ref(),
+        b"01620000000 01000 01000 001006440000001234"
+    );
+}
+
+#[test]
+fn cpio() {
+    let entry = Entry {
+        inode: HexAscii::try_from(0x1234).unwrap(),
+        mode: HexAscii::new(0o100644).unwrap(),
+    };
+    let packet = Packet::pack(&entry);
+    assert_eq!(packet.as_ref(), b"00001234000081A4");
+    assert_eq!(
+        View::<Entry>::try_from_slice(b"00001234000081a4")
+            .unwrap()
+            .unpack(),
+        entry
+    );
+}
+
+#[test]
+fn invalid() {
+    assert!(matches!(
+        Octal::<8>::check(b"0000648\0"),
+        Err(Error::InvalidNumber { .. })
+    ));
+    assert!(matches!(
+        Decimal::<4>::check(b"12\0a"),
+        Err(Error::InvalidNumber { .. })
+    ));
+    assert!(matches!(
+        HexAscii::<2>::new(0x100),
+        Err(Error::NumberTooLarge {
+            value: 0x100,
+            max: 0xff,
+            ..
+        })
+    ));
+}
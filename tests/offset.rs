@@ -0,0 +1,140 @@
+use packtool::{
+    offset::{FromField, FromStruct},
+    Buffer, Error, Offset, Packed, Packet,
+};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Kind {
+    Code = 1,
+    Data = 2,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Section {
+    kind: Kind,
+    length: u8,
+}
+
+/// an entry of the table, pointing to its section from its own start
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Entry {
+    id: u16,
+    section: Offset<u16, Section, FromStruct>,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Header {
+    magic: [u8; 2],
+    first: Entry,
+    second: Entry,
+    last: Offset<i16, Section, FromField>,
+    table: Offset<u32, Entry>,
+}
+
+fn file() -> Vec<u8> {
+    let header = Header {
+        magic: *b"OF",
+        // the entry is at 2, its section at 2 + 14
+        first: Entry {
+            id: 1,
+            section: Offset::new(14),
+        },
+        // the entry is at 6, its section at 6 + 12
+        second: Entry {
+            id: 2,
+            section: Offset::new(12),
+        },
+        // the field is at 10, the section at 10 + 10
+        last: Offset::new(10),
+        table: Offset::new(6),
+    };
+
+    let mut file = Packet::pack(&header).as_ref().to_vec();
+    file.extend_from_slice(&[1, 10, 2, 20, 2, 30]);
+    file
+}
+
+#[test]
+fn addressing() {
+    let file = file();
+    let header = Buffer::new(&file).root::<Header>().unwrap();
+
+    let first = header
+        .project(Header::first)
+        .project(Entry::section)
+        .follow()
+        .unwrap();
+    assert_eq!(first.position(), 16);
+    assert_eq!(
+        first.unpack(),
+        Section {
+            kind: Kind::Code,
+            length: 10
+        }
+    );
+
+    let second = header.project(Header::second).project(Entry::section);
+    assert_eq!(
+        second.follow().unwrap().project(Section::length).unpack(),
+        20
+    );
+
+    let last = header.project(Header::last).follow().unwrap();
+    assert_eq!(last.position(), 20);
+    assert_eq!(Section::length(last.view()).unpack(), 30);
+
+    let table = header.project(Header::table).follow().unwrap();
+    assert_eq!(table.unpack(), header.project(Header::second).unpack());
+}
+
+#[test]
+fn backward() {
+    let mut file = file();
+    // the last section is the first one: 10 + 6
+    file[10..12].copy_from_slice(&6i16.to_le_bytes());
+
+    let header = Buffer::new(&file).root::<Header>().unwrap();
+    assert_eq!(
+        header.project(Header::last).follow().unwrap().position(),
+        16
+    );
+
+    file[10..12].copy_from_slice(&(-11i16).to_le_bytes());
+    let header = Buffer::new(&file).root::<Header>().unwrap();
+    assert!(matches!(
+        header.project(Header::last).follow(),
+        Err(Error::OutOfBounds { position: -1, .. })
+    ));
+}
+
+#[test]
+fn out_of_bounds() {
+    let file = file();
+    let header = Buffer::new(&file[..21]).root::<Header>().unwrap();
+
+    assert!(header
+        .project(Header::first)
+        .project(Entry::section)
+        .follow()
+        .is_ok());
+    assert!(matches!(
+        header.project(Header::last).follow(),
+        Err(Error::OutOfBounds {
+            position: 20,
+            size: 2,
+            length: 21,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn invalid_target() {
+    let mut file = file();
+    file[18] = 3;
+
+    let header = Buffer::new(&file).root::<Header>().unwrap();
+    let second = header.project(Header::second).project(Entry::section);
+    assert!(matches!(second.follow(), Err(Error::Context { .. })));
+}
@@ -0,0 +1,121 @@
+use packtool::{Ordered, Packed, Packet};
+use quickcheck::{Arbitrary, Gen};
+use quickcheck_macros::quickcheck;
+use std::convert::TryFrom;
+
+#[derive(Packed, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[packed(value = b"k")]
+struct Prefix;
+
+#[derive(Packed, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[packed(ordered)]
+#[repr(i16)]
+enum Kind {
+    Deleted = -300,
+    Block = 2,
+    Transaction = 256,
+}
+
+#[derive(Packed, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[packed(ordered)]
+struct Position(u16, i8);
+
+#[derive(Packed, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[packed(ordered)]
+struct Key {
+    prefix: Prefix,
+    kind: Kind,
+    height: u32,
+    delta: i64,
+    position: Position,
+    pair: (u16, i32),
+    hash: [u8; 3],
+}
+
+impl Arbitrary for Kind {
+    fn arbitrary(g: &mut Gen) -> Self {
+        *g.choose(&[Kind::Deleted, Kind::Block, Kind::Transaction])
+            .unwrap()
+    }
+}
+
+impl Arbitrary for Key {
+    fn arbitrary(g: &mut Gen) -> Self {
+        // pick the values from small sets so the comparisons
+        // also reach the last fields
+        Key {
+            prefix: Prefix,
+            kind: Kind::arbitrary(g),
+            height: *g.choose(&[0, 1, 255, 256, u32::MAX]).unwrap(),
+            delta: *g.choose(&[i64::MIN, -256, -1, 0, 1, 256]).unwrap(),
+            position: Position(u16::arbitrary(g) % 3, i8::arbitrary(g)),
+            pair: (u16::arbitrary(g) % 2, i32::arbitrary(g)),
+            hash: [u8::arbitrary(g), u8::arbitrary(g), u8::arbitrary(g)],
+        }
+    }
+}
+
+#[quickcheck]
+fn bytes_order_is_value_order(a: Key, b: Key) -> bool {
+    let packed_a = Packet::pack(&a);
+    let packed_b = Packet::pack(&b);
+
+    a.cmp(&b) == packed_a.cmp(&packed_b) && a.cmp(&b) == packed_a.view().cmp(&packed_b.view())
+}
+
+#[quickcheck]
+fn roundtrip(key: Key) -> bool {
+    Packet::pack(&key).unpack() == key
+}
+
+#[test]
+fn encoding() {
+    let key = Key {
+        prefix: Prefix,
+        kind: Kind::Transaction,
+        height: 0x0102,
+        delta: -1,
+        position: Position(3, -128),
+        pair: (1, 0),
+        hash: *b"abc",
+    };
+    let packet = Packet::pack(&key);
+
+    assert_eq!(
+        packet.as_ref(),
+        &[
+            b'k', // prefix
+            0x81, 0x00, // kind
+            0x00, 0x00, 0x01, 0x02, // height
+            0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // delta
+            0x00, 0x03, 0x00, // position
+            0x00, 0x01, 0x80, 0x00, 0x00, 0x00, // pair
+            b'a', b'b', b'c', // hash
+        ][..]
+    );
+
+    let view = packet.view();
+    let height: Ordered<u32> = Key::height(view).unpack();
+    assert_eq!(height.into_inner(), 0x0102);
+    assert_eq!(Key::delta(view).unpack(), Ordered(-1));
+    assert_eq!(Key::kind(view).unpack(), Kind::Transaction);
+    assert_eq!(Position::_1(Key::position(view)).unpack().0, -128);
+    assert_eq!(Key::pair(view).unpack(), Ordered((1, 0)));
+    assert_eq!(Key::hash(view).unpack(), *b"abc");
+}
+
+#[test]
+fn invalid_discriminant() {
+    let mut bytes = Packet::pack(&Position(1, 1)).into_vec();
+    assert!(Packet::<Position>::try_from(bytes.clone()).is_ok());
+
+    bytes = vec![0x81, 0x01];
+    assert!(packtool::View::<Kind>::try_from_slice(&bytes).is_err());
+    bytes = vec![0x80, 0x02];
+    assert_eq!(
+        packtool::View::<Kind>::try_from_slice(&bytes)
+            .unwrap()
+            .unpack(),
+        Kind::Block
+    );
+}
@@ -0,0 +1,158 @@
+use packtool::{Packed, Packet, Segment, View};
+
+#[derive(Packed, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(u8)]
+enum Mode {
+    Off = 0,
+    On = 1,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Control {
+    mode: Mode,
+    level: u8,
+}
+
+/// the same 4 bytes seen as a raw register, as its bytes or as a
+/// control word followed by 2 reserved bytes
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(overlay)]
+struct Register {
+    raw: u32,
+    bytes: [u8; 4],
+    control: Control,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = b"v1")]
+struct V1;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = b"v2")]
+struct V2;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(overlay, check = "any")]
+struct AnyVersion(Option<V1>, Option<V2>);
+
+/// a register where the mode is only meaningful for some values
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(overlay, check = "any")]
+struct Reg {
+    mode: Option<Mode>,
+    raw: Option<u8>,
+}
+
+#[test]
+fn size_is_the_largest_member() {
+    assert_eq!(Register::SIZE, 4);
+    assert_eq!(AnyVersion::SIZE, 2);
+}
+
+#[test]
+fn every_member_views_the_same_bytes() {
+    let view = View::<Register>::try_from_slice(&[1, 7, 0xaa, 0xbb]).unwrap();
+
+    assert_eq!(Register::raw(view).unpack(), 0xbbaa_0701);
+    assert_eq!(Register::bytes(view).unpack(), [1, 7, 0xaa, 0xbb]);
+    let control = Register::control(view);
+    assert_eq!(control.as_slice(), &[1, 7]);
+    assert_eq!(Control::mode(control).unpack(), Mode::On);
+    assert_eq!(Control::level(control).unpack(), 7);
+
+    assert_eq!(
+        view.unpack(),
+        Register {
+            raw: 0xbbaa_0701,
+            bytes: [1, 7, 0xaa, 0xbb],
+            control: Control {
+                mode: Mode::On,
+                level: 7
+            },
+        }
+    );
+}
+
+#[test]
+fn the_first_member_is_written() {
+    let packet = Packet::pack(&Register {
+        raw: 0x0000_0200,
+        bytes: [0xff; 4],
+        control: Control {
+            mode: Mode::On,
+            level: 0xff,
+        },
+    });
+
+    assert_eq!(packet.view().as_slice(), &[0, 2, 0, 0]);
+    assert_eq!(Register::control(packet.view()).unpack().level, 2);
+}
+
+#[test]
+fn check_all_members() {
+    // `mode` is not valid, so the whole register is not valid
+    let error = View::<Register>::try_from_slice(&[2, 0, 0, 0]).unwrap_err();
+    assert!(error
+        .to_string()
+        .ends_with("Field control of overlay::Control is not valid"));
+
+    let report = View::<Register>::validate_all(&[2, 0, 0, 0]);
+    assert_eq!(report.issues().len(), 1);
+    assert_eq!(
+        report.issues()[0].path(),
+        &[Segment::Field("control"), Segment::Field("mode")]
+    );
+}
+
+#[test]
+fn check_any_member() {
+    let v1 = View::<AnyVersion>::try_from_slice(b"v1").unwrap();
+    assert!(AnyVersion::_0(v1).unwrap().as_slice() == b"v1");
+    assert!(AnyVersion::_1(v1).is_err());
+    assert_eq!(v1.unpack(), AnyVersion(Some(V1), None));
+    assert!(View::<AnyVersion>::try_from_slice(b"v2").is_ok());
+
+    assert!(View::<AnyVersion>::try_from_slice(b"v3").is_err());
+    let report = View::<AnyVersion>::validate_all(b"v3");
+    assert_eq!(report.issues().len(), 2);
+    assert_eq!(report.issues()[0].path(), &[Segment::Index(0)]);
+    assert_eq!(report.issues()[1].path(), &[Segment::Index(1)]);
+
+    assert!(View::<AnyVersion>::validate_all(b"v2").is_valid());
+}
+
+#[test]
+fn only_the_valid_members_are_read() {
+    let view = View::<Reg>::try_from_slice(&[5]).unwrap();
+    assert!(Reg::mode(view).is_err());
+    assert_eq!(Reg::raw(view).unwrap().unpack(), 5);
+    assert_eq!(
+        view.unpack(),
+        Reg {
+            mode: None,
+            raw: Some(5)
+        }
+    );
+
+    let view = View::<Reg>::try_from_slice(&[1]).unwrap();
+    assert_eq!(Reg::mode(view).unwrap().unpack(), Mode::On);
+    assert_eq!(
+        view.unpack(),
+        Reg {
+            mode: Some(Mode::On),
+            raw: Some(1)
+        }
+    );
+}
+
+#[test]
+fn the_first_member_set_is_written() {
+    let packet = Packet::pack(&Reg {
+        mode: None,
+        raw: Some(7),
+    });
+    assert_eq!(packet.view().as_slice(), &[7]);
+
+    let packet = Packet::pack(&AnyVersion(None, Some(V2)));
+    assert_eq!(packet.view().as_slice(), b"v2");
+}
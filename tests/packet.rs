@@ -0,0 +1,68 @@
+use packtool::{Packed, Packet};
+use std::{convert::TryFrom, rc::Rc, sync::Arc};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Kind {
+    Ping = 1,
+    Pong = 2,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Message {
+    kind: Kind,
+    nonce: u32,
+}
+
+const MESSAGE: &[u8] = &[2, 0x2a, 0, 0, 0];
+
+#[test]
+fn vec_without_copy() {
+    let vec = MESSAGE.to_vec();
+    let pointer = vec.as_ptr();
+
+    let packet = Packet::<Message, Vec<u8>>::try_from(vec).unwrap();
+    assert_eq!(Message::kind(packet.view()).unpack(), Kind::Pong);
+
+    let vec = packet.into_inner();
+    assert_eq!(vec.as_ptr(), pointer);
+}
+
+#[test]
+fn boxed() {
+    let packet = Packet::<Message>::try_from(MESSAGE.to_vec()).unwrap();
+    assert_eq!(
+        packet,
+        Packet::pack(&Message {
+            kind: Kind::Pong,
+            nonce: 0x2a,
+        })
+    );
+
+    let boxed = packet.into_vec().into_boxed_slice();
+    let pointer = boxed.as_ptr();
+    let packet = Packet::<Message>::from_boxed(boxed).unwrap();
+    let boxed = packet.into_inner();
+    assert_eq!(boxed.as_ptr(), pointer);
+}
+
+#[test]
+fn any_storage() {
+    let shared: Arc<[u8]> = MESSAGE.into();
+    let packet = Packet::<Message, _>::try_from(shared.clone()).unwrap();
+    let copy = packet.clone();
+
+    assert_eq!(Arc::strong_count(&shared), 3);
+    assert_eq!(copy.unpack().nonce, 0x2a);
+
+    let rc: Rc<[u8]> = MESSAGE.into();
+    let packet = Packet::<Message, _>::try_from_storage(rc).unwrap();
+    assert_eq!(packet, copy);
+}
+
+#[test]
+fn invalid() {
+    assert!(Packet::<Message>::try_from(vec![3, 0, 0, 0, 0]).is_err());
+    assert!(Packet::<Message, Vec<u8>>::try_from(vec![1, 0, 0, 0]).is_err());
+    assert!(Packet::<Message, &[u8]>::try_from_storage(&MESSAGE[1..]).is_err());
+}
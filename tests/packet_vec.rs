@@ -0,0 +1,124 @@
+use packtool::{Packed, Packet, PacketVec};
+
+#[derive(Packed, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(u8)]
+enum Kind {
+    Block = 1,
+    Transaction = 2,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Item {
+    kind: Kind,
+    height: u32,
+}
+
+fn item(kind: Kind, height: u32) -> Item {
+    Item { kind, height }
+}
+
+fn heights(items: &PacketVec<Item>) -> Vec<u32> {
+    items
+        .iter()
+        .map(|item| Item::height(item).unpack())
+        .collect()
+}
+
+#[test]
+fn push_and_get() {
+    let mut items = PacketVec::with_capacity(4);
+    assert!(items.is_empty());
+
+    items.push(&item(Kind::Block, 3));
+    items.push_view(Packet::pack(&item(Kind::Transaction, 1)).view());
+    items.try_push_slice(&[1, 2, 0, 0, 0]).unwrap();
+    assert!(items.try_push_slice(&[3, 2, 0, 0, 0]).is_err());
+    assert!(items.try_push_slice(&[1, 2, 0, 0]).is_err());
+
+    assert_eq!(items.len(), 3);
+    assert_eq!(items.get(1).unwrap().unpack(), item(Kind::Transaction, 1));
+    assert!(items.get(3).is_none());
+    assert_eq!(Item::height(items.last().unwrap()).unpack(), 2);
+    assert_eq!(heights(&items), vec![3, 1, 2]);
+
+    assert_eq!(
+        items.as_bytes(),
+        &[1, 3, 0, 0, 0, 2, 1, 0, 0, 0, 1, 2, 0, 0, 0]
+    );
+}
+
+#[test]
+fn remove() {
+    let records = (0..5)
+        .map(|height| item(Kind::Block, height))
+        .collect::<Vec<_>>();
+    let mut items = records.iter().collect::<PacketVec<_>>();
+
+    let removed = items.swap_remove(1);
+    assert_eq!(removed.unpack(), item(Kind::Block, 1));
+    assert_eq!(heights(&items), vec![0, 4, 2, 3]);
+
+    let removed = items.swap_remove(3);
+    assert_eq!(removed.unpack(), item(Kind::Block, 3));
+    assert_eq!(heights(&items), vec![0, 4, 2]);
+
+    assert_eq!(items.pop().unwrap().unpack(), item(Kind::Block, 2));
+
+    items.truncate(usize::MAX);
+    assert_eq!(heights(&items), vec![0, 4]);
+
+    items.truncate(1);
+    assert_eq!(heights(&items), vec![0]);
+
+    items.clear();
+    assert!(items.pop().is_none());
+}
+
+#[test]
+#[should_panic]
+fn swap_remove_out_of_bounds() {
+    let mut items = PacketVec::new();
+    items.push(&item(Kind::Block, 0));
+    items.swap_remove(1);
+}
+
+#[test]
+fn sort() {
+    let mut items = [300, 2, 256, 1]
+        .iter()
+        .map(|height| item(Kind::Block, *height))
+        .collect::<Vec<_>>()
+        .iter()
+        .collect::<PacketVec<_>>();
+    items.push(&item(Kind::Transaction, 2));
+
+    items.sort_by_key(|item| Item::height(item).unpack());
+    assert_eq!(heights(&items), vec![1, 2, 2, 256, 300]);
+    // stable sort
+    assert_eq!(
+        Item::kind(items.get(2).unwrap()).unpack(),
+        Kind::Transaction
+    );
+}
+
+#[test]
+fn from_bytes() {
+    let mut items = PacketVec::new();
+    items.push(&item(Kind::Block, 1));
+    items.push(&item(Kind::Transaction, 2));
+
+    let bytes = items.clone().into_bytes();
+    assert_eq!(
+        PacketVec::<Item>::try_from_bytes(bytes.clone()).unwrap(),
+        items
+    );
+
+    let mut invalid = bytes.clone();
+    invalid[5] = 0;
+    assert!(PacketVec::<Item>::try_from_bytes(invalid).is_err());
+
+    assert!(PacketVec::<Item>::try_from_bytes(bytes[..9].to_vec()).is_err());
+
+    let copy = items.iter().collect::<PacketVec<_>>();
+    assert_eq!(copy, items);
+}
@@ -0,0 +1,77 @@
+use packtool::{Packed, Packet, Shared, View};
+use std::convert::TryFrom;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Header {
+    version: u8,
+    height: u32,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Block {
+    #[packed(accessor = "header")]
+    header: Header,
+    content: [u8; 8],
+}
+
+fn block() -> Vec<u8> {
+    vec![1, 42, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8]
+}
+
+#[test]
+fn shared_allocation() {
+    let block = Packet::<Block, Shared>::try_from_storage(block().into()).unwrap();
+
+    let header = block.project(Block::header);
+    assert_eq!(header.storage().range(), 0..5);
+    assert_eq!(Header::height(header.view()).unpack(), 42);
+
+    let content = block.project(Block::content);
+    assert_eq!(content.storage().range(), 5..13);
+    assert_eq!(content.unpack(), [1, 2, 3, 4, 5, 6, 7, 8]);
+
+    // projecting from a projection stays in the same allocation
+    let height = header.project(Header::height);
+    assert_eq!(height.storage().range(), 1..5);
+    assert_eq!(height.unpack(), 42);
+
+    assert_eq!(std::sync::Arc::strong_count(block.storage().buffer()), 4);
+}
+
+#[test]
+fn into_shared() {
+    let block = Packet::<Block>::try_from(block()).unwrap().into_shared();
+    let header = block.project(Block::header);
+
+    drop(block);
+    assert_eq!(
+        header.unpack(),
+        Header {
+            version: 1,
+            height: 42
+        }
+    );
+}
+
+#[test]
+#[should_panic]
+fn foreign_view() {
+    static OTHER: &[u8] = &[1, 0, 0, 0, 0];
+
+    let block = Packet::<Block, Shared>::try_from_storage(block().into()).unwrap();
+    let _ = block.project(|_| View::<Header>::try_from_slice(OTHER).unwrap());
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes() {
+    let block = Packet::<Block, bytes::Bytes>::try_from_storage(block().into()).unwrap();
+    let header = block.project(Block::header);
+
+    assert_eq!(
+        header.storage().as_ptr(),
+        block.storage().as_ptr(),
+        "the sub packet is pointing in the same memory"
+    );
+    assert_eq!(Header::version(header.view()).unpack(), 1);
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "proptest")]
+
+use packtool::{
+    proptest::{
+        arbitrary::any,
+        strategy::{Strategy, ValueTree},
+        test_runner::TestRunner,
+    },
+    Packed,
+};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = b"check", proptest)]
+struct Tag;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(proptest)]
+#[repr(u16)]
+enum Version {
+    V1 = 0x0100,
+    V2 = 0x0200,
+    V3 = 0x0F00,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(proptest)]
+struct BlockNumber(u32, i64);
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(proptest)]
+struct Header {
+    tag: Tag,
+    version: Version,
+    block_number: BlockNumber,
+    pair: (u8, [u8; 3]),
+}
+
+#[test]
+fn roundtrip() {
+    packtool::testing::roundtrip_proptest::<Tag>();
+    packtool::testing::roundtrip_proptest::<Version>();
+    packtool::testing::roundtrip_proptest::<BlockNumber>();
+    packtool::testing::roundtrip_proptest::<Header>();
+}
+
+#[test]
+fn shrink_enum_toward_first_variant() {
+    let mut runner = TestRunner::deterministic();
+
+    for _ in 0..32 {
+        let mut tree = any::<Version>().new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+
+        assert_eq!(tree.current(), Version::V1);
+    }
+}
+
+#[test]
+fn shrink_struct() {
+    let mut runner = TestRunner::deterministic();
+
+    for _ in 0..32 {
+        let mut tree = any::<Header>().new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+
+        assert_eq!(
+            tree.current(),
+            Header {
+                tag: Tag,
+                version: Version::V1,
+                block_number: BlockNumber(0, 0),
+                pair: (0, [0; 3]),
+            }
+        );
+    }
+}
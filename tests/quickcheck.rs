@@ -0,0 +1,75 @@
+#![cfg(feature = "quickcheck")]
+
+use packtool::{quickcheck::Arbitrary, Packed};
+
+#[derive(Packed, Debug, Clone, PartialEq, Eq)]
+#[packed(value = b"check", quickcheck)]
+struct Tag;
+
+#[derive(Packed, Debug, Clone, PartialEq, Eq)]
+#[packed(quickcheck)]
+#[repr(u16)]
+enum Version {
+    V1 = 0x0100,
+    V2 = 0x0200,
+    V3 = 0x0F00,
+}
+
+#[derive(Packed, Debug, Clone, PartialEq, Eq)]
+#[packed(quickcheck)]
+struct BlockNumber(u32, i64);
+
+#[derive(Packed, Debug, Clone, PartialEq, Eq)]
+#[packed(quickcheck)]
+struct Header {
+    tag: Tag,
+    version: Version,
+    block_number: BlockNumber,
+    pair: (u8, [u8; 3]),
+}
+
+#[test]
+fn roundtrip() {
+    packtool::testing::roundtrip::<Tag>();
+    packtool::testing::roundtrip::<Version>();
+    packtool::testing::roundtrip::<BlockNumber>();
+    packtool::testing::roundtrip::<Header>();
+}
+
+#[test]
+fn shrink_unit() {
+    assert_eq!(Tag.shrink().count(), 0);
+}
+
+#[test]
+fn shrink_enum() {
+    assert_eq!(Version::V1.shrink().count(), 0);
+    assert_eq!(
+        Version::V3.shrink().collect::<Vec<_>>(),
+        vec![Version::V1, Version::V2]
+    );
+}
+
+#[test]
+fn shrink_struct() {
+    let header = Header {
+        tag: Tag,
+        version: Version::V2,
+        block_number: BlockNumber(0, 0),
+        pair: (0, [0; 3]),
+    };
+
+    // only the version can be shrunk
+    assert_eq!(
+        header.shrink().collect::<Vec<_>>(),
+        vec![Header {
+            version: Version::V1,
+            ..header.clone()
+        }]
+    );
+
+    let block_number = BlockNumber(2, 0);
+    assert!(block_number
+        .shrink()
+        .all(|BlockNumber(epoch, slot)| epoch < 2 && slot == 0));
+}
@@ -0,0 +1,90 @@
+use packtool::{Packed, Segment, View};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = b"tag")]
+struct Tag;
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Version {
+    V1 = 1,
+    V2 = 2,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Versions(Version, Version);
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Header {
+    tag: Tag,
+    version: Version,
+    value: u32,
+    versions: Versions,
+}
+
+const VALID: &[u8] = b"tag\x01\x2a\x00\x00\x00\x01\x02";
+const INVALID: &[u8] = b"taG\x03\x2a\x00\x00\x00\x01\x04";
+
+#[test]
+fn valid() {
+    let report = View::<Header>::validate_all(VALID);
+
+    assert!(report.is_valid());
+    assert!(report.issues().is_empty());
+}
+
+#[test]
+fn all_issues_reported() {
+    let report = View::<Header>::validate_all(INVALID);
+
+    assert!(!report.is_valid());
+
+    let issues = report.issues();
+    assert_eq!(issues.len(), 3);
+
+    assert_eq!(issues[0].offset(), 0);
+    assert_eq!(issues[0].path(), &[Segment::Field("tag")]);
+
+    assert_eq!(issues[1].offset(), 3);
+    assert_eq!(issues[1].path(), &[Segment::Field("version")]);
+    assert_eq!(
+        issues[1].error().to_string(),
+        "Invalid discriminant for report::Version, received 3 while expecting one of: [ 1, 2, ]"
+    );
+
+    assert_eq!(issues[2].offset(), 9);
+    assert_eq!(
+        issues[2].path(),
+        &[Segment::Field("versions"), Segment::Index(1)]
+    );
+
+    // the fail fast path still reports the first error only
+    let error = View::<Header>::try_from_slice(INVALID).unwrap_err();
+    assert!(error
+        .to_string()
+        .ends_with("Field tag of report::Tag is not valid"));
+}
+
+#[test]
+fn invalid_size() {
+    let report = View::<Header>::validate_all(&INVALID[..4]);
+    assert_eq!(report.issues().len(), 1);
+
+    let mut longer = INVALID.to_vec();
+    longer.push(0);
+    let report = View::<Header>::validate_all(&longer);
+    assert_eq!(report.issues().len(), 4);
+}
+
+#[test]
+fn display() {
+    let report = View::<Header>::validate_all(INVALID);
+
+    assert_eq!(
+        report.to_string(),
+        "3 issue(s) found in report::Header\n  \
+         at byte 0 (tag): Assumption `slice == b\"tag\"` failed for report::Tag: Invalid string, expected [116, 97, 103] but received [116, 97, 71]\n  \
+         at byte 3 (version): Invalid discriminant for report::Version, received 3 while expecting one of: [ 1, 2, ]\n  \
+         at byte 9 (versions.1): Invalid discriminant for report::Version, received 4 while expecting one of: [ 1, 2, ]"
+    );
+}
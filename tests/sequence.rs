@@ -0,0 +1,76 @@
+use packtool::{Error, Packed, Records, Sequence};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Kind {
+    Leaf = 1,
+    Node = 2,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Entry {
+    kind: Kind,
+    value: u16,
+}
+
+const ENTRIES: &[u8] = &[1, 1, 0, 2, 2, 0, 1, 3, 0];
+
+#[test]
+fn sequence() {
+    let entries = Sequence::<Entry>::try_from_slice(ENTRIES).unwrap();
+
+    assert_eq!(entries.len(), 3);
+    assert!(entries.get(3).is_none());
+    assert_eq!(Entry::kind(entries.get(1).unwrap()).unpack(), Kind::Node);
+
+    let values = entries
+        .iter()
+        .rev()
+        .map(|entry| Entry::value(entry).unpack())
+        .collect::<Vec<_>>();
+    assert_eq!(values, vec![3, 2, 1]);
+}
+
+#[test]
+fn empty() {
+    let entries = Sequence::<Entry>::try_from_slice(&[]).unwrap();
+    assert!(entries.is_empty());
+    assert_eq!(entries.iter().count(), 0);
+}
+
+#[test]
+fn truncated() {
+    let error = Sequence::<Entry>::try_from_slice(&ENTRIES[..7]).unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::Truncated {
+            records: 2,
+            remaining: 1,
+            ..
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "Truncated record of sequence::Entry: 1 trailing byte(s) after 2 record(s)"
+    );
+}
+
+#[test]
+fn lazy() {
+    let mut slice = ENTRIES[..8].to_vec();
+    slice[3] = 4;
+
+    let mut records = Records::<Entry>::new(&slice);
+    assert_eq!(records.len(), 3);
+
+    assert!(records.next().unwrap().is_ok());
+    assert!(records.next().unwrap().is_err());
+    assert!(matches!(
+        records.next().unwrap(),
+        Err(Error::Truncated { records: 2, .. })
+    ));
+    assert!(records.next().is_none());
+
+    assert!(Sequence::<Entry>::try_from_slice(&slice).is_err());
+}
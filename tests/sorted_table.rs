@@ -0,0 +1,82 @@
+use packtool::{Error, Packed, SortedTable, SortedTableBuilder, View};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Header {
+    block_number: u32,
+    parent: u8,
+}
+
+fn block_number(header: View<'_, Header>) -> u32 {
+    Header::block_number(header).unpack()
+}
+
+fn headers(numbers: &[u32]) -> Vec<u8> {
+    let mut builder = SortedTableBuilder::new(block_number);
+    for (parent, number) in numbers.iter().enumerate() {
+        builder.push(&Header {
+            block_number: *number,
+            parent: parent as u8,
+        });
+    }
+    builder.finish().into_bytes()
+}
+
+fn numbers<'a>(sequence: impl IntoIterator<Item = View<'a, Header>>) -> Vec<u32> {
+    sequence.into_iter().map(block_number).collect()
+}
+
+#[test]
+fn lookups() {
+    // 256 and 1 are out of order when comparing the little endian bytes
+    let bytes = headers(&[512, 1, 256, 7, 256]);
+    let table = SortedTable::try_from_slice(&bytes, block_number).unwrap();
+
+    assert_eq!(table.len(), 5);
+    assert_eq!(numbers(table.iter()), vec![1, 7, 256, 256, 512]);
+
+    assert_eq!(table.get(&256).unwrap().unpack().parent, 2);
+    assert!(table.get(&0).is_none());
+    assert!(table.get(&300).is_none());
+    assert!(table.get(&1000).is_none());
+
+    assert_eq!(table.lower_bound(&256), 2);
+    assert_eq!(table.upper_bound(&256), 4);
+    assert_eq!(table.lower_bound(&1000), 5);
+
+    assert_eq!(numbers(table.range(7..512)), vec![7, 256, 256]);
+    assert_eq!(numbers(table.range(7..=512)), vec![7, 256, 256, 512]);
+    assert_eq!(numbers(table.range(..256)), vec![1, 7]);
+    assert_eq!(numbers(table.range(300..)), vec![512]);
+    assert!(table.range(600..).is_empty());
+    #[allow(clippy::reversed_empty_ranges)]
+    let empty = table.range(300..2);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn not_sorted() {
+    let mut bytes = headers(&[1, 2, 3]);
+    bytes.swap(0, 5);
+
+    let error = SortedTable::try_from_slice(&bytes, block_number).unwrap_err();
+    assert!(matches!(error, Error::NotSorted { index: 1, .. }));
+}
+
+#[test]
+fn invalid_records() {
+    let bytes = headers(&[1, 2, 3]);
+
+    assert!(matches!(
+        SortedTable::try_from_slice(&bytes[..bytes.len() - 1], block_number),
+        Err(Error::Truncated { .. })
+    ));
+}
+
+#[test]
+fn empty() {
+    let table = SortedTable::try_from_slice(&[], block_number).unwrap();
+
+    assert!(table.is_empty());
+    assert!(table.get(&1).is_none());
+    assert!(table.range(..).is_empty());
+}
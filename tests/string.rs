@@ -0,0 +1,110 @@
+use packtool::{
+    string::{Ascii, SpacePadded, Utf16Be, Utf8Lossy},
+    CStr, Error, FixedStr, Packed, Packet, Utf16Str, View,
+};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct Entry {
+    name: CStr<8>,
+    kind: FixedStr<4, Ascii, SpacePadded>,
+    label: Utf16Str<6, Utf16Be>,
+}
+
+const ENTRY: &[u8] = b"file\0\xFF\xFF\xFFTXT \0a\0b\0\0";
+
+#[test]
+fn view() {
+    let entry = View::<Entry>::try_from_slice(ENTRY).unwrap();
+
+    let name: &str = Entry::name(entry).as_str();
+    assert_eq!(name, "file");
+    assert_eq!(Entry::kind(entry).as_str(), "TXT");
+    assert_eq!(Entry::label(entry).decode(), "ab");
+
+    // the string is borrowed from the slice
+    assert_eq!(name.as_ptr(), ENTRY.as_ptr());
+}
+
+#[test]
+fn round_trip() {
+    let entry = Entry {
+        name: CStr::new("file").unwrap(),
+        kind: FixedStr::new("TXT").unwrap(),
+        label: Utf16Str::new("ab").unwrap(),
+    };
+    let packet = Packet::pack(&entry);
+
+    // the garbage after the terminator is not written back
+    assert_eq!(packet.as_ref(), b"file\0\0\0\0TXT \0a\0b\0\0");
+    assert_eq!(
+        View::<Entry>::try_from_slice(ENTRY).unwrap().unpack(),
+        entry
+    );
+}
+
+#[test]
+fn invalid() {
+    // not ascii
+    assert!(matches!(
+        FixedStr::<4, Ascii, SpacePadded>::check(b"\xC3\xA9  "),
+        Err(Error::InvalidString { .. })
+    ));
+    // not utf8
+    assert!(matches!(
+        CStr::<4>::check(b"\xFF\0\0\0"),
+        Err(Error::InvalidString { .. })
+    ));
+    // garbage after the NUL padding
+    assert!(matches!(
+        FixedStr::<4>::check(b"a\0b\0"),
+        Err(Error::InvalidString { .. })
+    ));
+    // unpaired surrogate
+    assert!(matches!(
+        Utf16Str::<2, Utf16Be>::check(&[0xDC, 0x00]),
+        Err(Error::InvalidString { .. })
+    ));
+}
+
+#[test]
+fn lossy() {
+    let bytes = b"caf\xE9\0\xFF\xFF\xFF";
+    assert!(CStr::<8>::check(bytes).is_err());
+
+    let name = View::<CStr<8, Utf8Lossy>>::try_from_slice(bytes).unwrap();
+    assert_eq!(name.content(), b"caf\xE9");
+    assert_eq!(name.decode(), "caf\u{FFFD}");
+}
+
+#[test]
+fn too_long() {
+    assert!(matches!(
+        CStr::<4>::new("abcde"),
+        Err(Error::StringTooLong {
+            length: 5,
+            capacity: 4,
+            ..
+        })
+    ));
+    assert!(matches!(
+        Utf16Str::<4>::new("abc"),
+        Err(Error::StringTooLong {
+            length: 6,
+            capacity: 4,
+            ..
+        })
+    ));
+    // a full C string has no terminator
+    assert_eq!(CStr::<4>::new("abcd").unwrap().as_str(), "abcd");
+}
+
+#[test]
+fn display() {
+    let label = Utf16Str::<8>::new("été").unwrap();
+    assert_eq!(label.to_string(), "été");
+    assert_eq!(format!("{:?}", label), "\"été\"");
+    assert_eq!(
+        FixedStr::<4, Ascii, SpacePadded>::default().as_bytes(),
+        b"    "
+    );
+}
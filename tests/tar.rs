@@ -0,0 +1,270 @@
+#![cfg(feature = "tar")]
+
+use packtool::{
+    formats::tar::{Archive, Entry, EntryType, Header, Metadata, Writer, BLOCK},
+    Error,
+};
+
+const USTAR: &[u8] = include_bytes!("fixtures/tar/ustar.tar");
+const GNU: &[u8] = include_bytes!("fixtures/tar/gnu.tar");
+const PAX: &[u8] = include_bytes!("fixtures/tar/pax.tar");
+
+const LONG_DIRECTORY: &str =
+    "archive/this-is-a-rather-long-directory-name-used-to-exceed-the-hundred-bytes/of-the-ustar-name-field/";
+
+fn entries(archive: &[u8]) -> Vec<Entry<'_>> {
+    Archive::new(archive)
+        .entries()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+/// the fixtures are created with GNU tar from the same directory:
+///
+/// ```text
+/// tar --sort=name --owner=0 --group=0 --numeric-owner \
+///     --mtime=2021-05-03T00:00:00Z -b 1 --format=<format> -cf <format>.tar archive
+/// ```
+///
+/// the `pax` one with `--pax-option=delete=atime,delete=ctime`
+fn check_fixture(archive: &[u8]) {
+    let entries = entries(archive);
+    let summary = entries
+        .iter()
+        .map(|entry| (entry.path(), entry.entry_type(), entry.size()))
+        .collect::<Vec<_>>();
+    let nested = format!("{}nested.txt", LONG_DIRECTORY);
+
+    assert_eq!(
+        summary,
+        vec![
+            ("archive/", EntryType::Directory, 0),
+            ("archive/data.bin", EntryType::Regular, 600),
+            ("archive/hello.txt", EntryType::Regular, 13),
+            ("archive/link", EntryType::Symlink, 0),
+            (
+                "archive/this-is-a-rather-long-directory-name-used-to-exceed-the-hundred-bytes/",
+                EntryType::Directory,
+                0
+            ),
+            (LONG_DIRECTORY, EntryType::Directory, 0),
+            (nested.as_str(), EntryType::Regular, 7),
+        ]
+    );
+
+    for entry in entries.iter() {
+        assert_eq!(entry.uid(), 0);
+        assert_eq!(entry.gid(), 0);
+        assert_eq!(entry.mtime(), 1_620_000_000);
+    }
+
+    assert_eq!(entries[0].mode(), 0o755);
+    assert_eq!(entries[1].mode(), 0o644);
+    assert!(entries[1]
+        .data()
+        .iter()
+        .enumerate()
+        .all(|(index, byte)| *byte as usize == index % 251));
+    assert_eq!(entries[2].data(), b"hello, world\n");
+    assert_eq!(entries[3].link_name(), "hello.txt");
+    assert_eq!(entries[6].data(), b"nested\n");
+}
+
+/// write the checksum of the first header of the `archive`, after
+/// editing it
+fn update_checksum(archive: &mut [u8]) {
+    let checksum = format!("{:06o}\0 ", Header::compute_checksum(&archive[..BLOCK]));
+    archive[148..156].copy_from_slice(checksum.as_bytes());
+}
+
+#[test]
+fn ustar() {
+    check_fixture(USTAR);
+}
+
+#[test]
+fn gnu_long_names() {
+    check_fixture(GNU);
+}
+
+#[test]
+fn pax_extended_headers() {
+    check_fixture(PAX);
+}
+
+#[test]
+fn checksum() {
+    let mut archive = USTAR.to_vec();
+    // the mode of the first entry: 0755 -> 0757
+    archive[106] = b'7';
+
+    let mut entries = Archive::new(&archive).entries();
+    assert!(matches!(
+        entries.next(),
+        Some(Err(Error::Assumption { .. }))
+    ));
+    assert!(entries.next().is_none());
+}
+
+#[test]
+fn invalid_octal() {
+    let mut archive = USTAR.to_vec();
+    archive[124] = b'9';
+
+    assert!(Archive::new(&archive).entries().next().unwrap().is_err());
+}
+
+#[test]
+fn truncated() {
+    let archive = &USTAR[..2 * BLOCK + 100];
+    let results = Archive::new(archive).entries().collect::<Vec<_>>();
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(Error::OutOfBounds { .. })));
+}
+
+#[test]
+fn round_trip() {
+    let long_path = format!("{}{}", "d/".repeat(100), "file.txt");
+    let long_name = "f".repeat(200);
+
+    let mut file = Metadata::file("hello.txt");
+    file.uid = 0o7777777 + 1;
+    file.mtime = 1_620_000_000;
+    file.user_name = "packtool".to_owned();
+
+    let mut writer = Writer::new(Vec::new());
+    writer.append(&Metadata::directory("d"), &[]).unwrap();
+    writer.append(&file, b"hello").unwrap();
+    writer
+        .append(&Metadata::file(long_path.clone()), &[1; 1000])
+        .unwrap();
+    writer
+        .append(&Metadata::file(long_name.clone()), &[])
+        .unwrap();
+    writer
+        .append(&Metadata::symlink("link", long_name.clone()), &[])
+        .unwrap();
+    let archive = writer.finish().unwrap();
+    assert_eq!(archive.len() % BLOCK, 0);
+
+    let entries = entries(&archive);
+    assert_eq!(entries.len(), 5);
+
+    assert_eq!(entries[0].path(), "d/");
+    assert_eq!(entries[0].entry_type(), EntryType::Directory);
+
+    assert_eq!(entries[1].path(), "hello.txt");
+    assert_eq!(entries[1].uid(), 0o7777777 + 1);
+    assert_eq!(entries[1].mtime(), 1_620_000_000);
+    assert_eq!(entries[1].data(), b"hello");
+
+    // split in the prefix and the name of the header
+    assert_eq!(entries[2].path(), long_path);
+    assert_eq!(entries[2].data(), &[1; 1000][..]);
+
+    // written in a PAX extended header
+    assert_eq!(entries[3].path(), long_name);
+    assert_eq!(entries[4].link_name(), long_name);
+}
+
+#[test]
+fn pax_global_headers() {
+    let mut writer = Writer::new(Vec::new());
+    let mut global = Metadata::file("global");
+    global.entry_type = EntryType::PaxGlobal;
+    writer.append(&global, b"12 uid=1000\n").unwrap();
+    writer.append(&Metadata::file("a"), &[]).unwrap();
+    writer.append(&Metadata::file("b"), &[]).unwrap();
+    let archive = writer.finish().unwrap();
+
+    let entries = entries(&archive);
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|entry| entry.uid() == 1000));
+}
+
+#[test]
+fn invalid_metadata() {
+    let mut writer = Writer::new(Vec::new());
+    assert!(writer.append(&Metadata::file(""), &[]).is_err());
+    assert!(writer.append(&Metadata::file("a\0b"), &[]).is_err());
+}
+
+#[test]
+fn invalid_pax_records() {
+    let records: &[&[u8]] = &[
+        b"0 ",
+        // the length does not cover the length itself
+        b"1 path=a\n",
+        b"9 path=ab",
+        b"99 path=a\n",
+        b"path=a\n",
+    ];
+
+    for records in records {
+        let mut writer = Writer::new(Vec::new());
+        let mut extended = Metadata::file("extended");
+        extended.entry_type = EntryType::PaxExtended;
+        writer.append(&extended, records).unwrap();
+        writer.append(&Metadata::file("a"), &[]).unwrap();
+        let archive = writer.finish().unwrap();
+
+        let mut entries = Archive::new(&archive).entries();
+        assert!(
+            matches!(entries.next(), Some(Err(Error::Message(_)))),
+            "{:?}",
+            String::from_utf8_lossy(records)
+        );
+        assert!(entries.next().is_none());
+    }
+}
+
+#[test]
+fn v7_headers() {
+    let mut writer = Writer::new(Vec::new());
+    writer.append(&Metadata::file("a.txt"), b"hello").unwrap();
+    let mut archive = writer.finish().unwrap();
+
+    // the v7 headers end with the link name, the magic is not set
+    archive[257..BLOCK].iter_mut().for_each(|byte| *byte = 0);
+    update_checksum(&mut archive);
+
+    let entries = entries(&archive);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].path(), "a.txt");
+    assert_eq!(entries[0].data(), b"hello");
+}
+
+#[test]
+fn unknown_entry_types() {
+    let mut writer = Writer::new(Vec::new());
+    writer.append(&Metadata::file("sparse"), &[]).unwrap();
+    let mut archive = writer.finish().unwrap();
+
+    archive[156] = b'S';
+    update_checksum(&mut archive);
+
+    let mut entries = Archive::new(&archive).entries();
+    assert!(entries.next().unwrap().is_err());
+    assert!(entries.next().is_none());
+}
+
+#[test]
+fn non_utf8_names() {
+    let mut writer = Writer::new(Vec::new());
+    writer.append(&Metadata::file("cafe"), b"latin-1").unwrap();
+    writer.append(&Metadata::file("next"), &[]).unwrap();
+    let mut archive = writer.finish().unwrap();
+
+    // `café` in latin-1, as written by GNU tar in such a locale
+    archive[3] = 0xE9;
+    update_checksum(&mut archive);
+
+    let entries = entries(&archive);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].path_bytes(), b"caf\xE9");
+    assert_eq!(entries[0].path(), "caf\u{FFFD}");
+    assert_eq!(entries[0].data(), b"latin-1");
+    assert_eq!(entries[1].path(), "next");
+}
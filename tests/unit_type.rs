@@ -29,6 +29,12 @@ pub struct TagBytes;
 const BYTES_SLICE: &[u8] = b"bytes";
 const BAD_BYTES_SLICE: &[u8] = b"about";
 
+#[derive(Packed, PartialEq, Eq, Debug)]
+#[packed(value = b'a')]
+pub struct TagByte;
+const BYTE_SLICE: &[u8] = b"a";
+const BAD_BYTE_SLICE: &[u8] = b"b";
+
 #[derive(Packed, PartialEq, Eq, Debug)]
 #[packed(value = 'a')]
 pub struct TagChar;
@@ -166,6 +172,12 @@ mk_test!(
     [!INVALID_SLICE "Invalid size for unit_type::TagBytes: expected 5 bytes but received 30 bytes"],
     [!BAD_BYTES_SLICE "Assumption `slice == b\"bytes\"` failed for unit_type::TagBytes: Invalid string, expected [98, 121, 116, 101, 115] but received [97, 98, 111, 117, 116]"]
 ));
+mk_test!(
+    TagByte, byte<TagByte>(
+    [BYTE_SLICE],
+    [!INVALID_SLICE "Invalid size for unit_type::TagByte: expected 1 bytes but received 30 bytes"],
+    [!BAD_BYTE_SLICE "Assumption `byte == b'a'` failed for unit_type::TagByte: Invalid byte, expected 0x61 but received 0x62"]
+));
 mk_test!(
     TagChar, char<TagChar>(
     [CHAR_SLICE],
@@ -0,0 +1,159 @@
+use packtool::{Packed, PackedVersions, Packet, View};
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(value = b"hd")]
+struct Magic;
+
+#[derive(Packed, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(u8)]
+enum Version {
+    V1 = 1,
+    V2 = 2,
+    V3 = 3,
+    V4 = 4,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+struct HeaderV1 {
+    magic: Magic,
+    version: Version,
+    length: u16,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(upgrade(HeaderV1))]
+struct HeaderV2 {
+    magic: Magic,
+    #[packed(version(Version::V2))]
+    version: Version,
+    length: u32,
+    #[packed(default)]
+    checksum: u32,
+}
+
+#[derive(Packed, Debug, PartialEq, Eq)]
+#[packed(upgrade(HeaderV2))]
+struct HeaderV3 {
+    magic: Magic,
+    #[packed(version(Version::V3))]
+    version: Version,
+    length: u32,
+    checksum: u32,
+    #[packed(default = "[0xff; 2]")]
+    flags: [u8; 2],
+}
+
+#[derive(PackedVersions, Debug)]
+#[packed(tag(Version), tag_offset = 2, upgrade)]
+enum AnyHeader<'a> {
+    #[packed(version(Version::V1))]
+    V1(View<'a, HeaderV1>),
+    #[packed(version(Version::V2))]
+    V2(View<'a, HeaderV2>),
+    #[packed(version(Version::V3))]
+    V3(View<'a, HeaderV3>),
+}
+
+#[test]
+fn dispatch_on_the_tag() {
+    let v1 = Packet::pack(&HeaderV1 {
+        magic: Magic,
+        version: Version::V1,
+        length: 42,
+    });
+    let v2 = Packet::pack(&HeaderV2 {
+        magic: Magic,
+        version: Version::V2,
+        length: 42,
+        checksum: 7,
+    });
+
+    let header = AnyHeader::try_from_slice(v1.view().as_slice()).unwrap();
+    assert!(matches!(header, AnyHeader::V1(_)));
+    assert_eq!(header.tag(), Version::V1);
+    assert_eq!(header.as_slice(), v1.view().as_slice());
+
+    let header = AnyHeader::try_from_slice(v2.view().as_slice()).unwrap();
+    assert!(matches!(header, AnyHeader::V2(view) if HeaderV2::checksum(view).unpack() == 7));
+    assert_eq!(header.tag(), Version::V2);
+}
+
+#[test]
+fn upgrade_to_the_latest_version() {
+    let v1 = Packet::pack(&HeaderV1 {
+        magic: Magic,
+        version: Version::V1,
+        length: 42,
+    });
+
+    let header = AnyHeader::try_from_slice(v1.view().as_slice()).unwrap();
+    assert_eq!(
+        header.upgrade(),
+        HeaderV3 {
+            magic: Magic,
+            version: Version::V3,
+            length: 42,
+            checksum: 0,
+            flags: [0xff; 2],
+        }
+    );
+
+    let v3 = HeaderV3::from(HeaderV2::from(v1.unpack()));
+    assert_eq!(v3.length, 42);
+    assert_eq!(v3.version, Version::V3);
+}
+
+#[test]
+fn upgraded_records_are_read_back() {
+    let v1 = Packet::pack(&HeaderV1 {
+        magic: Magic,
+        version: Version::V1,
+        length: 42,
+    });
+
+    let v2 = Packet::pack(&HeaderV2::from(v1.unpack()));
+    let header = AnyHeader::try_from_slice(v2.as_ref()).unwrap();
+    assert_eq!(header.tag(), Version::V2);
+    assert!(matches!(header, AnyHeader::V2(view) if HeaderV2::length(view).unpack() == 42));
+
+    let upgraded = AnyHeader::try_from_slice(v1.as_ref()).unwrap().upgrade();
+    let v3 = Packet::pack(&upgraded);
+    let header = AnyHeader::try_from_slice(v3.as_ref()).unwrap();
+    assert_eq!(header.tag(), Version::V3);
+    assert_eq!(header.upgrade(), upgraded);
+}
+
+#[test]
+fn from_view() {
+    let v1 = Packet::pack(&HeaderV1 {
+        magic: Magic,
+        version: Version::V1,
+        length: 1,
+    });
+
+    let header = AnyHeader::from(v1.view());
+    assert!(matches!(header, AnyHeader::V1(_)));
+}
+
+#[test]
+fn invalid_records() {
+    // too short to hold the tag
+    let error = AnyHeader::try_from_slice(b"hd").unwrap_err();
+    assert!(error.to_string().starts_with("Invalid size"));
+
+    // the tag is not a valid `Version`
+    assert!(AnyHeader::try_from_slice(b"hd\x09\x2a\x00").is_err());
+
+    // valid tag, but no matching version in the family
+    let error = AnyHeader::try_from_slice(b"hd\x04\x2a\x00").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid discriminant for versions::AnyHeader<'_>, received [4] while expecting one of: [ Version::V1, Version::V2, Version::V3, ]"
+    );
+
+    // the tag says V2 but the slice has the size of a V1
+    assert!(AnyHeader::try_from_slice(b"hd\x02\x2a\x00").is_err());
+
+    // the rest of the record is still checked
+    assert!(AnyHeader::try_from_slice(b"HD\x01\x2a\x00").is_err());
+}